@@ -0,0 +1,110 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, LitStr};
+
+#[proc_macro_derive(DntRecord, attributes(dnt))]
+pub fn derive_dnt_record(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match expand(input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+fn expand(input: DeriveInput) -> Result<proc_macro2::TokenStream, Error> {
+    let name = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new_spanned(
+                    name,
+                    "DntRecord can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                name,
+                "DntRecord can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut columns = vec![];
+    let mut from_values = vec![];
+    let mut to_values = vec![];
+
+    for (index, field) in fields.iter().enumerate() {
+        let ident = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+        let column = column_name(field)?.unwrap_or_else(|| ident.to_string());
+
+        columns.push(quote! {
+            (#column, <#ty as ::dnt_file_reader_writer::DntField>::DATA_TYPE)
+        });
+        from_values.push(quote! {
+            #ident: <#ty as ::dnt_file_reader_writer::DntField>::from_value(values[#index])?
+        });
+        to_values.push(quote! {
+            ::dnt_file_reader_writer::DntField::to_value(&self.#ident)
+        });
+    }
+
+    let count = fields.len();
+
+    Ok(quote! {
+        impl #impl_generics ::dnt_file_reader_writer::DntRecord for #name #type_generics #where_clause {
+            fn columns() -> ::std::vec::Vec<(&'static str, ::dnt_file_reader_writer::DntDataType)> {
+                ::std::vec![#(#columns),*]
+            }
+
+            fn from_values(
+                values: &[&::dnt_file_reader_writer::DntValue],
+            ) -> ::std::result::Result<Self, ::dnt_file_reader_writer::DntError> {
+                if values.len() != #count {
+                    return ::std::result::Result::Err(
+                        ::dnt_file_reader_writer::DntError::InvalidValue(::std::format!(
+                            "expected {} values, got {}",
+                            #count,
+                            values.len()
+                        )),
+                    );
+                }
+
+                ::std::result::Result::Ok(Self {
+                    #(#from_values),*
+                })
+            }
+
+            fn to_values(&self) -> ::std::vec::Vec<::dnt_file_reader_writer::DntValue> {
+                ::std::vec![#(#to_values),*]
+            }
+        }
+    })
+}
+
+fn column_name(field: &syn::Field) -> Result<Option<String>, Error> {
+    let mut name = None;
+
+    for attribute in &field.attrs {
+        if !attribute.path().is_ident("dnt") {
+            continue;
+        }
+
+        attribute.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                let value: LitStr = meta.value()?.parse()?;
+                name = Some(value.value());
+                Ok(())
+            } else {
+                Err(meta.error("unsupported dnt attribute"))
+            }
+        })?;
+    }
+
+    Ok(name)
+}
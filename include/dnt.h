@@ -0,0 +1,44 @@
+#ifndef DNT_H
+#define DNT_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* A table read into memory. Strings returned for it stay valid until the
+ * next call on the same table, or until it is freed. */
+typedef struct DntFfiTable DntFfiTable;
+
+/* Reads a table. `encoding` is a WHATWG label such as "euc-kr", or NULL for
+ * Latin-1. Returns NULL on failure; see dnt_last_error. */
+DntFfiTable *dnt_table_open(const char *path, const char *encoding);
+void dnt_table_free(DntFfiTable *table);
+
+size_t dnt_table_row_count(const DntFfiTable *table);
+/* The number of columns, including the id. */
+size_t dnt_table_column_count(const DntFfiTable *table);
+const char *dnt_table_column_name(const DntFfiTable *table, size_t column);
+/* 1 for strings, 2 for uint32, 3 for int32, and 4 or 5 for float.
+ * 0 if there is no such column. */
+uint8_t dnt_table_column_type(const DntFfiTable *table, size_t column);
+
+/* Any cell as text. NULL if the cell doesn't exist. */
+const char *dnt_table_cell_string(DntFfiTable *table, size_t row, size_t column);
+bool dnt_table_cell_int(const DntFfiTable *table, size_t row, size_t column, int64_t *value);
+bool dnt_table_cell_float(const DntFfiTable *table, size_t row, size_t column, double *value);
+
+/* Writes the table in the encoding it was opened with. */
+bool dnt_table_write(const DntFfiTable *table, const char *path);
+
+/* The message of the last error on this thread, or NULL. */
+const char *dnt_last_error(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
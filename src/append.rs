@@ -0,0 +1,136 @@
+use std::{
+    io::{Read, Seek, SeekFrom, Write},
+    mem,
+};
+
+use crate::{check_rows, DntColumn, DntError, DntFileReader, DntFileWriter, DntRow, Latin1Codec};
+
+/// Offset of the row count in the header, after the magic and the column
+/// count.
+const ROWS_NB_OFFSET: u64 = 6;
+
+impl<W: Read + Write + Seek> DntFileWriter<W> {
+    /// Adds rows to the end of an existing table without rewriting it. The
+    /// rows are written over the footer, which is written again after them
+    /// together with any bytes that followed it, and the row count in the
+    /// header is updated. Extra header bytes are only expected if they were
+    /// set with `with_extra_header` or `with_profile`.
+    pub fn append(&mut self, rows: &[DntRow]) -> Result<(), DntError> {
+        self.writer.flush()?;
+
+        let codec = mem::replace(&mut self.codec, Box::new(Latin1Codec));
+        let mut reader = DntFileReader::new(self.writer.get_mut())
+            .with_unknown_width(self.unknown_width)
+            .with_endianness(self.endianness)
+            .with_extra_header_length(
+                self.extra_header_length
+                    .or(self.extra_header.as_ref().map(Vec::len))
+                    .unwrap_or(0),
+            );
+        reader.codec = codec;
+        reader.options.footer = self.options.footer.clone();
+        reader.type_codes = self.type_codes.clone().unwrap_or_default();
+
+        let found = reader.find_body_end();
+        self.codec = mem::replace(&mut reader.codec, Box::new(Latin1Codec));
+        let (head, rows_nb, body_end, trailing) = found?;
+
+        check_rows(&head, rows)?;
+        let rows_nb = u32::try_from(rows.len())
+            .ok()
+            .and_then(|added| rows_nb.checked_add(added))
+            .ok_or_else(|| DntError::InvalidHeader(String::from("too many rows")))?;
+
+        self.payload = None;
+        self.written = 0;
+
+        self.writer.seek(SeekFrom::Start(ROWS_NB_OFFSET))?;
+        self.write_u32(rows_nb)?;
+
+        self.writer.seek(SeekFrom::Start(body_end))?;
+        let total_rows = rows.len() as u32;
+        self.report_progress(0, total_rows);
+        for (index, row) in rows.iter().enumerate() {
+            self.write_row(&head, row)?;
+            self.report_progress(index as u32 + 1, total_rows);
+        }
+        self.write_footer()?;
+        self.writer.write_all(&trailing)?;
+
+        self.writer.flush()?;
+
+        Ok(())
+    }
+}
+
+impl<R: Read + Seek> DntFileReader<R> {
+    /// Skips over every row and checks the footer, returning the columns, the
+    /// row count, where the rows end and the bytes after the footer.
+    fn find_body_end(&mut self) -> Result<(Vec<DntColumn>, u32, u64, Vec<u8>), DntError> {
+        let header = self.read_header()?;
+
+        if self.inflated.is_some() {
+            return Err(DntError::InvalidHeader(String::from(
+                "compressed files cannot be appended to",
+            )));
+        }
+
+        for _ in 0..header.rows_nb {
+            for column in &header.head {
+                self.skip_value(column.data_type)?;
+            }
+        }
+
+        let body_end = self.position()?;
+        self.check_footer()?;
+
+        let mut trailing = vec![];
+        self.input().read_to_end(&mut trailing)?;
+
+        Ok((header.head, header.rows_nb, body_end, trailing))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use crate::{DntDataType, DntFileWriter, DntTable, DntTableBuilder};
+
+    fn table(rows: i32) -> DntTable {
+        let mut builder = DntTableBuilder::new()
+            .column("_Name", DntDataType::String)
+            .column("_Level", DntDataType::UInt32);
+        for id in 1..=rows {
+            builder = builder.row((id, format!("Item {}", id), id as u32 * 10));
+        }
+        builder.build().unwrap()
+    }
+
+    #[test]
+    fn writes_what_writing_the_whole_table_would() {
+        let mut writer = DntFileWriter::new(Cursor::new(vec![]));
+        writer.write(&table(2)).unwrap();
+        writer.append(&table(5).body[2..]).unwrap();
+        writer.append(&[]).unwrap();
+
+        let bytes = writer.into_inner().unwrap().into_inner();
+        assert_eq!(bytes, table(5).to_bytes().unwrap());
+    }
+
+    #[test]
+    fn checks_the_rows_against_the_file() {
+        let mut writer = DntFileWriter::new(Cursor::new(vec![]));
+        writer.write(&table(2)).unwrap();
+
+        let other = DntTableBuilder::new()
+            .column("_Name", DntDataType::String)
+            .row((3, "Item 3"))
+            .build()
+            .unwrap();
+        assert!(writer.append(&other.body).is_err());
+
+        let bytes = writer.into_inner().unwrap().into_inner();
+        assert_eq!(bytes, table(2).to_bytes().unwrap());
+    }
+}
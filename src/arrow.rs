@@ -0,0 +1,332 @@
+use std::{collections::HashMap, sync::Arc};
+
+use arrow::{
+    array::{
+        Array, ArrayRef, AsArray, BinaryArray, Float32Array, Float64Array, Int32Array, StringArray,
+        UInt32Array,
+    },
+    datatypes::{DataType, Field, Float32Type, Float64Type, Int32Type, Schema, UInt32Type},
+    record_batch::RecordBatch,
+};
+
+use crate::{DntColumn, DntDataType, DntError, DntRow, DntTable, DntValue};
+
+const RAW_DATA_TYPE_KEY: &str = "dnt_raw_data_type";
+
+impl DntTable {
+    pub fn to_arrow(&self) -> Result<RecordBatch, DntError> {
+        let fields = self
+            .head
+            .iter()
+            .enumerate()
+            .map(|(index, column)| {
+                let nullable = self
+                    .body
+                    .iter()
+                    .any(|row| matches!(row.values.get(index), Some(DntValue::Null)));
+
+                Field::new(&column.text, arrow_type(column.data_type), nullable).with_metadata(
+                    HashMap::from([(
+                        RAW_DATA_TYPE_KEY.to_owned(),
+                        column.raw_data_type.to_string(),
+                    )]),
+                )
+            })
+            .collect::<Vec<Field>>();
+
+        let columns = self
+            .head
+            .iter()
+            .enumerate()
+            .map(|(index, column)| column_array(self, index, column))
+            .collect::<Result<Vec<ArrayRef>, DntError>>()?;
+
+        Ok(RecordBatch::try_new(
+            Arc::new(Schema::new(fields)),
+            columns,
+        )?)
+    }
+
+    pub fn from_arrow(batch: &RecordBatch) -> Result<DntTable, DntError> {
+        let schema = batch.schema();
+
+        let head = schema
+            .fields()
+            .iter()
+            .map(|field| {
+                let raw_data_type = field
+                    .metadata()
+                    .get(RAW_DATA_TYPE_KEY)
+                    .and_then(|value| value.parse::<u8>().ok());
+
+                let data_type = match (field.data_type(), raw_data_type) {
+                    (DataType::Utf8 | DataType::LargeUtf8, _) => DntDataType::String,
+                    (DataType::UInt32, _) => DntDataType::UInt32,
+                    (DataType::Int32, _) => DntDataType::Int32,
+                    (DataType::Float32, _) => DntDataType::Float32,
+                    (DataType::Float64, _) => DntDataType::Float64,
+                    (DataType::Binary | DataType::LargeBinary, Some(raw_data_type)) => {
+                        DntDataType::from_u8(raw_data_type)
+                    }
+                    (DataType::Binary | DataType::LargeBinary, None) => DntDataType::Unknown(0),
+                    (other, _) => {
+                        return Err(DntError::InvalidHeader(format!(
+                            "unsupported Arrow type for column {}: {}",
+                            field.name(),
+                            other
+                        )))
+                    }
+                };
+
+                let mut column = DntColumn::new(field.name(), data_type);
+                if let Some(raw_data_type) = raw_data_type {
+                    column.raw_data_type = raw_data_type;
+                }
+                Ok(column)
+            })
+            .collect::<Result<Vec<DntColumn>, DntError>>()?;
+
+        if head.first().map(|column| column.data_type) != Some(DntDataType::Int32) {
+            return Err(DntError::InvalidHeader(String::from(
+                "the first Arrow column must be an Int32 id",
+            )));
+        }
+
+        let mut body = (0..batch.num_rows())
+            .map(|_| DntRow {
+                values: Vec::with_capacity(head.len()),
+            })
+            .collect::<Vec<DntRow>>();
+
+        for (index, (column, array)) in head.iter().zip(batch.columns()).enumerate() {
+            if index == 0 && array.null_count() > 0 {
+                return Err(DntError::InvalidValue(format!(
+                    "id column {} contains nulls",
+                    column.text
+                )));
+            }
+
+            for (row_index, (row, value)) in body.iter_mut().zip(array_values(array)?).enumerate() {
+                row.values.push(if array.is_null(row_index) {
+                    DntValue::Null
+                } else {
+                    value
+                });
+            }
+        }
+
+        Ok(DntTable::from_parts(head, body))
+    }
+}
+
+fn array_values(array: &ArrayRef) -> Result<Vec<DntValue>, DntError> {
+    Ok(match array.data_type() {
+        DataType::Utf8 => array
+            .as_string::<i32>()
+            .iter()
+            .map(|value| DntValue::String(value.unwrap_or_default().to_owned()))
+            .collect(),
+        DataType::LargeUtf8 => array
+            .as_string::<i64>()
+            .iter()
+            .map(|value| DntValue::String(value.unwrap_or_default().to_owned()))
+            .collect(),
+        DataType::UInt32 => array
+            .as_primitive::<UInt32Type>()
+            .values()
+            .iter()
+            .map(|value| DntValue::UInt32(*value))
+            .collect(),
+        DataType::Int32 => array
+            .as_primitive::<Int32Type>()
+            .values()
+            .iter()
+            .map(|value| DntValue::Int32(*value))
+            .collect(),
+        DataType::Float32 => array
+            .as_primitive::<Float32Type>()
+            .values()
+            .iter()
+            .map(|value| DntValue::Float32(*value))
+            .collect(),
+        DataType::Float64 => array
+            .as_primitive::<Float64Type>()
+            .values()
+            .iter()
+            .map(|value| DntValue::Float64(*value))
+            .collect(),
+        DataType::Binary => array
+            .as_binary::<i32>()
+            .iter()
+            .map(|value| DntValue::Bytes(value.unwrap_or_default().to_vec()))
+            .collect(),
+        DataType::LargeBinary => array
+            .as_binary::<i64>()
+            .iter()
+            .map(|value| DntValue::Bytes(value.unwrap_or_default().to_vec()))
+            .collect(),
+        other => {
+            return Err(DntError::InvalidHeader(format!(
+                "unsupported Arrow type: {}",
+                other
+            )))
+        }
+    })
+}
+
+fn arrow_type(data_type: DntDataType) -> DataType {
+    match data_type {
+        DntDataType::String => DataType::Utf8,
+        DntDataType::UInt32 => DataType::UInt32,
+        DntDataType::Int32 => DataType::Int32,
+        DntDataType::Float32 => DataType::Float32,
+        DntDataType::Float64 => DataType::Float64,
+        DntDataType::Unknown(_) => DataType::Binary,
+    }
+}
+
+fn column_array(table: &DntTable, index: usize, column: &DntColumn) -> Result<ArrayRef, DntError> {
+    let values = table.body.iter().enumerate().map(|(row_index, row)| {
+        row.values.get(index).ok_or_else(|| {
+            DntError::InvalidValue(format!(
+                "row {} has no value for column {}",
+                row_index, column.text
+            ))
+        })
+    });
+
+    let mismatch = |value: &DntValue| {
+        DntError::InvalidValue(format!(
+            "column {} has type {:?}, got {:?}",
+            column.text, column.data_type, value
+        ))
+    };
+
+    Ok(match column.data_type {
+        DntDataType::String => Arc::new(
+            values
+                .map(|value| match value? {
+                    DntValue::String(value) => Ok(Some(value.as_str())),
+                    DntValue::Null => Ok(None),
+                    other => Err(mismatch(other)),
+                })
+                .collect::<Result<StringArray, DntError>>()?,
+        ),
+        DntDataType::UInt32 => Arc::new(
+            values
+                .map(|value| match value? {
+                    DntValue::UInt32(value) => Ok(Some(*value)),
+                    DntValue::Null => Ok(None),
+                    other => Err(mismatch(other)),
+                })
+                .collect::<Result<UInt32Array, DntError>>()?,
+        ),
+        DntDataType::Int32 => Arc::new(
+            values
+                .map(|value| match value? {
+                    DntValue::Int32(value) => Ok(Some(*value)),
+                    DntValue::Null => Ok(None),
+                    other => Err(mismatch(other)),
+                })
+                .collect::<Result<Int32Array, DntError>>()?,
+        ),
+        DntDataType::Float32 => Arc::new(
+            values
+                .map(|value| match value? {
+                    DntValue::Float32(value) => Ok(Some(*value)),
+                    DntValue::Null => Ok(None),
+                    other => Err(mismatch(other)),
+                })
+                .collect::<Result<Float32Array, DntError>>()?,
+        ),
+        DntDataType::Float64 => Arc::new(
+            values
+                .map(|value| match value? {
+                    DntValue::Float64(value) => Ok(Some(*value)),
+                    DntValue::Null => Ok(None),
+                    other => Err(mismatch(other)),
+                })
+                .collect::<Result<Float64Array, DntError>>()?,
+        ),
+        DntDataType::Unknown(_) => Arc::new(
+            values
+                .map(|value| match value? {
+                    DntValue::Bytes(value) => Ok(Some(value.as_slice())),
+                    DntValue::Null => Ok(None),
+                    other => Err(mismatch(other)),
+                })
+                .collect::<Result<BinaryArray, DntError>>()?,
+        ),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::{
+        array::{StringArray, UInt32Array},
+        datatypes::{DataType, Field, Schema},
+        record_batch::RecordBatch,
+    };
+
+    use super::RAW_DATA_TYPE_KEY;
+    use crate::{DntDataType, DntError, DntTable, DntTableBuilder, DntValue};
+
+    fn table() -> DntTable {
+        let mut table = DntTableBuilder::new()
+            .column("_Name", DntDataType::String)
+            .column("_Rate", DntDataType::Float32)
+            .column("_Data", DntDataType::Unknown(9))
+            .row((1, "Sword", 0.5f32, vec![1, 2]))
+            .row((2, DntValue::Null, DntValue::Null, DntValue::Null))
+            .build()
+            .unwrap();
+        // Read from a file where the floats have the code that some
+        // versions use for doubles.
+        table.head[2].raw_data_type = 5;
+        table
+    }
+
+    #[test]
+    fn keeps_raw_types_and_nulls() {
+        let table = table();
+        let batch = table.to_arrow().unwrap();
+
+        let schema = batch.schema();
+        let rate = schema.field(2);
+        assert_eq!(rate.data_type(), &DataType::Float32);
+        assert!(rate.is_nullable());
+        assert!(!schema.field(0).is_nullable());
+        assert_eq!(
+            rate.metadata().get(RAW_DATA_TYPE_KEY).map(String::as_str),
+            Some("5")
+        );
+        assert_eq!(batch.column(1).null_count(), 1);
+
+        let read = DntTable::from_arrow(&batch).unwrap();
+        assert_eq!(read.head[2].raw_data_type(), 5);
+        assert_eq!(read.head[3].data_type, DntDataType::Unknown(9));
+        assert!(read == table);
+    }
+
+    #[test]
+    fn needs_an_int32_id() {
+        let batch = RecordBatch::try_new(
+            Arc::new(Schema::new(vec![
+                Field::new("id", DataType::UInt32, false),
+                Field::new("_Name", DataType::Utf8, false),
+            ])),
+            vec![
+                Arc::new(UInt32Array::from(vec![1])),
+                Arc::new(StringArray::from(vec!["Sword"])),
+            ],
+        )
+        .unwrap();
+
+        assert!(matches!(
+            DntTable::from_arrow(&batch),
+            Err(DntError::InvalidHeader(_))
+        ));
+    }
+}
@@ -0,0 +1,293 @@
+use std::io::{BufReader, Cursor};
+
+use encoding_rs::Encoding;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+#[cfg(feature = "zlib")]
+use crate::CompressionMode;
+use crate::{
+    DntError, DntFileReader, DntFileWriter, DntLimits, DntNulls, DntRecord, DntTable,
+    DntUnknownWidth, Endianness, FormatProfile, ProgressEvent, ReadOptions, RowView, StringCodec,
+    WriteOptions,
+};
+
+pub struct AsyncDntReader<R> {
+    reader: R,
+    inner: DntFileReader<Cursor<Vec<u8>>>,
+}
+
+impl<R: AsyncRead + Unpin> AsyncDntReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            inner: DntFileReader::new(Cursor::new(vec![])),
+        }
+    }
+
+    pub fn with_unknown_width(mut self, width: DntUnknownWidth) -> Self {
+        self.inner = self.inner.with_unknown_width(width);
+        self
+    }
+
+    pub fn with_codec(mut self, codec: impl StringCodec + 'static) -> Self {
+        self.inner = self.inner.with_codec(codec);
+        self
+    }
+
+    pub fn with_encoding(self, encoding: &'static Encoding) -> Self {
+        self.with_codec(encoding)
+    }
+
+    pub fn with_columns<I, S>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.inner = self.inner.with_columns(columns);
+        self
+    }
+
+    pub fn with_filter<F>(mut self, filter: F) -> Self
+    where
+        F: FnMut(&RowView<'_>) -> bool + Send + 'static,
+    {
+        self.inner = self.inner.with_filter(filter);
+        self
+    }
+
+    pub fn with_lenient_footer(mut self, lenient: bool) -> Self {
+        self.inner = self.inner.with_lenient_footer(lenient);
+        self
+    }
+
+    pub fn with_options(mut self, options: ReadOptions) -> Self {
+        self.inner = self.inner.with_options(options);
+        self
+    }
+
+    pub fn with_extra_header_length(mut self, length: usize) -> Self {
+        self.inner = self.inner.with_extra_header_length(length);
+        self
+    }
+
+    pub fn with_profile(mut self, profile: &FormatProfile) -> Self {
+        self.inner = self.inner.with_profile(profile);
+        self
+    }
+
+    pub fn with_limits(mut self, limits: DntLimits) -> Self {
+        self.inner = self.inner.with_limits(limits);
+        self
+    }
+
+    pub fn with_progress<F>(mut self, progress: F) -> Self
+    where
+        F: FnMut(ProgressEvent) + Send + 'static,
+    {
+        self.inner = self.inner.with_progress(progress);
+        self
+    }
+
+    pub fn with_nulls(mut self, nulls: DntNulls) -> Self {
+        self.inner = self.inner.with_nulls(nulls);
+        self
+    }
+
+    pub fn with_endianness(mut self, endianness: Endianness) -> Self {
+        self.inner = self.inner.with_endianness(endianness);
+        self
+    }
+
+    pub async fn read(&mut self) -> Result<(), DntError> {
+        self.fill().await?;
+        self.inner.read()
+    }
+
+    pub async fn read_records<T: DntRecord>(&mut self) -> Result<Vec<T>, DntError> {
+        self.fill().await?;
+        self.inner.read_records()
+    }
+
+    pub fn data(&mut self) -> &mut DntTable {
+        self.inner.data()
+    }
+
+    pub fn trailing_bytes(&self) -> u64 {
+        self.inner.trailing_bytes()
+    }
+
+    async fn fill(&mut self) -> Result<(), DntError> {
+        let max_bytes = self.inner.limits.max_bytes;
+        let mut bytes = vec![];
+        (&mut self.reader)
+            .take(max_bytes.saturating_add(1))
+            .read_to_end(&mut bytes)
+            .await?;
+        DntLimits::check("the data size", bytes.len() as u64, max_bytes)?;
+
+        self.inner.reader = BufReader::new(Cursor::new(bytes));
+        self.inner.inflated = None;
+        Ok(())
+    }
+}
+
+pub struct AsyncDntWriter<W> {
+    writer: W,
+    inner: DntFileWriter<Vec<u8>>,
+}
+
+impl<W: AsyncWrite + Unpin> AsyncDntWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            inner: DntFileWriter::new(vec![]),
+        }
+    }
+
+    pub fn with_unknown_width(mut self, width: DntUnknownWidth) -> Self {
+        self.inner = self.inner.with_unknown_width(width);
+        self
+    }
+
+    pub fn with_codec(mut self, codec: impl StringCodec + 'static) -> Self {
+        self.inner = self.inner.with_codec(codec);
+        self
+    }
+
+    pub fn with_encoding(self, encoding: &'static Encoding) -> Self {
+        self.with_codec(encoding)
+    }
+
+    pub fn with_progress<F>(mut self, progress: F) -> Self
+    where
+        F: FnMut(ProgressEvent) + Send + 'static,
+    {
+        self.inner = self.inner.with_progress(progress);
+        self
+    }
+
+    pub fn with_nulls(mut self, nulls: DntNulls) -> Self {
+        self.inner = self.inner.with_nulls(nulls);
+        self
+    }
+
+    pub fn with_endianness(mut self, endianness: Endianness) -> Self {
+        self.inner = self.inner.with_endianness(endianness);
+        self
+    }
+
+    pub fn with_magic(mut self, magic: [u8; 4]) -> Self {
+        self.inner = self.inner.with_magic(magic);
+        self
+    }
+
+    pub fn with_extra_header(mut self, extra_header: Vec<u8>) -> Self {
+        self.inner = self.inner.with_extra_header(extra_header);
+        self
+    }
+
+    pub fn with_trailing_bytes(mut self, write: bool) -> Self {
+        self.inner = self.inner.with_trailing_bytes(write);
+        self
+    }
+
+    pub fn with_options(mut self, options: WriteOptions) -> Self {
+        self.inner = self.inner.with_options(options);
+        self
+    }
+
+    pub fn with_profile(mut self, profile: &FormatProfile) -> Self {
+        self.inner = self.inner.with_profile(profile);
+        self
+    }
+
+    #[cfg(feature = "zlib")]
+    pub fn with_compression(mut self, compression: CompressionMode) -> Self {
+        self.inner = self.inner.with_compression(compression);
+        self
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    pub async fn write(&mut self, table: &DntTable) -> Result<(), DntError> {
+        self.inner.write(table)?;
+        self.flush().await
+    }
+
+    pub async fn write_records<T: DntRecord>(&mut self, records: &[T]) -> Result<(), DntError> {
+        self.inner.write_records(records)?;
+        self.flush().await
+    }
+
+    async fn flush(&mut self) -> Result<(), DntError> {
+        let bytes = std::mem::take(self.inner.writer.get_mut());
+
+        self.writer.write_all(&bytes).await?;
+        self.writer.flush().await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future::Future;
+
+    use crate::{
+        AsyncDntReader, AsyncDntWriter, DntDataType, DntError, DntLimits, DntTable, DntTableBuilder,
+    };
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+            .block_on(future)
+    }
+
+    fn table() -> DntTable {
+        DntTableBuilder::new()
+            .column("_Name", DntDataType::String)
+            .row((1, "Sword"))
+            .row((2, "Shield"))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn reads_what_it_writes() {
+        block_on(async {
+            let mut writer = AsyncDntWriter::new(vec![]);
+            writer.write(&table()).await.unwrap();
+            let bytes = writer.into_inner();
+            assert_eq!(bytes, table().to_bytes().unwrap());
+
+            let mut reader = AsyncDntReader::new(&bytes[..]);
+            reader.read().await.unwrap();
+            assert!(*reader.data() == table());
+        });
+    }
+
+    #[test]
+    fn limits_the_bytes_read() {
+        block_on(async {
+            let bytes = table().to_bytes().unwrap();
+            let limits = |max_bytes| DntLimits {
+                max_bytes,
+                ..DntLimits::default()
+            };
+
+            let mut reader =
+                AsyncDntReader::new(&bytes[..]).with_limits(limits(bytes.len() as u64 - 1));
+            assert!(matches!(
+                reader.read().await,
+                Err(DntError::LimitExceeded { .. })
+            ));
+
+            let mut reader =
+                AsyncDntReader::new(&bytes[..]).with_limits(limits(bytes.len() as u64));
+            reader.read().await.unwrap();
+            assert!(*reader.data() == table());
+        });
+    }
+}
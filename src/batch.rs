@@ -0,0 +1,269 @@
+use std::{
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use encoding_rs::Encoding;
+
+#[cfg(feature = "parquet")]
+use crate::ParquetOptions;
+use crate::{DntError, DntFileReader, DntTable, SqlOptions};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    #[cfg(feature = "csv")]
+    Csv,
+    #[cfg(feature = "json")]
+    Json,
+    #[cfg(feature = "parquet")]
+    Parquet,
+    Sql,
+    #[cfg(feature = "xlsx")]
+    Xlsx,
+}
+
+impl Format {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            #[cfg(feature = "csv")]
+            Format::Csv => "csv",
+            #[cfg(feature = "json")]
+            Format::Json => "json",
+            #[cfg(feature = "parquet")]
+            Format::Parquet => "parquet",
+            Format::Sql => "sql",
+            #[cfg(feature = "xlsx")]
+            Format::Xlsx => "xlsx",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BatchOptions {
+    /// Encoding of the strings in the tables, Latin-1 if `None`.
+    pub encoding: Option<&'static Encoding>,
+    /// Also converts the tables in subdirectories, recreating the same
+    /// directories under the output directory.
+    pub recursive: bool,
+    pub sql: SqlOptions,
+    #[cfg(feature = "parquet")]
+    pub parquet: ParquetOptions,
+}
+
+#[derive(Debug)]
+pub struct BatchFile {
+    pub input: PathBuf,
+    pub output: PathBuf,
+    /// The number of rows converted, or why the file could not be converted.
+    pub result: Result<usize, DntError>,
+}
+
+#[derive(Debug, Default)]
+pub struct BatchReport {
+    pub files: Vec<BatchFile>,
+}
+
+impl BatchReport {
+    pub fn is_ok(&self) -> bool {
+        self.files.iter().all(|file| file.result.is_ok())
+    }
+
+    pub fn succeeded(&self) -> impl Iterator<Item = &BatchFile> {
+        self.files.iter().filter(|file| file.result.is_ok())
+    }
+
+    pub fn failed(&self) -> impl Iterator<Item = &BatchFile> {
+        self.files.iter().filter(|file| file.result.is_err())
+    }
+}
+
+/// Converts every `.dnt` file in `input_dir` to `format`, writing each one to
+/// `output_dir` under the same name with the extension of the format. A file
+/// that fails to convert doesn't stop the others; its error is kept in the
+/// report instead. Only errors listing the input directory or creating the
+/// output directory are returned as errors.
+pub fn convert_dir(
+    input_dir: impl AsRef<Path>,
+    output_dir: impl AsRef<Path>,
+    format: Format,
+    options: &BatchOptions,
+) -> Result<BatchReport, DntError> {
+    let mut report = BatchReport::default();
+    convert_dir_into(
+        input_dir.as_ref(),
+        output_dir.as_ref(),
+        format,
+        options,
+        &mut report,
+    )?;
+
+    Ok(report)
+}
+
+fn convert_dir_into(
+    input_dir: &Path,
+    output_dir: &Path,
+    format: Format,
+    options: &BatchOptions,
+    report: &mut BatchReport,
+) -> Result<(), DntError> {
+    let mut paths = fs::read_dir(input_dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<PathBuf>, _>>()?;
+    paths.sort();
+
+    fs::create_dir_all(output_dir)?;
+
+    for path in paths {
+        let Some(name) = path.file_name() else {
+            continue;
+        };
+
+        if path.is_dir() {
+            if options.recursive {
+                convert_dir_into(&path, &output_dir.join(name), format, options, report)?;
+            }
+        } else if is_dnt(&path) {
+            let output = output_dir.join(name).with_extension(format.extension());
+            let result = convert_file(&path, &output, format, options);
+
+            report.files.push(BatchFile {
+                input: path,
+                output,
+                result,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+fn is_dnt(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("dnt"))
+}
+
+fn convert_file(
+    input: &Path,
+    output: &Path,
+    format: Format,
+    options: &BatchOptions,
+) -> Result<usize, DntError> {
+    let mut reader = DntFileReader::new(File::open(input)?);
+    if let Some(encoding) = options.encoding {
+        reader = reader.with_encoding(encoding);
+    }
+    reader.read()?;
+    let table = reader.data();
+
+    let name = output
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    write_table(table, &name, output, format, options)?;
+
+    Ok(table.body.len())
+}
+
+fn write_table(
+    table: &DntTable,
+    name: &str,
+    output: &Path,
+    format: Format,
+    options: &BatchOptions,
+) -> Result<(), DntError> {
+    match format {
+        #[cfg(feature = "csv")]
+        Format::Csv => table.to_csv(BufWriter::new(File::create(output)?)),
+        #[cfg(feature = "json")]
+        Format::Json => Ok(fs::write(output, table.to_json()?)?),
+        #[cfg(feature = "parquet")]
+        Format::Parquet => table.to_parquet(File::create(output)?, &options.parquet),
+        Format::Sql => {
+            let mut writer = BufWriter::new(File::create(output)?);
+            table.write_sql(&mut writer, name, &options.sql)?;
+            Ok(writer.flush()?)
+        }
+        #[cfg(feature = "xlsx")]
+        Format::Xlsx => table.to_xlsx(output, name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use super::{convert_dir, BatchOptions, Format};
+    use crate::{DntDataType, DntTableBuilder};
+
+    /// A directory holding `a.dnt`, a `bad.dnt` that isn't a table, a file
+    /// that isn't a `.dnt` and `sub/b.dnt`.
+    fn input_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("dnt-batch-{name}-{}", std::process::id()));
+        fs::create_dir_all(dir.join("in/sub")).unwrap();
+
+        let bytes = DntTableBuilder::new()
+            .column("_Name", DntDataType::String)
+            .row((1, "a"))
+            .row((2, "b"))
+            .build()
+            .unwrap()
+            .to_bytes()
+            .unwrap();
+        fs::write(dir.join("in/a.dnt"), &bytes).unwrap();
+        fs::write(dir.join("in/sub/b.dnt"), &bytes).unwrap();
+        fs::write(dir.join("in/bad.dnt"), b"not a table").unwrap();
+        fs::write(dir.join("in/notes.txt"), b"skipped").unwrap();
+
+        dir
+    }
+
+    #[test]
+    fn converts_a_directory_keeping_failures_in_the_report() {
+        let dir = input_dir("flat");
+        let report = convert_dir(
+            dir.join("in"),
+            dir.join("out"),
+            Format::Sql,
+            &BatchOptions::default(),
+        )
+        .unwrap();
+
+        assert!(!report.is_ok());
+        assert_eq!(report.files.len(), 2);
+
+        let succeeded = report.succeeded().collect::<Vec<_>>();
+        assert_eq!(succeeded.len(), 1);
+        assert_eq!(succeeded[0].output, dir.join("out/a.sql"));
+        assert_eq!(*succeeded[0].result.as_ref().unwrap(), 2);
+        assert!(fs::read_to_string(dir.join("out/a.sql"))
+            .unwrap()
+            .contains("INSERT"));
+
+        let failed = report.failed().collect::<Vec<_>>();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].input, dir.join("in/bad.dnt"));
+
+        assert!(!dir.join("out/sub").exists());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn converts_subdirectories_when_recursive() {
+        let dir = input_dir("recursive");
+        let options = BatchOptions {
+            recursive: true,
+            ..BatchOptions::default()
+        };
+        let report = convert_dir(dir.join("in"), dir.join("out"), Format::Sql, &options).unwrap();
+
+        assert_eq!(report.files.len(), 3);
+        assert_eq!(report.succeeded().count(), 2);
+        assert!(dir.join("out/sub/b.sql").is_file());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,171 @@
+use std::{
+    error::Error,
+    fs::File,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use clap::{Parser, Subcommand};
+use dnt_file_reader_writer::{
+    encoding_rs::Encoding, DntFileReader, DntFileWriter, DntTable, DntValue, ForwardReader,
+};
+
+#[derive(Parser)]
+#[command(name = "dnt", about = "Inspect and convert .dnt files")]
+struct Cli {
+    #[arg(long, global = true, help = "Text encoding of strings, e.g. euc-kr")]
+    encoding: Option<String>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    #[command(about = "Print the columns and the number of rows")]
+    Info { input: PathBuf },
+
+    #[command(about = "Print every row as tab-separated values")]
+    Dump { input: PathBuf },
+
+    #[command(about = "Convert a .dnt file to CSV")]
+    ToCsv {
+        input: PathBuf,
+        #[arg(help = "Output file, standard output if omitted or -")]
+        output: Option<PathBuf>,
+    },
+
+    #[command(about = "Convert a CSV file to .dnt")]
+    FromCsv {
+        input: PathBuf,
+        output: PathBuf,
+        #[arg(long, help = "Take the column types from an existing .dnt file")]
+        like: Option<PathBuf>,
+    },
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<(), Box<dyn Error>> {
+    let encoding = match &cli.encoding {
+        Some(label) => Some(
+            Encoding::for_label(label.as_bytes())
+                .ok_or_else(|| format!("unknown encoding: {}", label))?,
+        ),
+        None => None,
+    };
+
+    match cli.command {
+        Command::Info { input } => {
+            let header = reader(&input, encoding)?.read_header()?;
+
+            println!("{} rows, {} columns", header.rows_nb, header.head.len());
+            for column in &header.head {
+                println!(
+                    "{}\t{:?}\t{}",
+                    column.text,
+                    column.data_type(),
+                    column.raw_data_type()
+                );
+            }
+        }
+        Command::Dump { input } => {
+            let table = read(&input, encoding)?;
+            let mut output = io::stdout().lock();
+
+            let names = table
+                .head
+                .iter()
+                .map(|column| column.text.as_str())
+                .collect::<Vec<&str>>();
+            writeln!(output, "{}", names.join("\t"))?;
+
+            for row in &table.body {
+                let values = row.values.iter().map(format_value).collect::<Vec<String>>();
+                writeln!(output, "{}", values.join("\t"))?;
+            }
+        }
+        Command::ToCsv { input, output } => {
+            let table = read(&input, encoding)?;
+
+            table.to_csv(create(output.as_deref())?)?;
+        }
+        Command::FromCsv {
+            input,
+            output,
+            like,
+        } => {
+            let table = match like {
+                Some(like) => {
+                    let head = reader(&like, encoding)?.read_header()?.head;
+                    DntTable::from_csv_with_head(open(&input)?, &head)?
+                }
+                None => DntTable::from_csv(open(&input)?)?,
+            };
+
+            let mut writer = DntFileWriter::new(create(Some(output.as_path()))?);
+            if let Some(encoding) = encoding {
+                writer = writer.with_encoding(encoding);
+            }
+            writer.write(&table)?;
+        }
+    }
+
+    Ok(())
+}
+
+type Reader = DntFileReader<ForwardReader<Box<dyn Read>>>;
+
+fn is_stdio(path: &Path) -> bool {
+    path.as_os_str() == "-"
+}
+
+/// Opens a file, or standard input for `-`.
+fn open(path: &Path) -> Result<Box<dyn Read>, Box<dyn Error>> {
+    Ok(if is_stdio(path) {
+        Box::new(io::stdin().lock())
+    } else {
+        Box::new(File::open(path)?)
+    })
+}
+
+/// Creates a file, or writes to standard output for `-` or no path.
+fn create(path: Option<&Path>) -> Result<Box<dyn Write>, Box<dyn Error>> {
+    Ok(match path {
+        Some(path) if !is_stdio(path) => Box::new(File::create(path)?),
+        _ => Box::new(io::stdout().lock()),
+    })
+}
+
+/// Tables are read front to back, so standard input works as well as files.
+fn reader(path: &Path, encoding: Option<&'static Encoding>) -> Result<Reader, Box<dyn Error>> {
+    let reader = DntFileReader::from_stream(open(path)?);
+
+    Ok(match encoding {
+        Some(encoding) => reader.with_encoding(encoding),
+        None => reader,
+    })
+}
+
+fn read(path: &Path, encoding: Option<&'static Encoding>) -> Result<DntTable, Box<dyn Error>> {
+    let mut reader = reader(path, encoding)?;
+    reader.read()?;
+
+    Ok(std::mem::take(reader.data()))
+}
+
+fn format_value(value: &DntValue) -> String {
+    match value {
+        DntValue::Null => String::new(),
+        value => value.to_string(),
+    }
+}
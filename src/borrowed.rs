@@ -0,0 +1,422 @@
+use std::{
+    borrow::Cow,
+    io::{Cursor, Seek},
+};
+
+use crate::{
+    DntColumn, DntDataType, DntError, DntFileReader, DntHeader, DntLayout, DntLimits, DntRow,
+    DntTable, DntUnknownWidth, DntValue, StringCodec,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DntValueRef<'a> {
+    String(&'a [u8]),
+    UInt32(u32),
+    Int32(i32),
+    Float32(f32),
+    Float64(f64),
+    Bytes(&'a [u8]),
+}
+
+impl<'a> DntValueRef<'a> {
+    pub fn as_str(&self) -> Option<&'a str> {
+        match self {
+            DntValueRef::String(bytes) => std::str::from_utf8(bytes).ok(),
+            _ => None,
+        }
+    }
+
+    pub fn to_value(&self, codec: &dyn StringCodec) -> Result<DntValue, DntError> {
+        Ok(match *self {
+            DntValueRef::String(bytes) => DntValue::String(codec.decode(bytes)?),
+            DntValueRef::UInt32(value) => DntValue::UInt32(value),
+            DntValueRef::Int32(value) => DntValue::Int32(value),
+            DntValueRef::Float32(value) => DntValue::Float32(value),
+            DntValueRef::Float64(value) => DntValue::Float64(value),
+            DntValueRef::Bytes(bytes) => DntValue::Bytes(bytes.to_vec()),
+        })
+    }
+}
+
+pub struct DntRowsRef<'a> {
+    bytes: &'a [u8],
+    position: usize,
+    layout: DntLayout,
+    unknown_width: DntUnknownWidth,
+    limits: DntLimits,
+    remaining: u32,
+    row: u32,
+}
+
+impl<'a> DntRowsRef<'a> {
+    pub(crate) fn new(
+        bytes: &'a [u8],
+        position: usize,
+        layout: DntLayout,
+        unknown_width: DntUnknownWidth,
+        limits: DntLimits,
+        remaining: u32,
+    ) -> Self {
+        Self {
+            bytes,
+            position,
+            layout,
+            unknown_width,
+            limits,
+            remaining,
+            row: 0,
+        }
+    }
+
+    pub fn head(&self) -> &[DntColumn] {
+        self.layout.head()
+    }
+
+    pub(crate) fn layout(&self) -> &DntLayout {
+        &self.layout
+    }
+
+    pub(crate) fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Starts decoding `remaining` rows again from `position`.
+    pub(crate) fn restart(&mut self, position: usize, remaining: u32) {
+        self.position = position;
+        self.remaining = remaining;
+        self.row = 0;
+    }
+
+    #[cfg(feature = "rayon")]
+    pub(crate) fn skip_row(&mut self) -> Result<(), DntError> {
+        for index in 0..self.layout.columns.len() {
+            match self.layout.columns[index].0 {
+                DntDataType::String => {
+                    self.take_string()?;
+                }
+                DntDataType::UInt32 | DntDataType::Int32 | DntDataType::Float32 => {
+                    self.take(4)?;
+                }
+                DntDataType::Float64 => {
+                    self.take(8)?;
+                }
+                DntDataType::Unknown(_) => {
+                    self.take_unknown()?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn take(&mut self, length: usize) -> Result<&'a [u8], DntError> {
+        let end = self
+            .position
+            .checked_add(length)
+            .filter(|&end| end <= self.bytes.len())
+            .ok_or(DntError::UnexpectedEof)?;
+
+        let bytes = &self.bytes[self.position..end];
+        self.position = end;
+        Ok(bytes)
+    }
+
+    fn take_array<const N: usize>(&mut self) -> Result<[u8; N], DntError> {
+        let mut result = [0; N];
+        result.copy_from_slice(self.take(N)?);
+        Ok(result)
+    }
+
+    fn take_u16(&mut self) -> Result<u16, DntError> {
+        Ok(self.layout.endianness.read_u16(self.take_array()?))
+    }
+
+    fn take_string(&mut self) -> Result<&'a [u8], DntError> {
+        let length = self.take_u16()? as usize;
+        self.take_limited(length)
+    }
+
+    fn take_unknown(&mut self) -> Result<&'a [u8], DntError> {
+        let length = match self.unknown_width {
+            DntUnknownWidth::LengthPrefixed => self.take_u16()? as usize,
+            DntUnknownWidth::Fixed(width) => width,
+        };
+        self.take_limited(length)
+    }
+
+    fn take_limited(&mut self, length: usize) -> Result<&'a [u8], DntError> {
+        DntLimits::check(
+            "a string length",
+            length as u64,
+            self.limits.max_string_length as u64,
+        )?;
+        self.take(length)
+    }
+
+    fn read_row(&mut self) -> Result<Vec<DntValueRef<'a>>, DntError> {
+        let mut values = Vec::with_capacity(self.layout.head().len());
+
+        for index in 0..self.layout.columns.len() {
+            let (data_type, selected) = self.layout.columns[index];
+            let start = self.position;
+
+            let value = self.take_value(data_type).map_err(|error| {
+                self.layout.value_error(
+                    error,
+                    self.row,
+                    index,
+                    start as u64,
+                    (self.position - start) as u64,
+                    self.unknown_width,
+                )
+            })?;
+            if selected {
+                values.push(value);
+            }
+        }
+        DntLimits::check("the data size", self.position as u64, self.limits.max_bytes)?;
+
+        Ok(values)
+    }
+
+    fn take_value(&mut self, data_type: DntDataType) -> Result<DntValueRef<'a>, DntError> {
+        Ok(match data_type {
+            DntDataType::String => DntValueRef::String(self.take_string()?),
+            DntDataType::UInt32 => {
+                DntValueRef::UInt32(self.layout.endianness.read_u32(self.take_array()?))
+            }
+            DntDataType::Int32 => {
+                DntValueRef::Int32(self.layout.endianness.read_i32(self.take_array()?))
+            }
+            DntDataType::Float32 => {
+                DntValueRef::Float32(self.layout.endianness.read_f32(self.take_array()?))
+            }
+            DntDataType::Float64 => {
+                DntValueRef::Float64(self.layout.endianness.read_f64(self.take_array()?))
+            }
+            DntDataType::Unknown(_) => DntValueRef::Bytes(self.take_unknown()?),
+        })
+    }
+}
+
+impl<'a> Iterator for DntRowsRef<'a> {
+    type Item = Result<Vec<DntValueRef<'a>>, DntError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        match self.read_row() {
+            Ok(row) => {
+                self.remaining -= 1;
+                self.row += 1;
+                Some(Ok(row))
+            }
+            Err(error) => {
+                self.remaining = 0;
+                Some(Err(error))
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.remaining as usize))
+    }
+}
+
+/// A value of a `DntTableRef`. Strings borrow from the buffer when they are
+/// already valid UTF-8 in the encoding of the reader, which is the case for
+/// ASCII text in every supported encoding, and are decoded into an owned
+/// string otherwise.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DntValueCow<'a> {
+    String(Cow<'a, str>),
+    UInt32(u32),
+    Int32(i32),
+    Float32(f32),
+    Float64(f64),
+    Bytes(&'a [u8]),
+}
+
+impl<'a> DntValueCow<'a> {
+    fn new(value: DntValueRef<'a>, codec: &dyn StringCodec) -> Result<Self, DntError> {
+        Ok(match value {
+            DntValueRef::String(bytes) => DntValueCow::String(codec.decode_borrowed(bytes)?),
+            DntValueRef::UInt32(value) => DntValueCow::UInt32(value),
+            DntValueRef::Int32(value) => DntValueCow::Int32(value),
+            DntValueRef::Float32(value) => DntValueCow::Float32(value),
+            DntValueRef::Float64(value) => DntValueCow::Float64(value),
+            DntValueRef::Bytes(bytes) => DntValueCow::Bytes(bytes),
+        })
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            DntValueCow::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn to_value(&self) -> DntValue {
+        match self {
+            DntValueCow::String(value) => DntValue::String(value.as_ref().to_owned()),
+            DntValueCow::UInt32(value) => DntValue::UInt32(*value),
+            DntValueCow::Int32(value) => DntValue::Int32(*value),
+            DntValueCow::Float32(value) => DntValue::Float32(*value),
+            DntValueCow::Float64(value) => DntValue::Float64(*value),
+            DntValueCow::Bytes(bytes) => DntValue::Bytes(bytes.to_vec()),
+        }
+    }
+}
+
+/// A table whose strings borrow from the buffer it was read from, returned by
+/// `read_ref`. Reading one allocates a vector per row but, for most tables,
+/// no string at all.
+#[derive(Debug, Clone)]
+pub struct DntTableRef<'a> {
+    pub magic: [u8; 4],
+    pub extra_header: Vec<u8>,
+    pub head: Vec<DntColumn>,
+    pub body: Vec<Vec<DntValueCow<'a>>>,
+    pub trailing_bytes: Vec<u8>,
+}
+
+impl<'a> DntTableRef<'a> {
+    pub fn len(&self) -> usize {
+        self.body.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.body.is_empty()
+    }
+
+    pub fn get(&self, row: usize, column: &str) -> Option<&DntValueCow<'a>> {
+        let index = self.head.iter().position(|head| head.text == column)?;
+        self.body.get(row)?.get(index)
+    }
+
+    /// Copies every value into an owned `DntTable`.
+    pub fn to_table(&self) -> DntTable {
+        let mut table = DntTable::from_parts(
+            self.head.clone(),
+            self.body
+                .iter()
+                .map(|values| DntRow {
+                    values: values.iter().map(DntValueCow::to_value).collect(),
+                })
+                .collect(),
+        );
+        table.magic = self.magic;
+        table.extra_header = self.extra_header.clone();
+        table.trailing_bytes = self.trailing_bytes.clone();
+        table
+    }
+}
+
+impl<'a> DntFileReader<Cursor<&'a [u8]>> {
+    pub fn rows_ref(&mut self) -> Result<DntRowsRef<'a>, DntError> {
+        let header = self.read_header()?;
+        self.rows_ref_from(header)
+    }
+
+    fn rows_ref_from(&mut self, header: DntHeader) -> Result<DntRowsRef<'a>, DntError> {
+        let rows_nb = header.rows_nb;
+        let layout = self.layout(header)?;
+
+        if self.inflated.is_some() {
+            return Err(DntError::InvalidHeader(String::from(
+                "compressed files cannot be borrowed from, use rows() instead",
+            )));
+        }
+
+        Ok(DntRowsRef::new(
+            self.reader.get_ref().get_ref(),
+            self.reader.stream_position()? as usize,
+            layout,
+            self.unknown_width,
+            self.limits,
+            rows_nb,
+        ))
+    }
+
+    /// Reads the whole table, borrowing strings from the buffer where
+    /// possible. Filters and null rules don't apply; `with_columns` does.
+    pub fn read_ref(&mut self) -> Result<DntTableRef<'a>, DntError> {
+        let mut header = self.read_header()?;
+        let magic = header.magic;
+        let extra_header = std::mem::take(&mut header.extra_header);
+        let mut rows = self.rows_ref_from(header)?;
+        let head = rows.head().to_vec();
+
+        let body = rows
+            .by_ref()
+            .map(|values| {
+                values?
+                    .into_iter()
+                    .map(|value| DntValueCow::new(value, &*self.codec))
+                    .collect()
+            })
+            .collect::<Result<Vec<Vec<DntValueCow<'a>>>, DntError>>()?;
+
+        self.seek(rows.position() as u64)?;
+        self.read_footer()?;
+
+        Ok(DntTableRef {
+            magic,
+            extra_header,
+            head,
+            body,
+            trailing_bytes: std::mem::take(&mut self.trailing),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use crate::{DntDataType, DntError, DntFileReader, DntLimits, DntTableBuilder};
+
+    fn limit(result: Result<impl Sized, DntError>) -> Option<&'static str> {
+        match result {
+            Err(DntError::Parse { error, .. }) => limit(Err::<(), _>(*error)),
+            Err(DntError::LimitExceeded { limit, .. }) => Some(limit),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn checks_the_limits() {
+        let bytes = DntTableBuilder::new()
+            .column("_N", DntDataType::String)
+            .row((1, "Sword"))
+            .build()
+            .unwrap()
+            .to_bytes()
+            .unwrap();
+
+        let limits = DntLimits {
+            max_string_length: 4,
+            ..DntLimits::default()
+        };
+        let result = DntFileReader::new(Cursor::new(&bytes[..]))
+            .with_limits(limits)
+            .read_ref();
+        assert_eq!(limit(result), Some("a string length"));
+
+        let limits = DntLimits {
+            max_bytes: 16,
+            ..DntLimits::default()
+        };
+        let result = DntFileReader::new(Cursor::new(&bytes[..]))
+            .with_limits(limits)
+            .read_ref();
+        assert_eq!(limit(result), Some("the data size"));
+
+        let table = DntFileReader::new(Cursor::new(&bytes[..]))
+            .read_ref()
+            .unwrap();
+        assert_eq!(table.body.len(), 1);
+    }
+}
@@ -0,0 +1,148 @@
+use std::collections::HashSet;
+
+use crate::{diff::row_id, DntDataType, DntError, DntSchema, DntTable, DntValue};
+
+/// Builds a table column by column and row by row. The id column is added
+/// automatically, so the first value of every row is its id.
+#[derive(Debug, Clone, Default)]
+pub struct DntTableBuilder {
+    schema: DntSchema,
+    rows: Vec<Vec<DntValue>>,
+}
+
+impl DntTableBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn column(mut self, name: &str, data_type: DntDataType) -> Self {
+        self.schema = self.schema.with_column(name, data_type);
+        self
+    }
+
+    pub fn row(mut self, row: impl IntoDntRow) -> Self {
+        self.rows.push(row.into_values());
+        self
+    }
+
+    pub fn build(self) -> Result<DntTable, DntError> {
+        let mut table = DntTable::new(self.schema);
+        let mut ids = HashSet::with_capacity(self.rows.len());
+
+        for values in self.rows {
+            let row = table.checked_row(values)?;
+            let id = row_id(&row)?;
+            if !ids.insert(id) {
+                return Err(DntError::InvalidValue(format!("duplicate id {}", id)));
+            }
+            table.body.push(row);
+        }
+
+        Ok(table)
+    }
+}
+
+/// Values making up a row, starting with its id.
+pub trait IntoDntRow {
+    fn into_values(self) -> Vec<DntValue>;
+}
+
+impl IntoDntRow for Vec<DntValue> {
+    fn into_values(self) -> Vec<DntValue> {
+        self
+    }
+}
+
+macro_rules! impl_into_dnt_row {
+    ($($name:ident),+) => {
+        impl<$($name: Into<DntValue>),+> IntoDntRow for ($($name,)+) {
+            #[allow(non_snake_case)]
+            fn into_values(self) -> Vec<DntValue> {
+                let ($($name,)+) = self;
+                vec![$($name.into()),+]
+            }
+        }
+    };
+}
+
+impl_into_dnt_row!(A);
+impl_into_dnt_row!(A, B);
+impl_into_dnt_row!(A, B, C);
+impl_into_dnt_row!(A, B, C, D);
+impl_into_dnt_row!(A, B, C, D, E);
+impl_into_dnt_row!(A, B, C, D, E, F);
+impl_into_dnt_row!(A, B, C, D, E, F, G);
+impl_into_dnt_row!(A, B, C, D, E, F, G, H);
+impl_into_dnt_row!(A, B, C, D, E, F, G, H, I);
+impl_into_dnt_row!(A, B, C, D, E, F, G, H, I, J);
+impl_into_dnt_row!(A, B, C, D, E, F, G, H, I, J, K);
+impl_into_dnt_row!(A, B, C, D, E, F, G, H, I, J, K, L);
+
+#[cfg(test)]
+mod tests {
+    use super::DntTableBuilder;
+    use crate::{DntDataType, DntError, DntValue};
+
+    fn builder() -> DntTableBuilder {
+        DntTableBuilder::new()
+            .column("_Name", DntDataType::String)
+            .column("_Level", DntDataType::UInt32)
+    }
+
+    #[test]
+    fn builds_the_rows_after_the_id_column() {
+        let table = builder()
+            .row((1, "Sword", 10u32))
+            .row(vec![
+                DntValue::Int32(2),
+                DntValue::String(String::from("Shield")),
+                DntValue::Null,
+            ])
+            .build()
+            .unwrap();
+
+        assert_eq!(table.head[0].text, "id");
+        assert_eq!(table.head.len(), 3);
+        assert_eq!(table.body.len(), 2);
+        assert_eq!(table.body[1].values[2], DntValue::Null);
+    }
+
+    #[test]
+    fn rejects_rows_of_the_wrong_length() {
+        assert!(matches!(
+            builder().row((1, "Sword")).build(),
+            Err(DntError::InvalidValue(_))
+        ));
+        assert!(matches!(
+            builder().row((1, "Sword", 10u32, 20u32)).build(),
+            Err(DntError::InvalidValue(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_values_of_the_wrong_type() {
+        assert!(matches!(
+            builder().row((1, "Sword", -10)).build(),
+            Err(DntError::TypeMismatch {
+                column,
+                expected: DntDataType::UInt32,
+                found: DntDataType::Int32,
+            }) if column == "_Level"
+        ));
+        assert!(matches!(
+            builder().row((1u32, "Sword", 10u32)).build(),
+            Err(DntError::TypeMismatch { column, .. }) if column == "id"
+        ));
+    }
+
+    #[test]
+    fn rejects_duplicate_ids() {
+        assert!(matches!(
+            builder()
+                .row((1, "Sword", 10u32))
+                .row((1, "Shield", 20u32))
+                .build(),
+            Err(DntError::InvalidValue(_))
+        ));
+    }
+}
@@ -0,0 +1,38 @@
+use std::io::Cursor;
+
+use crate::{DntError, DntFileReader, DntFileWriter, DntTable};
+
+impl DntTable {
+    pub fn parse(bytes: &[u8]) -> Result<DntTable, DntError> {
+        let mut reader = DntFileReader::new(Cursor::new(bytes));
+        reader.read()?;
+
+        Ok(std::mem::take(reader.data()))
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, DntError> {
+        let mut writer = DntFileWriter::new(vec![]);
+        writer.write(self)?;
+
+        writer.into_inner()
+    }
+}
+
+/// Bytes as lowercase hex pairs, the way exports write `DntValue::Bytes`.
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Reads back what `to_hex` writes, in either case. `None` if `text` isn't
+/// made of hex pairs.
+#[cfg(any(feature = "csv", feature = "json", feature = "xlsx"))]
+pub(crate) fn parse_hex(text: &str) -> Option<Vec<u8>> {
+    if !text.len().is_multiple_of(2) {
+        return None;
+    }
+
+    (0..text.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(text.get(index..index + 2)?, 16).ok())
+        .collect()
+}
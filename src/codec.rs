@@ -0,0 +1,134 @@
+use std::borrow::Cow;
+
+use encoding_rs::{Encoding, EUC_KR};
+
+use crate::DntError;
+
+pub trait StringCodec: Send + Sync {
+    fn decode(&self, bytes: &[u8]) -> Result<String, DntError>;
+
+    fn encode<'a>(&self, value: &'a str) -> Result<Cow<'a, [u8]>, DntError>;
+
+    /// Decodes without copying when the bytes are already valid UTF-8 text
+    /// in this encoding. The default always copies.
+    fn decode_borrowed<'a>(&self, bytes: &'a [u8]) -> Result<Cow<'a, str>, DntError> {
+        self.decode(bytes).map(Cow::Owned)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Utf8Codec;
+
+impl StringCodec for Utf8Codec {
+    fn decode(&self, bytes: &[u8]) -> Result<String, DntError> {
+        String::from_utf8(bytes.to_vec())
+            .map_err(|_| DntError::InvalidValue(format!("{:?} is not valid UTF-8", bytes)))
+    }
+
+    fn encode<'a>(&self, value: &'a str) -> Result<Cow<'a, [u8]>, DntError> {
+        Ok(Cow::Borrowed(value.as_bytes()))
+    }
+
+    fn decode_borrowed<'a>(&self, bytes: &'a [u8]) -> Result<Cow<'a, str>, DntError> {
+        std::str::from_utf8(bytes)
+            .map(Cow::Borrowed)
+            .map_err(|_| DntError::InvalidValue(format!("{:?} is not valid UTF-8", bytes)))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Latin1Codec;
+
+impl StringCodec for Latin1Codec {
+    fn decode(&self, bytes: &[u8]) -> Result<String, DntError> {
+        Ok(bytes.iter().copied().map(char::from).collect())
+    }
+
+    fn encode<'a>(&self, value: &'a str) -> Result<Cow<'a, [u8]>, DntError> {
+        value
+            .chars()
+            .map(|character| {
+                u8::try_from(character).map_err(|_| {
+                    DntError::InvalidValue(format!(
+                        "{:?} contains characters outside of Latin-1",
+                        value
+                    ))
+                })
+            })
+            .collect::<Result<Vec<u8>, DntError>>()
+            .map(Cow::Owned)
+    }
+
+    fn decode_borrowed<'a>(&self, bytes: &'a [u8]) -> Result<Cow<'a, str>, DntError> {
+        match std::str::from_utf8(bytes) {
+            Ok(value) if bytes.is_ascii() => Ok(Cow::Borrowed(value)),
+            _ => self.decode(bytes).map(Cow::Owned),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Cp949Codec;
+
+impl StringCodec for Cp949Codec {
+    fn decode(&self, bytes: &[u8]) -> Result<String, DntError> {
+        StringCodec::decode(&EUC_KR, bytes)
+    }
+
+    fn encode<'a>(&self, value: &'a str) -> Result<Cow<'a, [u8]>, DntError> {
+        StringCodec::encode(&EUC_KR, value)
+    }
+
+    fn decode_borrowed<'a>(&self, bytes: &'a [u8]) -> Result<Cow<'a, str>, DntError> {
+        StringCodec::decode_borrowed(&EUC_KR, bytes)
+    }
+}
+
+impl StringCodec for &'static Encoding {
+    fn decode(&self, bytes: &[u8]) -> Result<String, DntError> {
+        self.decode_borrowed(bytes).map(Cow::into_owned)
+    }
+
+    fn decode_borrowed<'a>(&self, bytes: &'a [u8]) -> Result<Cow<'a, str>, DntError> {
+        self.decode_without_bom_handling_and_without_replacement(bytes)
+            .ok_or_else(|| {
+                DntError::InvalidValue(format!("{:?} is not valid {}", bytes, self.name()))
+            })
+    }
+
+    fn encode<'a>(&self, value: &'a str) -> Result<Cow<'a, [u8]>, DntError> {
+        let (bytes, _, had_errors) = Encoding::encode(self, value);
+        if had_errors {
+            return Err(DntError::InvalidValue(format!(
+                "{:?} cannot be encoded as {}",
+                value,
+                self.name()
+            )));
+        }
+        Ok(bytes)
+    }
+}
+
+/// Decodes with `codec`, and with `fallback` what `codec` rejects.
+pub(crate) struct FallbackCodec {
+    pub(crate) codec: Box<dyn StringCodec>,
+    pub(crate) fallback: &'static Encoding,
+}
+
+impl StringCodec for FallbackCodec {
+    fn decode(&self, bytes: &[u8]) -> Result<String, DntError> {
+        self.codec
+            .decode(bytes)
+            .or_else(|error| StringCodec::decode(&self.fallback, bytes).map_err(|_| error))
+    }
+
+    fn encode<'a>(&self, value: &'a str) -> Result<Cow<'a, [u8]>, DntError> {
+        self.codec.encode(value)
+    }
+
+    fn decode_borrowed<'a>(&self, bytes: &'a [u8]) -> Result<Cow<'a, str>, DntError> {
+        self.codec
+            .decode_borrowed(bytes)
+            .or_else(|error| StringCodec::decode_borrowed(&self.fallback, bytes).map_err(|_| error))
+    }
+}
@@ -0,0 +1,334 @@
+use crate::{
+    check_rows, edit::default_value, DntColumn, DntDataType, DntError, DntField, DntRow, DntTable,
+    DntValue,
+};
+
+/// The values of one column, in a `Vec` of their own type.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DntColumnData {
+    String(Vec<String>),
+    UInt32(Vec<u32>),
+    Int32(Vec<i32>),
+    Float32(Vec<f32>),
+    Float64(Vec<f64>),
+    Bytes(Vec<Vec<u8>>),
+}
+
+impl DntColumnData {
+    fn new(data_type: DntDataType, capacity: usize) -> Self {
+        match data_type {
+            DntDataType::String => DntColumnData::String(Vec::with_capacity(capacity)),
+            DntDataType::UInt32 => DntColumnData::UInt32(Vec::with_capacity(capacity)),
+            DntDataType::Int32 => DntColumnData::Int32(Vec::with_capacity(capacity)),
+            DntDataType::Float32 => DntColumnData::Float32(Vec::with_capacity(capacity)),
+            DntDataType::Float64 => DntColumnData::Float64(Vec::with_capacity(capacity)),
+            DntDataType::Unknown(_) => DntColumnData::Bytes(Vec::with_capacity(capacity)),
+        }
+    }
+
+    /// Pushes a value already checked to match the column type.
+    fn push(&mut self, value: DntValue) {
+        match (self, value) {
+            (DntColumnData::String(values), DntValue::String(value)) => values.push(value),
+            (DntColumnData::UInt32(values), DntValue::UInt32(value)) => values.push(value),
+            (DntColumnData::Int32(values), DntValue::Int32(value)) => values.push(value),
+            (DntColumnData::Float32(values), DntValue::Float32(value)) => values.push(value),
+            (DntColumnData::Float64(values), DntValue::Float64(value)) => values.push(value),
+            (DntColumnData::Bytes(values), DntValue::Bytes(value)) => values.push(value),
+            _ => unreachable!("the value was checked against the column type"),
+        }
+    }
+
+    fn value(&self, index: usize) -> DntValue {
+        match self {
+            DntColumnData::String(values) => DntValue::String(values[index].clone()),
+            DntColumnData::UInt32(values) => DntValue::UInt32(values[index]),
+            DntColumnData::Int32(values) => DntValue::Int32(values[index]),
+            DntColumnData::Float32(values) => DntValue::Float32(values[index]),
+            DntColumnData::Float64(values) => DntValue::Float64(values[index]),
+            DntColumnData::Bytes(values) => DntValue::Bytes(values[index].clone()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            DntColumnData::String(values) => values.len(),
+            DntColumnData::UInt32(values) => values.len(),
+            DntColumnData::Int32(values) => values.len(),
+            DntColumnData::Float32(values) => values.len(),
+            DntColumnData::Float64(values) => values.len(),
+            DntColumnData::Bytes(values) => values.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn as_strings(&self) -> Option<&[String]> {
+        match self {
+            DntColumnData::String(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub fn as_u32(&self) -> Option<&[u32]> {
+        match self {
+            DntColumnData::UInt32(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub fn as_i32(&self) -> Option<&[i32]> {
+        match self {
+            DntColumnData::Int32(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub fn as_f32(&self) -> Option<&[f32]> {
+        match self {
+            DntColumnData::Float32(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<&[f64]> {
+        match self {
+            DntColumnData::Float64(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub fn as_bytes(&self) -> Option<&[Vec<u8>]> {
+        match self {
+            DntColumnData::Bytes(values) => Some(values),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DntColumnarColumn {
+    pub column: DntColumn,
+    pub data: DntColumnData,
+    /// Which rows hold `DntValue::Null`, or `None` if none does. Null cells
+    /// hold the zero value of the column type in `data`.
+    pub nulls: Option<Vec<bool>>,
+}
+
+/// A table stored column by column, so scanning a column reads one
+/// contiguous `Vec` instead of jumping from row to row.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DntColumnarTable {
+    pub magic: [u8; 4],
+    pub extra_header: Vec<u8>,
+    pub columns: Vec<DntColumnarColumn>,
+    pub trailing_bytes: Vec<u8>,
+}
+
+impl DntColumnarTable {
+    /// The number of rows, taken from the id column.
+    pub fn len(&self) -> usize {
+        self.columns.first().map_or(0, |column| column.data.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn column(&self, name: &str) -> Option<&DntColumnarColumn> {
+        self.columns
+            .iter()
+            .find(|column| column.column.text == name)
+    }
+
+    pub fn column_mut(&mut self, name: &str) -> Option<&mut DntColumnarColumn> {
+        self.columns
+            .iter_mut()
+            .find(|column| column.column.text == name)
+    }
+
+    /// Borrows the values of a column as a slice of `T`, without copying.
+    /// Null cells hold the zero value of the type, see `nulls`.
+    pub fn values<T: DntField>(&self, name: &str) -> Result<&[T], DntError> {
+        let column = self
+            .column(name)
+            .ok_or_else(|| DntError::ColumnNotFound(name.to_owned()))?;
+
+        T::column_data(&column.data).ok_or_else(|| DntError::TypeMismatch {
+            column: name.to_owned(),
+            expected: T::DATA_TYPE,
+            found: column.column.data_type,
+        })
+    }
+
+    /// Converts back to rows. Every column must hold as many values as the
+    /// id column.
+    pub fn to_table(&self) -> Result<DntTable, DntError> {
+        let rows = self.len();
+        for column in &self.columns {
+            let found = column.data.len();
+            let nulls = column.nulls.as_ref().map_or(found, Vec::len);
+            if found != rows || nulls != rows {
+                return Err(DntError::InvalidValue(format!(
+                    "column {} has {} values, expected {}",
+                    column.column.text,
+                    found.min(nulls),
+                    rows
+                )));
+            }
+        }
+
+        let body = (0..rows)
+            .map(|row| DntRow {
+                values: self
+                    .columns
+                    .iter()
+                    .map(|column| match &column.nulls {
+                        Some(nulls) if nulls[row] => DntValue::Null,
+                        _ => column.data.value(row),
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        let mut table = DntTable::from_parts(
+            self.columns
+                .iter()
+                .map(|column| column.column.clone())
+                .collect(),
+            body,
+        );
+        table.magic = self.magic;
+        table.extra_header = self.extra_header.clone();
+        table.trailing_bytes = self.trailing_bytes.clone();
+        Ok(table)
+    }
+}
+
+impl DntTable {
+    /// Copies the table into a `DntColumnarTable`, checking that every cell
+    /// matches the type of its column.
+    pub fn to_columnar(&self) -> Result<DntColumnarTable, DntError> {
+        check_rows(&self.head, &self.body)?;
+
+        let columns = self
+            .head
+            .iter()
+            .enumerate()
+            .map(|(index, column)| {
+                let mut data = DntColumnData::new(column.data_type(), self.body.len());
+                let mut nulls = None;
+
+                for (row, values) in self.body.iter().enumerate() {
+                    match &values.values[index] {
+                        DntValue::Null => {
+                            nulls.get_or_insert_with(|| vec![false; self.body.len()])[row] = true;
+                            data.push(default_value(column.data_type()));
+                        }
+                        value => data.push(value.clone()),
+                    }
+                }
+
+                DntColumnarColumn {
+                    column: column.clone(),
+                    data,
+                    nulls,
+                }
+            })
+            .collect();
+
+        Ok(DntColumnarTable {
+            magic: self.magic,
+            extra_header: self.extra_header.clone(),
+            columns,
+            trailing_bytes: self.trailing_bytes.clone(),
+        })
+    }
+}
+
+impl TryFrom<&DntTable> for DntColumnarTable {
+    type Error = DntError;
+
+    fn try_from(table: &DntTable) -> Result<Self, DntError> {
+        table.to_columnar()
+    }
+}
+
+impl TryFrom<&DntColumnarTable> for DntTable {
+    type Error = DntError;
+
+    fn try_from(table: &DntColumnarTable) -> Result<Self, DntError> {
+        table.to_table()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        DntColumnData, DntColumnarTable, DntDataType, DntError, DntTable, DntTableBuilder, DntValue,
+    };
+
+    fn table() -> DntTable {
+        DntTableBuilder::new()
+            .column("_Name", DntDataType::String)
+            .column("_Level", DntDataType::UInt32)
+            .row((1, "Sword", 10u32))
+            .row((2, "Shield", DntValue::Null))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn converts_both_ways_keeping_nulls() {
+        let table = table();
+        let columnar = table.to_columnar().unwrap();
+        assert_eq!(columnar.len(), 2);
+        assert_eq!(columnar.values::<i32>("id").unwrap(), [1, 2]);
+
+        let level = columnar.column("_Level").unwrap();
+        assert_eq!(level.data, DntColumnData::UInt32(vec![10, 0]));
+        assert_eq!(level.nulls, Some(vec![false, true]));
+        assert_eq!(columnar.column("_Name").unwrap().nulls, None);
+
+        assert!(columnar.to_table().unwrap() == table);
+        assert!(DntTable::try_from(&DntColumnarTable::try_from(&table).unwrap()).unwrap() == table);
+    }
+
+    #[test]
+    fn checks_column_types() {
+        let columnar = table().to_columnar().unwrap();
+        assert_eq!(
+            columnar.values::<String>("_Name").unwrap(),
+            ["Sword", "Shield"]
+        );
+        assert!(matches!(
+            columnar.values::<f32>("_Level"),
+            Err(DntError::TypeMismatch { .. })
+        ));
+        assert!(matches!(
+            columnar.values::<u32>("_Missing"),
+            Err(DntError::ColumnNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_columns_of_different_lengths() {
+        let mut columnar = table().to_columnar().unwrap();
+        if let DntColumnData::String(names) = &mut columnar.column_mut("_Name").unwrap().data {
+            names.pop();
+        }
+        assert!(matches!(
+            columnar.to_table(),
+            Err(DntError::InvalidValue(_))
+        ));
+
+        let mut columnar = table().to_columnar().unwrap();
+        columnar.column_mut("_Level").unwrap().nulls = Some(vec![true]);
+        assert!(matches!(
+            columnar.to_table(),
+            Err(DntError::InvalidValue(_))
+        ));
+    }
+}
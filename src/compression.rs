@@ -0,0 +1,130 @@
+use std::io::{BufRead, ErrorKind, Read, Seek, SeekFrom, Write};
+
+use flate2::{read::ZlibDecoder, write::ZlibEncoder, Compression};
+
+use crate::{DntError, DntLimits};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CompressionMode {
+    #[default]
+    None,
+    Zlib,
+}
+
+/// Decompresses the payload, reading at most `max_bytes` of it so that a
+/// small file cannot inflate into an unbounded buffer. The zlib header is
+/// where plain files have their column count, so a payload that doesn't
+/// decompress gives `None`, with the reader back at the start of the
+/// payload, and the file is read as plain. Without a zlib header nothing is
+/// consumed, so streams that cannot seek back still read plain files.
+pub(crate) fn inflate<R: BufRead + Seek>(
+    reader: &mut R,
+    max_bytes: u64,
+) -> Result<Option<Vec<u8>>, DntError> {
+    let header = reader.fill_buf()?;
+    if header.len() < 2 || !is_zlib_header(header[0], header[1]) {
+        return Ok(None);
+    }
+    let start = reader.stream_position()?;
+
+    let mut payload = vec![];
+    let result = ZlibDecoder::new(&mut *reader)
+        .take(max_bytes.saturating_add(1))
+        .read_to_end(&mut payload);
+    match result {
+        Ok(_) => {}
+        Err(error)
+            if matches!(
+                error.kind(),
+                ErrorKind::InvalidInput | ErrorKind::InvalidData | ErrorKind::UnexpectedEof
+            ) =>
+        {
+            reader.seek(SeekFrom::Start(start))?;
+            return Ok(None);
+        }
+        Err(error) => return Err(error.into()),
+    }
+    DntLimits::check("the data size", payload.len() as u64, max_bytes)?;
+
+    Ok(Some(payload))
+}
+
+pub(crate) fn deflate<W: Write>(
+    writer: &mut W,
+    payload: &[u8],
+    mode: CompressionMode,
+) -> Result<(), DntError> {
+    match mode {
+        CompressionMode::None => writer.write_all(payload)?,
+        CompressionMode::Zlib => {
+            let mut encoder = ZlibEncoder::new(writer, Compression::default());
+            encoder.write_all(payload)?;
+            encoder.finish()?;
+        }
+    }
+
+    Ok(())
+}
+
+fn is_zlib_header(first: u8, second: u8) -> bool {
+    first & 0x0f == 8 && first >> 4 <= 7 && (u16::from(first) << 8 | u16::from(second)) % 31 == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use crate::{
+        CompressionMode, DntDataType, DntError, DntFileReader, DntFileWriter, DntLimits, DntTable,
+        DntTableBuilder, DntValue,
+    };
+
+    #[test]
+    fn reads_what_it_writes() {
+        let mut builder = DntTableBuilder::new().column("_Name", DntDataType::String);
+        for id in 1..=100 {
+            builder = builder.row((id, "Sword"));
+        }
+        let table = builder.build().unwrap();
+
+        let mut writer =
+            DntFileWriter::new(Cursor::new(vec![])).with_compression(CompressionMode::Zlib);
+        writer.write(&table).unwrap();
+        let bytes = writer.into_inner().unwrap().into_inner();
+        let plain = table.to_bytes().unwrap();
+        assert!(bytes.len() < plain.len());
+        assert_eq!(bytes[..4], plain[..4]);
+
+        let mut reader = DntFileReader::new(Cursor::new(&bytes));
+        reader.read().unwrap();
+        assert!(*reader.data() == table);
+
+        let limits = DntLimits {
+            max_bytes: 100,
+            ..DntLimits::default()
+        };
+        let mut reader = DntFileReader::new(Cursor::new(&bytes)).with_limits(limits);
+        assert!(matches!(
+            reader.read(),
+            Err(DntError::LimitExceeded {
+                limit: "the data size",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn reads_plain_tables_whose_column_count_looks_like_zlib() {
+        let mut builder = DntTableBuilder::new();
+        for index in 0..376 {
+            builder = builder.column(&format!("_Column{}", index), DntDataType::UInt32);
+        }
+        let mut row = vec![DntValue::Int32(1)];
+        row.extend((0..376).map(DntValue::UInt32));
+        let table = builder.row(row).build().unwrap();
+
+        let bytes = table.to_bytes().unwrap();
+        assert_eq!(bytes[4..6], [0x78, 0x01]);
+        assert!(DntTable::parse(&bytes).unwrap() == table);
+    }
+}
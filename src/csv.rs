@@ -0,0 +1,271 @@
+use std::io::{Read, Write};
+
+use crate::{
+    bytes::parse_hex, DntColumn, DntDataType, DntError, DntRow, DntTable, DntValue, ExportOptions,
+};
+
+impl DntTable {
+    pub fn to_csv<W: Write>(&self, writer: W) -> Result<(), DntError> {
+        self.to_csv_with(writer, &ExportOptions::default())
+    }
+
+    pub fn to_csv_with<W: Write>(
+        &self,
+        writer: W,
+        options: &ExportOptions,
+    ) -> Result<(), DntError> {
+        let columns = options.columns.resolve(self)?;
+        let mut writer = ::csv::Writer::from_writer(writer);
+
+        writer.write_record(columns.iter().map(|(_, name)| name))?;
+
+        for row in &self.body {
+            writer.write_record(columns.iter().map(|(index, _)| {
+                options.format_value(row.values.get(*index).unwrap_or(&DntValue::Null))
+            }))?;
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+
+    pub fn from_csv<R: Read>(reader: R) -> Result<DntTable, DntError> {
+        let (names, records) = read_csv(reader)?;
+
+        let head = names
+            .iter()
+            .enumerate()
+            .map(|(index, name)| {
+                let data_type = if index == 0 {
+                    DntDataType::Int32
+                } else {
+                    infer_data_type(records.iter().map(|record| &record[index]))
+                };
+                DntColumn::new(name, data_type)
+            })
+            .collect::<Vec<DntColumn>>();
+
+        let body = parse_rows(&head, &records, |index| index)?;
+
+        Ok(DntTable::from_parts(head, body))
+    }
+
+    pub fn from_csv_with_head<R: Read>(
+        reader: R,
+        head: &[DntColumn],
+    ) -> Result<DntTable, DntError> {
+        let (names, records) = read_csv(reader)?;
+
+        if let Some(name) = names
+            .iter()
+            .find(|name| !head.iter().any(|column| &column.text == *name))
+        {
+            return Err(DntError::InvalidHeader(format!(
+                "unexpected CSV column: {}",
+                name
+            )));
+        }
+
+        let indexes = head
+            .iter()
+            .map(|column| {
+                names
+                    .iter()
+                    .position(|name| name == &column.text)
+                    .ok_or_else(|| DntError::ColumnNotFound(column.text.to_owned()))
+            })
+            .collect::<Result<Vec<usize>, DntError>>()?;
+
+        let head = head.to_vec();
+
+        let body = parse_rows(&head, &records, |index| indexes[index])?;
+
+        Ok(DntTable::from_parts(head, body))
+    }
+}
+
+fn read_csv<R: Read>(reader: R) -> Result<(Vec<String>, Vec<::csv::StringRecord>), DntError> {
+    let mut reader = ::csv::Reader::from_reader(reader);
+
+    let names = reader
+        .headers()?
+        .iter()
+        .map(String::from)
+        .collect::<Vec<String>>();
+
+    if names.is_empty() {
+        return Err(DntError::InvalidHeader(String::from(
+            "CSV file has no id column",
+        )));
+    }
+
+    let records = reader
+        .records()
+        .collect::<Result<Vec<::csv::StringRecord>, ::csv::Error>>()?;
+
+    Ok((names, records))
+}
+
+/// Empty cells are left out, as they read as `Null` in numeric columns.
+fn infer_data_type<'a>(cells: impl Iterator<Item = &'a str> + Clone) -> DntDataType {
+    let mut cells = cells.filter(|cell| !cell.is_empty());
+    if cells.clone().next().is_none() {
+        DntDataType::String
+    } else if cells.clone().all(|cell| cell.parse::<i32>().is_ok()) {
+        DntDataType::Int32
+    } else if cells.clone().all(|cell| cell.parse::<u32>().is_ok()) {
+        DntDataType::UInt32
+    } else if cells.all(|cell| cell.parse::<f32>().is_ok()) {
+        DntDataType::Float32
+    } else {
+        DntDataType::String
+    }
+}
+
+fn parse_rows(
+    head: &[DntColumn],
+    records: &[::csv::StringRecord],
+    index: impl Fn(usize) -> usize,
+) -> Result<Vec<DntRow>, DntError> {
+    records
+        .iter()
+        .enumerate()
+        .map(|(row_index, record)| {
+            let values = head
+                .iter()
+                .enumerate()
+                .map(|(column_index, column)| {
+                    let cell = &record[index(column_index)];
+                    if cell.is_empty() && column_index > 0 && is_numeric(column.data_type) {
+                        return Ok(DntValue::Null);
+                    }
+                    parse_value(cell, column.data_type).ok_or_else(|| {
+                        DntError::InvalidValue(format!(
+                            "row {}, column {}: {:?} is not a valid {:?}",
+                            row_index, column.text, cell, column.data_type
+                        ))
+                    })
+                })
+                .collect::<Result<Vec<DntValue>, DntError>>()?;
+
+            Ok(DntRow { values })
+        })
+        .collect()
+}
+
+/// Whether an empty cell of the type is a `Null`, as `to_csv` writes it,
+/// rather than an empty value.
+fn is_numeric(data_type: DntDataType) -> bool {
+    !matches!(data_type, DntDataType::String | DntDataType::Unknown(_))
+}
+
+fn parse_value(cell: &str, data_type: DntDataType) -> Option<DntValue> {
+    match data_type {
+        DntDataType::String => Some(DntValue::String(cell.to_owned())),
+        DntDataType::UInt32 => cell.parse().ok().map(DntValue::UInt32),
+        DntDataType::Int32 => cell.parse().ok().map(DntValue::Int32),
+        DntDataType::Float32 => cell.parse().ok().map(DntValue::Float32),
+        DntDataType::Float64 => cell.parse().ok().map(DntValue::Float64),
+        DntDataType::Unknown(_) => parse_hex(cell).map(DntValue::Bytes),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DntDataType, DntTable, DntTableBuilder, DntValue};
+
+    fn table() -> DntTable {
+        DntTableBuilder::new()
+            .column("_Name", DntDataType::String)
+            .column("_Level", DntDataType::Int32)
+            .column("_Price", DntDataType::UInt32)
+            .column("_Rate", DntDataType::Float32)
+            .row((1, "Sword, sharp", -1, 3_000_000_000u32, 0.5f32))
+            .row((2, "Shield", 2, 20u32, 1.0f32))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn writes_a_record_per_row() {
+        let mut csv = vec![];
+        table().to_csv(&mut csv).unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "id,_Name,_Level,_Price,_Rate\n1,\"Sword, sharp\",-1,3000000000,0.5\n2,Shield,2,20,1\n"
+        );
+    }
+
+    #[test]
+    fn infers_the_types_of_the_columns() {
+        let mut csv = vec![];
+        table().to_csv(&mut csv).unwrap();
+
+        let read = DntTable::from_csv(&csv[..]).unwrap();
+        let types = read
+            .head
+            .iter()
+            .map(|column| column.data_type)
+            .collect::<Vec<DntDataType>>();
+        assert_eq!(
+            types,
+            [
+                DntDataType::Int32,
+                DntDataType::String,
+                DntDataType::Int32,
+                DntDataType::UInt32,
+                DntDataType::Float32
+            ]
+        );
+        assert!(read == table());
+    }
+
+    #[test]
+    fn enforces_the_head() {
+        let head = table().head;
+        assert!(DntTable::from_csv_with_head(&b"id,_Name\n1,Sword\n"[..], &head).is_err());
+        assert!(
+            DntTable::from_csv_with_head(&b"id,_Name,_Level,_Price,_Rate,_Extra\n"[..], &head)
+                .is_err()
+        );
+        assert!(DntTable::from_csv_with_head(
+            &b"_Rate,_Price,_Level,_Name,id\n0.5,1,-2,Sword,1\n"[..],
+            &head
+        )
+        .is_ok());
+        assert!(DntTable::from_csv_with_head(
+            &b"id,_Name,_Level,_Price,_Rate\n1,Sword,1,-1,0.5\n"[..],
+            &head
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn reads_empty_numbers_as_nulls() {
+        let table = DntTableBuilder::new()
+            .column("_Name", DntDataType::String)
+            .column("_Level", DntDataType::Int32)
+            .column("_Rate", DntDataType::Float32)
+            .row((1, "Sword", DntValue::Null, 0.5f32))
+            .row((2, "Shield", 2, DntValue::Null))
+            .build()
+            .unwrap();
+        let mut csv = vec![];
+        table.to_csv(&mut csv).unwrap();
+        assert_eq!(
+            String::from_utf8(csv.clone()).unwrap(),
+            "id,_Name,_Level,_Rate\n1,Sword,,0.5\n2,Shield,2,\n"
+        );
+
+        assert!(DntTable::from_csv_with_head(&csv[..], &table.head).unwrap() == table);
+        let inferred = DntTable::from_csv(&csv[..]).unwrap();
+        assert_eq!(inferred.head[2].data_type, DntDataType::Int32);
+        assert!(inferred == table);
+
+        assert!(DntTable::from_csv_with_head(
+            &b"id,_Name,_Level,_Rate\n,Sword,1,0.5\n"[..],
+            &table.head
+        )
+        .is_err());
+    }
+}
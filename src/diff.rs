@@ -0,0 +1,285 @@
+use std::{collections::HashMap, fmt};
+
+use crate::{view::display_cell, DntColumn, DntDataType, DntError, DntRow, DntTable, DntValue};
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DntDiff {
+    pub schema: Vec<DntSchemaChange>,
+    pub added: Vec<DntRow>,
+    pub removed: Vec<DntRow>,
+    pub changed: Vec<DntRowChange>,
+}
+
+impl DntDiff {
+    pub fn is_empty(&self) -> bool {
+        self.schema.is_empty()
+            && self.added.is_empty()
+            && self.removed.is_empty()
+            && self.changed.is_empty()
+    }
+}
+
+/// One line per change, such as `id 2041: _CoolTime 12 -> 10.5`, for commit
+/// messages and patch notes. Column changes come first, then added, removed
+/// and changed rows. Strings are quoted so that spaces in them stay
+/// readable.
+impl fmt::Display for DntDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.schema {
+            match change {
+                DntSchemaChange::Added { column, .. } => {
+                    writeln!(f, "added column {} ({:?})", column.text, column.data_type)?
+                }
+                DntSchemaChange::Removed(column) => writeln!(f, "removed column {}", column.text)?,
+                DntSchemaChange::TypeChanged { column, old, new } => {
+                    writeln!(f, "column {}: {:?} -> {:?}", column, old, new)?
+                }
+            }
+        }
+        for row in &self.added {
+            writeln!(f, "{} added: {}", RowLabel(row), row)?;
+        }
+        for row in &self.removed {
+            writeln!(f, "{} removed: {}", RowLabel(row), row)?;
+        }
+        for row in &self.changed {
+            for cell in &row.cells {
+                write!(f, "id {}: {} ", row.id, cell.column)?;
+                match &cell.old {
+                    Some(old) => display_cell(f, old)?,
+                    None => f.write_str("(none)")?,
+                }
+                f.write_str(" -> ")?;
+                display_cell(f, &cell.new)?;
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `id 5`, or `row` for rows whose id isn't an `Int32`.
+struct RowLabel<'a>(&'a DntRow);
+
+impl fmt::Display for RowLabel<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match row_id(self.0) {
+            Ok(id) => write!(f, "id {}", id),
+            Err(_) => f.write_str("row"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DntSchemaChange {
+    Added {
+        column: DntColumn,
+        /// The position of the column in the new table.
+        index: usize,
+    },
+    Removed(DntColumn),
+    TypeChanged {
+        column: String,
+        old: DntDataType,
+        new: DntDataType,
+    },
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DntRowChange {
+    pub id: i32,
+    pub cells: Vec<DntCellChange>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DntCellChange {
+    pub column: String,
+    pub old: Option<DntValue>,
+    pub new: DntValue,
+}
+
+pub fn diff(old: &DntTable, new: &DntTable) -> Result<DntDiff, DntError> {
+    let old_ids = id_index(old)?;
+    let new_ids = id_index(new)?;
+
+    let mut schema = vec![];
+    for column in old.head.iter().skip(1) {
+        match column_index(new, &column.text) {
+            None => schema.push(DntSchemaChange::Removed(column.clone())),
+            Some(index) if new.head[index].data_type != column.data_type => {
+                schema.push(DntSchemaChange::TypeChanged {
+                    column: column.text.clone(),
+                    old: column.data_type,
+                    new: new.head[index].data_type,
+                })
+            }
+            Some(_) => {}
+        }
+    }
+    for (index, column) in new.head.iter().enumerate().skip(1) {
+        if column_index(old, &column.text).is_none() {
+            schema.push(DntSchemaChange::Added {
+                column: column.clone(),
+                index,
+            });
+        }
+    }
+
+    let columns = new
+        .head
+        .iter()
+        .enumerate()
+        .skip(1)
+        .map(|(index, column)| (index, column_index(old, &column.text)))
+        .collect::<Vec<(usize, Option<usize>)>>();
+
+    let mut added = vec![];
+    let mut changed = vec![];
+    for row in &new.body {
+        let id = row_id(row)?;
+        let Some(&old_index) = old_ids.get(&id) else {
+            added.push(row.clone());
+            continue;
+        };
+        let old_row = &old.body[old_index];
+
+        let mut cells = vec![];
+        for &(new_column, old_column) in &columns {
+            let Some(value) = row.values.get(new_column) else {
+                continue;
+            };
+            let old_value = old_column.and_then(|index| old_row.values.get(index));
+            if old_value.is_some_and(|old_value| same_value(old_value, value)) {
+                continue;
+            }
+
+            cells.push(DntCellChange {
+                column: new.head[new_column].text.clone(),
+                old: old_value.cloned(),
+                new: value.clone(),
+            });
+        }
+
+        if !cells.is_empty() {
+            changed.push(DntRowChange { id, cells });
+        }
+    }
+
+    let mut removed = vec![];
+    for row in &old.body {
+        if !new_ids.contains_key(&row_id(row)?) {
+            removed.push(row.clone());
+        }
+    }
+
+    Ok(DntDiff {
+        schema,
+        added,
+        removed,
+        changed,
+    })
+}
+
+pub(crate) fn row_id(row: &DntRow) -> Result<i32, DntError> {
+    match row.values.first() {
+        Some(DntValue::Int32(id)) => Ok(*id),
+        other => Err(DntError::InvalidValue(format!(
+            "expected an Int32 id, got {:?}",
+            other
+        ))),
+    }
+}
+
+pub(crate) fn id_index(table: &DntTable) -> Result<HashMap<i32, usize>, DntError> {
+    let mut ids = HashMap::with_capacity(table.body.len());
+
+    for (index, row) in table.body.iter().enumerate() {
+        let id = row_id(row)?;
+        if ids.insert(id, index).is_some() {
+            return Err(DntError::InvalidValue(format!("duplicate id {}", id)));
+        }
+    }
+
+    Ok(ids)
+}
+
+pub(crate) fn column_index(table: &DntTable, name: &str) -> Option<usize> {
+    table
+        .head
+        .iter()
+        .skip(1)
+        .position(|column| column.text == name)
+        .map(|index| index + 1)
+}
+
+pub(crate) fn same_value(left: &DntValue, right: &DntValue) -> bool {
+    left == right
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{diff, DntDataType, DntSchemaChange, DntTable, DntTableBuilder, DntValue};
+
+    fn tables() -> (DntTable, DntTable) {
+        let old = DntTableBuilder::new()
+            .column("_Name", DntDataType::String)
+            .row((1, "Sword"))
+            .row((2, "Shield"))
+            .row((3, "Bow"))
+            .build()
+            .unwrap();
+        let new = DntTableBuilder::new()
+            .column("_Level", DntDataType::UInt32)
+            .column("_Name", DntDataType::String)
+            .row((4, 10u32, "Axe"))
+            .row((2, 20u32, "Big Shield"))
+            .row((1, 30u32, "Sword"))
+            .build()
+            .unwrap();
+        (old, new)
+    }
+
+    #[test]
+    fn compares_rows_by_id() {
+        let (old, new) = tables();
+        let changes = diff(&old, &new).unwrap();
+
+        assert!(matches!(
+            &changes.schema[..],
+            [DntSchemaChange::Added { column, index: 1 }] if column.text == "_Level"
+        ));
+        assert_eq!(changes.added, [new.body[0].clone()]);
+        assert_eq!(changes.removed, [old.body[2].clone()]);
+
+        let changed = changes
+            .changed
+            .iter()
+            .map(|row| (row.id, row.cells.len()))
+            .collect::<Vec<(i32, usize)>>();
+        assert_eq!(changed, [(2, 2), (1, 1)]);
+        let name = &changes.changed[0].cells[1];
+        assert_eq!(name.column, "_Name");
+        assert_eq!(name.old, Some(DntValue::from("Shield")));
+        assert_eq!(name.new, DntValue::from("Big Shield"));
+
+        assert!(diff(&old, &old).unwrap().is_empty());
+    }
+
+    #[test]
+    fn displays_one_line_per_change() {
+        let (old, new) = tables();
+        assert_eq!(
+            diff(&old, &new).unwrap().to_string(),
+            "added column _Level (UInt32)\n\
+             id 4 added: [4, 10, \"Axe\"]\n\
+             id 3 removed: [3, \"Bow\"]\n\
+             id 2: _Level (none) -> 20\n\
+             id 2: _Name \"Shield\" -> \"Big Shield\"\n\
+             id 1: _Level (none) -> 30\n"
+        );
+    }
+}
@@ -0,0 +1,212 @@
+use std::{collections::HashMap, hash::Hash};
+
+use crate::{DntError, DntTable, DntValue};
+
+/// Groups of row indices, in order of first occurrence. Each group holds at
+/// least two rows.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DntDuplicates {
+    /// Rows that share an id.
+    pub ids: Vec<Vec<usize>>,
+    /// Rows whose values are identical apart from the id.
+    pub rows: Vec<Vec<usize>>,
+}
+
+impl DntDuplicates {
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty() && self.rows.is_empty()
+    }
+}
+
+/// Which row of a group of duplicates `dedup_by` keeps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DedupPolicy {
+    #[default]
+    KeepFirst,
+    KeepLast,
+}
+
+#[derive(PartialEq, Eq, Hash)]
+pub(crate) enum ValueKey<'a> {
+    String(&'a str),
+    UInt32(u32),
+    Int32(i32),
+    Float32(u32),
+    Float64(u64),
+    Bytes(&'a [u8]),
+    Null,
+}
+
+impl<'a> From<&'a DntValue> for ValueKey<'a> {
+    fn from(value: &'a DntValue) -> Self {
+        match value {
+            DntValue::String(value) => ValueKey::String(value),
+            DntValue::UInt32(value) => ValueKey::UInt32(*value),
+            DntValue::Int32(value) => ValueKey::Int32(*value),
+            DntValue::Float32(value) => ValueKey::Float32(value.to_bits()),
+            DntValue::Float64(value) => ValueKey::Float64(value.to_bits()),
+            DntValue::Bytes(value) => ValueKey::Bytes(value),
+            DntValue::Null => ValueKey::Null,
+        }
+    }
+}
+
+impl DntTable {
+    pub fn duplicates(&self) -> DntDuplicates {
+        DntDuplicates {
+            ids: groups(
+                self.body
+                    .iter()
+                    .map(|row| row.values.first().map(ValueKey::from)),
+            ),
+            rows: groups(self.body.iter().map(|row| {
+                row.values
+                    .iter()
+                    .skip(1)
+                    .map(ValueKey::from)
+                    .collect::<Vec<ValueKey>>()
+            })),
+        }
+    }
+
+    /// Removes the rows whose values in `columns` are the same as those of
+    /// another row, keeping one row per group as `policy` says. The rows left
+    /// keep their order. Returns how many rows were removed. At least one
+    /// column must be given.
+    pub fn dedup_by(&mut self, columns: &[&str], policy: DedupPolicy) -> Result<usize, DntError> {
+        if columns.is_empty() {
+            return Err(DntError::InvalidValue(String::from(
+                "no columns to compare rows by",
+            )));
+        }
+        let indices = columns
+            .iter()
+            .map(|column| self.column_position(column))
+            .collect::<Result<Vec<usize>, DntError>>()?;
+
+        let groups = groups(self.body.iter().map(|row| {
+            indices
+                .iter()
+                .map(|&index| row.values.get(index).map(ValueKey::from))
+                .collect::<Vec<Option<ValueKey>>>()
+        }));
+
+        let mut removed = vec![false; self.body.len()];
+        for group in &groups {
+            let kept = match policy {
+                DedupPolicy::KeepFirst => group[0],
+                DedupPolicy::KeepLast => group[group.len() - 1],
+            };
+            for &row in group {
+                removed[row] = row != kept;
+            }
+        }
+
+        let before = self.body.len();
+        let mut row = 0;
+        self.body.retain(|_| {
+            row += 1;
+            !removed[row - 1]
+        });
+        self.refresh_id_index()?;
+
+        Ok(before - self.body.len())
+    }
+}
+
+fn groups<K: Eq + Hash>(keys: impl Iterator<Item = K>) -> Vec<Vec<usize>> {
+    let mut positions = HashMap::new();
+    let mut groups = Vec::<Vec<usize>>::new();
+
+    for (index, key) in keys.enumerate() {
+        let group = *positions.entry(key).or_insert_with(|| {
+            groups.push(vec![]);
+            groups.len() - 1
+        });
+        groups[group].push(index);
+    }
+
+    groups.retain(|group| group.len() > 1);
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DedupPolicy, DntDataType, DntDuplicates, DntError, DntTable, DntTableBuilder};
+
+    fn table() -> DntTable {
+        DntTableBuilder::new()
+            .column("_Skill", DntDataType::UInt32)
+            .column("_Level", DntDataType::UInt32)
+            .row((1, 10u32, 1u32))
+            .row((2, 20u32, 1u32))
+            .row((3, 10u32, 1u32))
+            .row((4, 10u32, 2u32))
+            .row((5, 20u32, 1u32))
+            .build()
+            .unwrap()
+    }
+
+    fn ids(table: &DntTable) -> Vec<i32> {
+        table
+            .body
+            .iter()
+            .map(|row| row.values[0].as_i32().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn keeps_the_first_row_of_each_group() {
+        let mut table = table();
+        let removed = table
+            .dedup_by(&["_Skill", "_Level"], DedupPolicy::KeepFirst)
+            .unwrap();
+        assert_eq!(removed, 2);
+        assert_eq!(ids(&table), [1, 2, 4]);
+        assert!(table.get_by_id(3).is_none());
+    }
+
+    #[test]
+    fn keeps_the_last_row_of_each_group() {
+        let mut table = table();
+        let removed = table.dedup_by(&["_Skill"], DedupPolicy::KeepLast).unwrap();
+        assert_eq!(removed, 3);
+        assert_eq!(ids(&table), [4, 5]);
+    }
+
+    #[test]
+    fn needs_columns_to_compare() {
+        let mut table = table();
+        assert!(matches!(
+            table.dedup_by(&[], DedupPolicy::KeepFirst),
+            Err(DntError::InvalidValue(_))
+        ));
+        assert!(matches!(
+            table.dedup_by(&["_Missing"], DedupPolicy::KeepFirst),
+            Err(DntError::ColumnNotFound(_))
+        ));
+        assert_eq!(table.body.len(), 5);
+    }
+
+    #[test]
+    fn finds_shared_ids_and_identical_rows() {
+        let mut table = table();
+        // Builders refuse repeated ids, so one is made by hand.
+        table.body[4].values[0] = 1.into();
+
+        let duplicates = table.duplicates();
+        assert_eq!(
+            duplicates,
+            DntDuplicates {
+                ids: vec![vec![0, 4]],
+                rows: vec![vec![0, 2], vec![1, 4]],
+            }
+        );
+        assert!(!duplicates.is_empty());
+
+        table
+            .dedup_by(&["_Skill", "_Level"], DedupPolicy::KeepFirst)
+            .unwrap();
+        assert!(table.duplicates().is_empty());
+    }
+}
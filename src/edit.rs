@@ -0,0 +1,280 @@
+use crate::{
+    diff::row_id, DntColumn, DntDataType, DntError, DntField, DntRow, DntTable, DntValue,
+    RowViewMut,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowKey {
+    Id(i32),
+    Index(usize),
+}
+
+impl DntTable {
+    pub fn set(&mut self, row: RowKey, column: &str, value: DntValue) -> Result<(), DntError> {
+        let column_index = self.column_position(column)?;
+
+        let expected = self.head[column_index].data_type;
+        if !value.matches(expected) {
+            return Err(DntError::TypeMismatch {
+                column: column.to_owned(),
+                expected,
+                found: value.data_type(),
+            });
+        }
+
+        let row_index = self.row_index(row)?;
+        if let (0, DntValue::Int32(id)) = (column_index, &value) {
+            if self
+                .index_of_id(*id)
+                .is_some_and(|index| index != row_index)
+            {
+                return Err(DntError::InvalidValue(format!("duplicate id {}", id)));
+            }
+        }
+        let cell = self.body[row_index]
+            .values
+            .get_mut(column_index)
+            .ok_or_else(|| {
+                DntError::InvalidValue(format!("row has no value for column {}", column))
+            })?;
+
+        *cell = value;
+        if column_index == 0 {
+            self.refresh_id_index()?;
+        }
+
+        Ok(())
+    }
+
+    /// Replaces every value of a column with what `update` returns for it,
+    /// such as `|rate: f32| rate * 1.1`. Null cells are left as they are.
+    /// The column type and every cell are checked first, so on error the
+    /// table is unchanged.
+    pub fn update_column<T, F>(&mut self, column: &str, mut update: F) -> Result<(), DntError>
+    where
+        T: DntField,
+        F: FnMut(T) -> T,
+    {
+        let index = self.column_position(column)?;
+
+        let found = self.head[index].data_type;
+        if found != T::DATA_TYPE {
+            return Err(DntError::TypeMismatch {
+                column: column.to_owned(),
+                expected: T::DATA_TYPE,
+                found,
+            });
+        }
+
+        for (row_index, row) in self.body.iter().enumerate() {
+            let value = row.values.get(index).ok_or(DntError::InvalidRowLength {
+                row: row_index,
+                expected: self.head.len(),
+                found: row.values.len(),
+            })?;
+
+            if !value.matches(found) {
+                return Err(DntError::InvalidCell {
+                    row: row_index,
+                    column: column.to_owned(),
+                    expected: found,
+                    found: value.data_type(),
+                });
+            }
+        }
+
+        for row in &mut self.body {
+            let cell = &mut row.values[index];
+            if !cell.is_null() {
+                *cell = update(T::from_value(cell)?).to_value();
+            }
+        }
+
+        if index == 0 {
+            self.refresh_id_index()?;
+        }
+        Ok(())
+    }
+
+    /// Calls `update` on every row in turn, stopping at the first error.
+    /// Rows updated before it keep their changes. If the ids are no longer
+    /// unique afterwards, the id index is dropped and the duplicate reported.
+    pub fn for_each_row_mut<F>(&mut self, mut update: F) -> Result<(), DntError>
+    where
+        F: FnMut(&mut RowViewMut<'_>) -> Result<(), DntError>,
+    {
+        let result = self
+            .body
+            .iter_mut()
+            .try_for_each(|row| update(&mut RowViewMut::new(&self.head, row)));
+
+        let refreshed = self.refresh_id_index();
+        result.and(refreshed)
+    }
+
+    pub fn add_column(
+        &mut self,
+        name: &str,
+        data_type: DntDataType,
+        default: DntValue,
+    ) -> Result<(), DntError> {
+        self.insert_column(self.head.len(), name, data_type, default)
+    }
+
+    /// Like `add_column`, placing the column at `index` instead of at the
+    /// end, or at the end if `index` is past it.
+    pub(crate) fn insert_column(
+        &mut self,
+        index: usize,
+        name: &str,
+        data_type: DntDataType,
+        default: DntValue,
+    ) -> Result<(), DntError> {
+        if self.head.iter().any(|column| column.text == name) {
+            return Err(DntError::InvalidHeader(format!(
+                "column {} already exists",
+                name
+            )));
+        }
+
+        if !default.matches(data_type) {
+            return Err(DntError::TypeMismatch {
+                column: name.to_owned(),
+                expected: data_type,
+                found: default.data_type(),
+            });
+        }
+
+        let index = index.min(self.head.len());
+        self.head.insert(index, DntColumn::new(name, data_type));
+        for row in &mut self.body {
+            let index = index.min(row.values.len());
+            row.values.insert(index, default.clone());
+        }
+
+        Ok(())
+    }
+
+    pub fn remove_column(&mut self, name: &str) -> Result<(), DntError> {
+        let index = self.column_position(name)?;
+        if index == 0 {
+            return Err(DntError::InvalidHeader(String::from(
+                "the id column cannot be removed",
+            )));
+        }
+
+        self.head.remove(index);
+        for row in &mut self.body {
+            if index < row.values.len() {
+                row.values.remove(index);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn rename_column(&mut self, old: &str, new: &str) -> Result<(), DntError> {
+        let index = self.column_position(old)?;
+        if old != new && self.head.iter().any(|column| column.text == new) {
+            return Err(DntError::InvalidHeader(format!(
+                "column {} already exists",
+                new
+            )));
+        }
+
+        self.head[index].text = new.to_owned();
+        Ok(())
+    }
+
+    pub fn insert_row(&mut self, values: Vec<DntValue>) -> Result<i32, DntError> {
+        let row = self.checked_row(values)?;
+        let id = row_id(&row)?;
+        if self.index_of_id(id).is_some() {
+            return Err(DntError::InvalidValue(format!("duplicate id {}", id)));
+        }
+
+        self.body.push(row);
+        if let Some(ids) = &mut self.id_index {
+            ids.insert(id, self.body.len() - 1);
+        }
+
+        Ok(id)
+    }
+
+    /// A row made of `values`, if they match the columns of the table.
+    pub(crate) fn checked_row(&self, values: Vec<DntValue>) -> Result<DntRow, DntError> {
+        if values.len() != self.head.len() {
+            return Err(DntError::InvalidValue(format!(
+                "expected {} values, got {}",
+                self.head.len(),
+                values.len()
+            )));
+        }
+
+        for (column, value) in self.head.iter().zip(&values) {
+            if !value.matches(column.data_type) {
+                return Err(DntError::TypeMismatch {
+                    column: column.text.clone(),
+                    expected: column.data_type,
+                    found: value.data_type(),
+                });
+            }
+        }
+
+        Ok(DntRow { values })
+    }
+
+    pub fn insert_row_with_next_id(&mut self, values: Vec<DntValue>) -> Result<i32, DntError> {
+        let id = match self
+            .body
+            .iter()
+            .map(row_id)
+            .try_fold(None, |max, id| id.map(|id| max.max(Some(id))))?
+        {
+            Some(max) => max
+                .checked_add(1)
+                .ok_or_else(|| DntError::InvalidValue(String::from("no free id left")))?,
+            None => 1,
+        };
+
+        let mut row = Vec::with_capacity(values.len() + 1);
+        row.push(DntValue::Int32(id));
+        row.extend(values);
+
+        self.insert_row(row)
+    }
+
+    pub fn remove_row(&mut self, id: i32) -> Result<DntRow, DntError> {
+        let index = self.row_index(RowKey::Id(id))?;
+        let row = self.body.remove(index);
+
+        self.refresh_id_index()?;
+        Ok(row)
+    }
+
+    pub(crate) fn column_position(&self, name: &str) -> Result<usize, DntError> {
+        self.head
+            .iter()
+            .position(|column| column.text == name)
+            .ok_or_else(|| DntError::ColumnNotFound(name.to_owned()))
+    }
+
+    pub(crate) fn row_index(&self, row: RowKey) -> Result<usize, DntError> {
+        match row {
+            RowKey::Index(index) if index < self.body.len() => Ok(index),
+            RowKey::Id(id) => self.index_of_id(id).ok_or(DntError::RowNotFound(row)),
+            RowKey::Index(_) => Err(DntError::RowNotFound(row)),
+        }
+    }
+}
+
+pub(crate) fn default_value(data_type: DntDataType) -> DntValue {
+    match data_type {
+        DntDataType::String => DntValue::String(String::new()),
+        DntDataType::UInt32 => DntValue::UInt32(0),
+        DntDataType::Int32 => DntValue::Int32(0),
+        DntDataType::Float32 => DntValue::Float32(0.0),
+        DntDataType::Float64 => DntValue::Float64(0.0),
+        DntDataType::Unknown(_) => DntValue::Bytes(vec![]),
+    }
+}
@@ -0,0 +1,36 @@
+/// Byte order of the integers and floats in a table. Files of the PC client
+/// are little-endian; some converted and console variants are big-endian.
+/// Only the numbers are affected: the magic, string bytes and footer text
+/// are the same either way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Endianness {
+    #[default]
+    Little,
+    Big,
+}
+
+macro_rules! impl_endian {
+    ($type:ty, $size:expr, $read:ident, $write:ident) => {
+        pub(crate) fn $read(self, bytes: [u8; $size]) -> $type {
+            match self {
+                Endianness::Little => <$type>::from_le_bytes(bytes),
+                Endianness::Big => <$type>::from_be_bytes(bytes),
+            }
+        }
+
+        pub(crate) fn $write(self, value: $type) -> [u8; $size] {
+            match self {
+                Endianness::Little => value.to_le_bytes(),
+                Endianness::Big => value.to_be_bytes(),
+            }
+        }
+    };
+}
+
+impl Endianness {
+    impl_endian!(u16, 2, read_u16, u16_bytes);
+    impl_endian!(u32, 4, read_u32, u32_bytes);
+    impl_endian!(i32, 4, read_i32, i32_bytes);
+    impl_endian!(f32, 4, read_f32, f32_bytes);
+    impl_endian!(f64, 8, read_f64, f64_bytes);
+}
@@ -0,0 +1,287 @@
+use std::{error::Error, fmt, io};
+
+use crate::{DntDataType, RowKey};
+
+#[derive(Debug)]
+pub enum DntError {
+    Io(io::Error),
+    UnexpectedEof,
+    InvalidColumnType(u8),
+    InvalidHeader(String),
+    InvalidFooter(String),
+    StringTooLong(usize),
+    InvalidValue(String),
+    ColumnNotFound(String),
+    RowNotFound(RowKey),
+    TypeMismatch {
+        column: String,
+        expected: DntDataType,
+        found: DntDataType,
+    },
+    InvalidRowLength {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    InvalidCell {
+        row: usize,
+        column: String,
+        expected: DntDataType,
+        found: DntDataType,
+    },
+    MergeConflict(i32),
+    LimitExceeded {
+        limit: &'static str,
+        found: u64,
+        max: u64,
+    },
+    /// An error met while reading a table, with where it happened. `offset`
+    /// is in bytes from the start of the table data, after decompression for
+    /// compressed files. `row` and `column` are indexes in the file, the id
+    /// column being column 0, and `expected` names what was being read.
+    Parse {
+        offset: u64,
+        row: Option<u32>,
+        column: Option<usize>,
+        expected: String,
+        error: Box<DntError>,
+    },
+    #[cfg(feature = "csv")]
+    Csv(::csv::Error),
+    #[cfg(feature = "arrow")]
+    Arrow(arrow::error::ArrowError),
+    #[cfg(feature = "json")]
+    Json(serde_json::Error),
+    #[cfg(feature = "parquet")]
+    Parquet(parquet::errors::ParquetError),
+    #[cfg(feature = "polars")]
+    Polars(polars::error::PolarsError),
+    #[cfg(feature = "sqlite")]
+    Sqlite(rusqlite::Error),
+    #[cfg(feature = "watch")]
+    Watch(notify::Error),
+    #[cfg(feature = "uistring")]
+    Xml(quick_xml::Error),
+    #[cfg(feature = "xlsx")]
+    XlsxRead(calamine::Error),
+    #[cfg(feature = "xlsx")]
+    XlsxWrite(rust_xlsxwriter::XlsxError),
+    #[cfg(feature = "yaml")]
+    Yaml(serde_yaml_ng::Error),
+}
+
+impl fmt::Display for DntError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DntError::Io(error) => write!(f, "I/O error: {}", error),
+            DntError::UnexpectedEof => write!(f, "Unexpected end of file"),
+            DntError::InvalidColumnType(value) => write!(f, "Invalid column type value: {}", value),
+            DntError::InvalidHeader(message) => write!(f, "Invalid header: {}", message),
+            DntError::InvalidFooter(message) => write!(f, "Invalid footer: {}", message),
+            DntError::StringTooLong(length) => {
+                write!(f, "String of {} bytes does not fit in a u16 length", length)
+            }
+            DntError::InvalidValue(message) => write!(f, "Invalid value: {}", message),
+            DntError::ColumnNotFound(column) => write!(f, "Column not found: {}", column),
+            DntError::RowNotFound(row) => write!(f, "Row not found: {:?}", row),
+            DntError::TypeMismatch {
+                column,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Column {} has type {:?}, expected {:?}",
+                column, found, expected
+            ),
+            DntError::InvalidRowLength {
+                row,
+                expected,
+                found,
+            } => write!(f, "Row {} has {} values, expected {}", row, found, expected),
+            DntError::InvalidCell {
+                row,
+                column,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Row {}, column {} has type {:?}, expected {:?}",
+                row, column, found, expected
+            ),
+            DntError::MergeConflict(id) => write!(f, "Conflicting rows for id {}", id),
+            DntError::LimitExceeded { limit, found, max } => {
+                write!(f, "Limit exceeded: {} is {}, at most {}", limit, found, max)
+            }
+            DntError::Parse {
+                offset,
+                expected,
+                error,
+                ..
+            } => write!(f, "{} at byte {}, reading the {}", error, offset, expected),
+            #[cfg(feature = "csv")]
+            DntError::Csv(error) => write!(f, "CSV error: {}", error),
+            #[cfg(feature = "arrow")]
+            DntError::Arrow(error) => write!(f, "Arrow error: {}", error),
+            #[cfg(feature = "json")]
+            DntError::Json(error) => write!(f, "JSON error: {}", error),
+            #[cfg(feature = "parquet")]
+            DntError::Parquet(error) => write!(f, "Parquet error: {}", error),
+            #[cfg(feature = "polars")]
+            DntError::Polars(error) => write!(f, "Polars error: {}", error),
+            #[cfg(feature = "sqlite")]
+            DntError::Sqlite(error) => write!(f, "SQLite error: {}", error),
+            #[cfg(feature = "watch")]
+            DntError::Watch(error) => write!(f, "Watch error: {}", error),
+            #[cfg(feature = "uistring")]
+            DntError::Xml(error) => write!(f, "XML error: {}", error),
+            #[cfg(feature = "xlsx")]
+            DntError::XlsxRead(error) => write!(f, "XLSX read error: {}", error),
+            #[cfg(feature = "xlsx")]
+            DntError::XlsxWrite(error) => write!(f, "XLSX write error: {}", error),
+            #[cfg(feature = "yaml")]
+            DntError::Yaml(error) => write!(f, "YAML error: {}", error),
+        }
+    }
+}
+
+impl Error for DntError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            DntError::Io(error) => Some(error),
+            DntError::Parse { error, .. } => Some(&**error),
+            #[cfg(feature = "csv")]
+            DntError::Csv(error) => Some(error),
+            #[cfg(feature = "arrow")]
+            DntError::Arrow(error) => Some(error),
+            #[cfg(feature = "json")]
+            DntError::Json(error) => Some(error),
+            #[cfg(feature = "parquet")]
+            DntError::Parquet(error) => Some(error),
+            #[cfg(feature = "polars")]
+            DntError::Polars(error) => Some(error),
+            #[cfg(feature = "sqlite")]
+            DntError::Sqlite(error) => Some(error),
+            #[cfg(feature = "watch")]
+            DntError::Watch(error) => Some(error),
+            #[cfg(feature = "uistring")]
+            DntError::Xml(error) => Some(error),
+            #[cfg(feature = "xlsx")]
+            DntError::XlsxRead(error) => Some(error),
+            #[cfg(feature = "xlsx")]
+            DntError::XlsxWrite(error) => Some(error),
+            #[cfg(feature = "yaml")]
+            DntError::Yaml(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl DntError {
+    /// Adds where the error happened. I/O errors other than the end of the
+    /// file are passed through, and so are errors that already have a
+    /// location.
+    pub(crate) fn at(
+        self,
+        offset: u64,
+        row: Option<u32>,
+        column: Option<usize>,
+        expected: impl FnOnce() -> String,
+    ) -> DntError {
+        match self {
+            DntError::Io(_) | DntError::Parse { .. } => self,
+            error => DntError::Parse {
+                offset,
+                row,
+                column,
+                expected: expected(),
+                error: Box::new(error),
+            },
+        }
+    }
+}
+
+impl From<io::Error> for DntError {
+    fn from(error: io::Error) -> Self {
+        match error.kind() {
+            io::ErrorKind::UnexpectedEof => DntError::UnexpectedEof,
+            _ => DntError::Io(error),
+        }
+    }
+}
+
+#[cfg(feature = "csv")]
+impl From<::csv::Error> for DntError {
+    fn from(error: ::csv::Error) -> Self {
+        DntError::Csv(error)
+    }
+}
+
+#[cfg(feature = "json")]
+impl From<serde_json::Error> for DntError {
+    fn from(error: serde_json::Error) -> Self {
+        DntError::Json(error)
+    }
+}
+
+#[cfg(feature = "arrow")]
+impl From<arrow::error::ArrowError> for DntError {
+    fn from(error: arrow::error::ArrowError) -> Self {
+        DntError::Arrow(error)
+    }
+}
+
+#[cfg(feature = "parquet")]
+impl From<parquet::errors::ParquetError> for DntError {
+    fn from(error: parquet::errors::ParquetError) -> Self {
+        DntError::Parquet(error)
+    }
+}
+
+#[cfg(feature = "polars")]
+impl From<polars::error::PolarsError> for DntError {
+    fn from(error: polars::error::PolarsError) -> Self {
+        DntError::Polars(error)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl From<rusqlite::Error> for DntError {
+    fn from(error: rusqlite::Error) -> Self {
+        DntError::Sqlite(error)
+    }
+}
+
+#[cfg(feature = "watch")]
+impl From<notify::Error> for DntError {
+    fn from(error: notify::Error) -> Self {
+        DntError::Watch(error)
+    }
+}
+
+#[cfg(feature = "uistring")]
+impl From<quick_xml::Error> for DntError {
+    fn from(error: quick_xml::Error) -> Self {
+        DntError::Xml(error)
+    }
+}
+
+#[cfg(feature = "xlsx")]
+impl From<calamine::Error> for DntError {
+    fn from(error: calamine::Error) -> Self {
+        DntError::XlsxRead(error)
+    }
+}
+
+#[cfg(feature = "xlsx")]
+impl From<rust_xlsxwriter::XlsxError> for DntError {
+    fn from(error: rust_xlsxwriter::XlsxError) -> Self {
+        DntError::XlsxWrite(error)
+    }
+}
+
+#[cfg(feature = "yaml")]
+impl From<serde_yaml_ng::Error> for DntError {
+    fn from(error: serde_yaml_ng::Error) -> Self {
+        DntError::Yaml(error)
+    }
+}
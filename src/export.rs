@@ -0,0 +1,97 @@
+use std::borrow::Cow;
+
+use crate::{bytes::to_hex, ColumnMapping, DntValue};
+
+/// How values are written as text by `to_csv_with`, `to_json_with`,
+/// `to_yaml_with` and `to_markdown`.
+/// Numbers never depend on the locale: integers are plain digits and floats
+/// always use a `.` without grouping.
+#[derive(Debug, Clone)]
+pub struct ExportOptions {
+    /// Digits after the decimal point. If `None`, floats are written with the
+    /// fewest digits that read back as the same value, so a `Float32` of
+    /// 0.25 is `0.25` rather than `0.25000001`.
+    pub float_precision: Option<usize>,
+    /// Drops the zeros that `float_precision` leaves at the end, writing
+    /// `0.25` instead of `0.2500`.
+    pub trim_trailing_zeros: bool,
+    /// Text written for `DntValue::Null` in CSV and Markdown. JSON always
+    /// uses `null`.
+    pub null: String,
+    /// Writes line breaks and tabs in strings as `\n`, `\r` and `\t` in CSV
+    /// and Markdown.
+    pub escape_newlines: bool,
+    /// The columns written, their order and their names.
+    pub columns: ColumnMapping,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            float_precision: None,
+            trim_trailing_zeros: true,
+            null: String::new(),
+            escape_newlines: false,
+            columns: ColumnMapping::default(),
+        }
+    }
+}
+
+impl ExportOptions {
+    pub(crate) fn format_f32(&self, value: f32) -> String {
+        match self.float_precision {
+            Some(precision) if value.is_finite() => self.format_fixed(value as f64, precision),
+            _ => value.to_string(),
+        }
+    }
+
+    pub(crate) fn format_f64(&self, value: f64) -> String {
+        match self.float_precision {
+            Some(precision) if value.is_finite() => self.format_fixed(value, precision),
+            _ => value.to_string(),
+        }
+    }
+
+    fn format_fixed(&self, value: f64, precision: usize) -> String {
+        let mut text = format!("{:.*}", precision, value);
+
+        if self.trim_trailing_zeros && text.contains('.') {
+            text.truncate(text.trim_end_matches('0').trim_end_matches('.').len());
+        }
+        if text
+            .trim_start_matches('-')
+            .bytes()
+            .all(|byte| byte == b'0' || byte == b'.')
+        {
+            text = text.trim_start_matches('-').to_owned();
+        }
+
+        text
+    }
+
+    /// The text of any value, for the formats that write values as text.
+    pub(crate) fn format_value(&self, value: &DntValue) -> String {
+        match value {
+            DntValue::String(value) => self.format_string(value).into_owned(),
+            DntValue::UInt32(value) => value.to_string(),
+            DntValue::Int32(value) => value.to_string(),
+            DntValue::Float32(value) => self.format_f32(*value),
+            DntValue::Float64(value) => self.format_f64(*value),
+            DntValue::Bytes(value) => to_hex(value),
+            DntValue::Null => self.null.clone(),
+        }
+    }
+
+    pub(crate) fn format_string<'a>(&self, value: &'a str) -> Cow<'a, str> {
+        if self.escape_newlines && value.contains(['\n', '\r', '\t']) {
+            Cow::Owned(
+                value
+                    .replace('\n', "\\n")
+                    .replace('\r', "\\r")
+                    .replace('\t', "\\t"),
+            )
+        } else {
+            Cow::Borrowed(value)
+        }
+    }
+}
@@ -0,0 +1,360 @@
+use std::{
+    cell::RefCell,
+    ffi::{c_char, CStr, CString},
+    fs::File,
+    ptr,
+};
+
+use encoding_rs::Encoding;
+
+use crate::{DntError, DntFileReader, DntFileWriter, DntTable, DntValue};
+
+/// A table opened through the C API. Strings returned for it stay valid
+/// until the next call on the same table, or until it is freed.
+pub struct DntFfiTable {
+    table: DntTable,
+    encoding: Option<&'static Encoding>,
+    names: Vec<CString>,
+    text: CString,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_error(error: impl ToString) {
+    let message = CString::new(error.to_string().replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+}
+
+unsafe fn to_str<'a>(value: *const c_char) -> Result<&'a str, DntError> {
+    if value.is_null() {
+        return Err(DntError::InvalidValue(String::from(
+            "unexpected NULL string",
+        )));
+    }
+
+    CStr::from_ptr(value)
+        .to_str()
+        .map_err(|_| DntError::InvalidValue(String::from("the string is not valid UTF-8")))
+}
+
+unsafe fn open(path: *const c_char, encoding: *const c_char) -> Result<DntFfiTable, DntError> {
+    let path = to_str(path)?;
+    let encoding = if encoding.is_null() {
+        None
+    } else {
+        let label = to_str(encoding)?;
+        Some(
+            Encoding::for_label(label.as_bytes())
+                .ok_or_else(|| DntError::InvalidValue(format!("unknown encoding: {}", label)))?,
+        )
+    };
+
+    let mut reader = DntFileReader::new(File::open(path)?);
+    if let Some(encoding) = encoding {
+        reader = reader.with_encoding(encoding);
+    }
+    reader.read()?;
+
+    let table = std::mem::take(reader.data());
+    let names = table
+        .head
+        .iter()
+        .map(|column| CString::new(column.text.replace('\0', " ")).unwrap_or_default())
+        .collect();
+
+    Ok(DntFfiTable {
+        table,
+        encoding,
+        names,
+        text: CString::default(),
+    })
+}
+
+fn cell(table: &DntFfiTable, row: usize, column: usize) -> Result<&DntValue, DntError> {
+    table
+        .table
+        .body
+        .get(row)
+        .ok_or_else(|| DntError::InvalidValue(format!("no row {}", row)))?
+        .values
+        .get(column)
+        .ok_or_else(|| DntError::InvalidValue(format!("no column {}", column)))
+}
+
+/// Reads a table. `encoding` is a WHATWG label such as `"euc-kr"`, or `NULL`
+/// for Latin-1. Returns `NULL` on failure.
+///
+/// # Safety
+///
+/// `path` must be a NUL-terminated string, and `encoding` one or `NULL`.
+#[no_mangle]
+pub unsafe extern "C" fn dnt_table_open(
+    path: *const c_char,
+    encoding: *const c_char,
+) -> *mut DntFfiTable {
+    match open(path, encoding) {
+        Ok(table) => Box::into_raw(Box::new(table)),
+        Err(error) => {
+            set_error(error);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// # Safety
+///
+/// `table` must come from `dnt_table_open` and not be used afterwards. It
+/// may be `NULL`.
+#[no_mangle]
+pub unsafe extern "C" fn dnt_table_free(table: *mut DntFfiTable) {
+    if !table.is_null() {
+        drop(Box::from_raw(table));
+    }
+}
+
+/// # Safety
+///
+/// `table` must be a table returned by `dnt_table_open`.
+#[no_mangle]
+pub unsafe extern "C" fn dnt_table_row_count(table: *const DntFfiTable) -> usize {
+    let table = &*table;
+    table.table.body.len()
+}
+
+/// The number of columns, including the id.
+///
+/// # Safety
+///
+/// `table` must be a table returned by `dnt_table_open`.
+#[no_mangle]
+pub unsafe extern "C" fn dnt_table_column_count(table: *const DntFfiTable) -> usize {
+    let table = &*table;
+    table.table.head.len()
+}
+
+/// Returns `NULL` if there is no such column.
+///
+/// # Safety
+///
+/// `table` must be a table returned by `dnt_table_open`.
+#[no_mangle]
+pub unsafe extern "C" fn dnt_table_column_name(
+    table: *const DntFfiTable,
+    column: usize,
+) -> *const c_char {
+    let table = &*table;
+    match table.names.get(column) {
+        Some(name) => name.as_ptr(),
+        None => {
+            set_error(format!("no column {}", column));
+            ptr::null()
+        }
+    }
+}
+
+/// The type byte stored in the file: 1 for strings, 2 for `u32`, 3 for
+/// `i32`, and 4 or 5 for `f32`. Returns 0 if there is no such column.
+///
+/// # Safety
+///
+/// `table` must be a table returned by `dnt_table_open`.
+#[no_mangle]
+pub unsafe extern "C" fn dnt_table_column_type(table: *const DntFfiTable, column: usize) -> u8 {
+    let table = &*table;
+    table
+        .table
+        .head
+        .get(column)
+        .map_or(0, |column| column.raw_data_type())
+}
+
+/// Any cell as text, as written by `DntValue`'s `Display`. Returns `NULL` if
+/// the cell doesn't exist.
+///
+/// # Safety
+///
+/// `table` must be a table returned by `dnt_table_open`.
+#[no_mangle]
+pub unsafe extern "C" fn dnt_table_cell_string(
+    table: *mut DntFfiTable,
+    row: usize,
+    column: usize,
+) -> *const c_char {
+    let table = &mut *table;
+    let text = match cell(table, row, column) {
+        Ok(value) => value.to_string().replace('\0', " "),
+        Err(error) => {
+            set_error(error);
+            return ptr::null();
+        }
+    };
+
+    table.text = CString::new(text).unwrap_or_default();
+    table.text.as_ptr()
+}
+
+/// Stores an integer cell in `value`. Returns `false` if the cell doesn't
+/// exist or isn't an integer.
+///
+/// # Safety
+///
+/// `table` must be a table returned by `dnt_table_open` and `value` must
+/// point to an `int64_t`.
+#[no_mangle]
+pub unsafe extern "C" fn dnt_table_cell_int(
+    table: *const DntFfiTable,
+    row: usize,
+    column: usize,
+    value: *mut i64,
+) -> bool {
+    match cell(&*table, row, column) {
+        Ok(DntValue::UInt32(cell)) => *value = i64::from(*cell),
+        Ok(DntValue::Int32(cell)) => *value = i64::from(*cell),
+        Ok(other) => {
+            set_error(format!("{:?} is not an integer", other));
+            return false;
+        }
+        Err(error) => {
+            set_error(error);
+            return false;
+        }
+    }
+    true
+}
+
+/// Stores a numeric cell in `value`, converting integers. Returns `false` if
+/// the cell doesn't exist or isn't a number.
+///
+/// # Safety
+///
+/// `table` must be a table returned by `dnt_table_open` and `value` must
+/// point to a `double`.
+#[no_mangle]
+pub unsafe extern "C" fn dnt_table_cell_float(
+    table: *const DntFfiTable,
+    row: usize,
+    column: usize,
+    value: *mut f64,
+) -> bool {
+    match cell(&*table, row, column) {
+        Ok(cell) => match cell.as_f64() {
+            Some(cell) => *value = cell,
+            None => {
+                set_error(format!("{:?} is not a number", cell));
+                return false;
+            }
+        },
+        Err(error) => {
+            set_error(error);
+            return false;
+        }
+    }
+    true
+}
+
+/// Writes the table to `path`, in the encoding it was opened with.
+///
+/// # Safety
+///
+/// `table` must be a table returned by `dnt_table_open` and `path` a
+/// NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn dnt_table_write(table: *const DntFfiTable, path: *const c_char) -> bool {
+    let table = &*table;
+    let result = to_str(path).and_then(|path| {
+        let mut writer = DntFileWriter::new(File::create(path)?);
+        if let Some(encoding) = table.encoding {
+            writer = writer.with_encoding(encoding);
+        }
+        writer.write(&table.table)
+    });
+
+    match result {
+        Ok(()) => true,
+        Err(error) => {
+            set_error(error);
+            false
+        }
+    }
+}
+
+/// The message of the last error on this thread, or `NULL` if there was
+/// none. It stays valid until the next error.
+#[no_mangle]
+pub extern "C" fn dnt_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .map_or(ptr::null(), |error| error.as_ptr())
+    })
+}
+
+impl DntFfiTable {
+    /// The table behind a handle, for Rust code that receives one.
+    pub fn table(&self) -> &DntTable {
+        &self.table
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        ffi::{CStr, CString},
+        fs, ptr,
+    };
+
+    use super::*;
+    use crate::{DntDataType, DntTableBuilder};
+
+    #[test]
+    fn opens_reads_and_writes_tables() {
+        let directory = std::env::temp_dir().join(format!("dnt-ffi-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("items.dnt");
+        let copy = directory.join("copy.dnt");
+
+        let table = DntTableBuilder::new()
+            .column("_Name", DntDataType::String)
+            .column("_Rate", DntDataType::Float32)
+            .row((7, "Sword", 0.5f32))
+            .build()
+            .unwrap();
+        fs::write(&path, table.to_bytes().unwrap()).unwrap();
+        let c_path = CString::new(path.to_str().unwrap()).unwrap();
+        let c_copy = CString::new(copy.to_str().unwrap()).unwrap();
+
+        unsafe {
+            let handle = dnt_table_open(c_path.as_ptr(), ptr::null());
+            assert!(!handle.is_null());
+            assert_eq!(dnt_table_row_count(handle), 1);
+            assert_eq!(dnt_table_column_count(handle), 3);
+            assert_eq!(CStr::from_ptr(dnt_table_column_name(handle, 1)), c"_Name");
+            assert!(dnt_table_column_name(handle, 3).is_null());
+            assert_eq!(dnt_table_column_type(handle, 2), 4);
+            assert_eq!(
+                CStr::from_ptr(dnt_table_cell_string(handle, 0, 1)),
+                c"Sword"
+            );
+
+            let mut int = 0;
+            assert!(dnt_table_cell_int(handle, 0, 0, &mut int));
+            assert_eq!(int, 7);
+            assert!(!dnt_table_cell_int(handle, 0, 1, &mut int));
+            assert!(!dnt_last_error().is_null());
+            let mut float = 0.0;
+            assert!(dnt_table_cell_float(handle, 0, 2, &mut float));
+            assert_eq!(float, 0.5);
+
+            assert!(dnt_table_write(handle, c_copy.as_ptr()));
+            dnt_table_free(handle);
+
+            let missing = CString::new(directory.join("missing.dnt").to_str().unwrap()).unwrap();
+            assert!(dnt_table_open(missing.as_ptr(), ptr::null()).is_null());
+        }
+
+        assert_eq!(fs::read(&copy).unwrap(), fs::read(&path).unwrap());
+        fs::remove_dir_all(directory).unwrap();
+    }
+}
@@ -0,0 +1,128 @@
+use std::io::{self, ErrorKind, Read, Seek, SeekFrom};
+
+use crate::DntFileReader;
+
+/// Makes a stream that cannot seek, such as standard input or a pipe,
+/// usable by `DntFileReader`. Seeking forward reads and discards the bytes
+/// in between and seeking to the end reads the rest of the stream; seeking
+/// backwards fails. This is enough for `read`, `rows` and `with_columns`,
+/// but not for `read_row_at` or a lenient footer that turns out to be
+/// missing.
+pub struct ForwardReader<R> {
+    inner: R,
+    position: u64,
+}
+
+impl<R: Read> ForwardReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self { inner, position: 0 }
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn skip(&mut self, amount: u64) -> io::Result<u64> {
+        let skipped = io::copy(&mut (&mut self.inner).take(amount), &mut io::sink())?;
+        self.position += skipped;
+        Ok(self.position)
+    }
+}
+
+impl<R: Read> Read for ForwardReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let length = self.inner.read(buf)?;
+        self.position += length as u64;
+        Ok(length)
+    }
+}
+
+impl<R: Read> Seek for ForwardReader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let target = match pos {
+            SeekFrom::Start(target) => Some(target),
+            SeekFrom::Current(offset) => self.position.checked_add_signed(offset),
+            SeekFrom::End(0) => return self.skip(u64::MAX),
+            SeekFrom::End(_) => None,
+        };
+
+        match target {
+            Some(target) if target >= self.position => self.skip(target - self.position),
+            _ => Err(io::Error::new(
+                ErrorKind::Unsupported,
+                "cannot seek backwards in a stream",
+            )),
+        }
+    }
+
+    fn stream_position(&mut self) -> io::Result<u64> {
+        Ok(self.position)
+    }
+}
+
+impl<R: Read> DntFileReader<ForwardReader<R>> {
+    /// Reads a table from a stream that cannot seek, see `ForwardReader`.
+    pub fn from_stream(reader: R) -> Self {
+        DntFileReader::new(ForwardReader::new(reader))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DntDataType, DntError, DntFileReader, DntTable, DntTableBuilder, ReadOptions};
+
+    fn table() -> DntTable {
+        DntTableBuilder::new()
+            .column("_Name", DntDataType::String)
+            .row((1, "Sword"))
+            .row((2, "Shield"))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn reads_a_stream_that_cannot_seek() {
+        let bytes = table().to_bytes().unwrap();
+
+        let mut reader = DntFileReader::from_stream(&bytes[..]);
+        reader.read().unwrap();
+        assert!(*reader.data() == table());
+        assert_eq!(reader.trailing_bytes(), 0);
+    }
+
+    #[test]
+    fn checks_the_footer() {
+        let mut bytes = table().to_bytes().unwrap();
+        *bytes.last_mut().unwrap() = b'X';
+
+        let mut reader = DntFileReader::from_stream(&bytes[..]);
+        assert!(matches!(
+            reader.read(),
+            Err(DntError::Parse { error, .. }) if matches!(*error, DntError::InvalidFooter(_))
+        ));
+    }
+
+    #[test]
+    fn counts_and_keeps_trailing_bytes() {
+        let mut bytes = table().to_bytes().unwrap();
+        bytes.extend(b"abc");
+
+        let mut reader = DntFileReader::from_stream(&bytes[..]);
+        reader.read().unwrap();
+        assert_eq!(reader.trailing_bytes(), 3);
+
+        let mut reader = DntFileReader::from_stream(&bytes[..]).with_options(ReadOptions {
+            keep_trailing_bytes: true,
+            ..ReadOptions::default()
+        });
+        reader.read().unwrap();
+        assert_eq!(reader.data().trailing_bytes, b"abc");
+
+        let mut reader = DntFileReader::from_stream(&bytes[..]).with_options(ReadOptions::strict());
+        assert!(reader.read().is_err());
+    }
+}
@@ -0,0 +1,244 @@
+use std::collections::HashMap;
+
+use crate::{sort::compare_values, DntColumn, DntDataType, DntError, DntRow, DntTable, DntValue};
+
+/// What `DntGroupBy::aggregate` computes over the values of a column in
+/// each group. Null cells are left out of every aggregate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DntAggregate {
+    Count,
+    Sum,
+    Min,
+    Max,
+    Mean,
+}
+
+impl DntAggregate {
+    fn name(self) -> &'static str {
+        match self {
+            DntAggregate::Count => "count",
+            DntAggregate::Sum => "sum",
+            DntAggregate::Min => "min",
+            DntAggregate::Max => "max",
+            DntAggregate::Mean => "mean",
+        }
+    }
+}
+
+/// The rows of a table grouped by the values of one column, in the order
+/// each value first appears.
+pub struct DntGroupBy<'a> {
+    table: &'a DntTable,
+    key: usize,
+    groups: Vec<(&'a DntValue, Vec<usize>)>,
+}
+
+impl DntTable {
+    pub fn group_by(&self, column: &str) -> Result<DntGroupBy<'_>, DntError> {
+        let key = self.column_position(column)?;
+        if key == 0 || column == "id" {
+            // Ids are unique, and the aggregated table has an id column of
+            // its own.
+            return Err(DntError::InvalidValue(format!(
+                "cannot group by {}, the id column",
+                column
+            )));
+        }
+
+        let mut positions = HashMap::new();
+        let mut groups: Vec<(&DntValue, Vec<usize>)> = vec![];
+        for (index, row) in self.body.iter().enumerate() {
+            let Some(value) = row.values.get(key) else {
+                continue;
+            };
+            let position = *positions.entry(value).or_insert_with(|| {
+                groups.push((value, vec![]));
+                groups.len() - 1
+            });
+            groups[position].1.push(index);
+        }
+
+        Ok(DntGroupBy {
+            table: self,
+            key,
+            groups,
+        })
+    }
+}
+
+impl DntGroupBy<'_> {
+    pub fn len(&self) -> usize {
+        self.groups.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    /// Builds a table with one row per group: a new id counting from 1, the
+    /// value grouped by, then one column per aggregate, named after the
+    /// column and the aggregate, such as `_LevelLimit_max`. Counts are
+    /// `UInt32`, sums and means `Float64`, and minimums and maximums keep the
+    /// type of their column. Only `Count` accepts columns that aren't
+    /// numeric.
+    pub fn aggregate(&self, aggregates: &[(&str, DntAggregate)]) -> Result<DntTable, DntError> {
+        let table = self.table;
+        let key_column = &table.head[self.key];
+
+        let mut head = vec![
+            DntColumn::new("id", DntDataType::Int32),
+            DntColumn::new(&key_column.text, key_column.data_type),
+        ];
+        let mut indices = vec![];
+        for &(column, aggregate) in aggregates {
+            let index = table.column_position(column)?;
+            let data_type = table.head[index].data_type;
+            let numeric = !matches!(data_type, DntDataType::String | DntDataType::Unknown(_));
+            if aggregate != DntAggregate::Count && !numeric {
+                return Err(DntError::InvalidValue(format!(
+                    "cannot compute the {} of column {}, which is not numeric",
+                    aggregate.name(),
+                    column
+                )));
+            }
+
+            let output_type = match aggregate {
+                DntAggregate::Count => DntDataType::UInt32,
+                DntAggregate::Sum | DntAggregate::Mean => DntDataType::Float64,
+                DntAggregate::Min | DntAggregate::Max => data_type,
+            };
+            head.push(DntColumn::new(
+                &format!("{}_{}", column, aggregate.name()),
+                output_type,
+            ));
+            indices.push((index, aggregate));
+        }
+
+        let body = self
+            .groups
+            .iter()
+            .enumerate()
+            .map(|(group, (key, rows))| {
+                let mut values = vec![DntValue::Int32(group as i32 + 1), (*key).clone()];
+                for &(index, aggregate) in &indices {
+                    let cells = rows
+                        .iter()
+                        .filter_map(|&row| table.body[row].values.get(index))
+                        .filter(|value| !value.is_null());
+                    values.push(compute(aggregate, cells)?);
+                }
+                Ok(DntRow { values })
+            })
+            .collect::<Result<Vec<DntRow>, DntError>>()?;
+
+        Ok(DntTable::from_parts(head, body))
+    }
+}
+
+fn compute<'a>(
+    aggregate: DntAggregate,
+    cells: impl Iterator<Item = &'a DntValue>,
+) -> Result<DntValue, DntError> {
+    Ok(match aggregate {
+        DntAggregate::Count => {
+            let count = cells.count();
+            DntValue::UInt32(u32::try_from(count).map_err(|_| {
+                DntError::InvalidValue(format!("{} values do not fit in a UInt32", count))
+            })?)
+        }
+        DntAggregate::Sum => DntValue::Float64(cells.filter_map(DntValue::as_f64).sum()),
+        DntAggregate::Mean => {
+            let (sum, count) = cells
+                .filter_map(DntValue::as_f64)
+                .fold((0.0, 0), |(sum, count), value| (sum + value, count + 1));
+            if count == 0 {
+                DntValue::Null
+            } else {
+                DntValue::Float64(sum / count as f64)
+            }
+        }
+        DntAggregate::Min => cells
+            .min_by(|left, right| compare_values(left, right))
+            .cloned()
+            .unwrap_or(DntValue::Null),
+        DntAggregate::Max => cells
+            .max_by(|left, right| compare_values(left, right))
+            .cloned()
+            .unwrap_or(DntValue::Null),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DntAggregate, DntDataType, DntError, DntTable, DntTableBuilder, DntValue};
+
+    fn table() -> DntTable {
+        DntTableBuilder::new()
+            .column("_Job", DntDataType::String)
+            .column("_Level", DntDataType::UInt32)
+            .row((1, "Warrior", 10u32))
+            .row((2, "Archer", 20u32))
+            .row((3, "Warrior", 30u32))
+            .row((4, "Warrior", DntValue::Null))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn aggregates_each_group() {
+        let table = table();
+        let groups = table.group_by("_Job").unwrap();
+        assert_eq!(groups.len(), 2);
+
+        let aggregated = groups
+            .aggregate(&[
+                ("_Level", DntAggregate::Count),
+                ("_Level", DntAggregate::Sum),
+                ("_Level", DntAggregate::Max),
+                ("_Level", DntAggregate::Mean),
+            ])
+            .unwrap();
+        let names = aggregated
+            .head
+            .iter()
+            .map(|column| column.text.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(
+            names,
+            [
+                "id",
+                "_Job",
+                "_Level_count",
+                "_Level_sum",
+                "_Level_max",
+                "_Level_mean"
+            ]
+        );
+        assert_eq!(
+            aggregated.body[0].values,
+            [
+                DntValue::Int32(1),
+                DntValue::from("Warrior"),
+                DntValue::UInt32(2),
+                DntValue::Float64(40.0),
+                DntValue::UInt32(30),
+                DntValue::Float64(20.0),
+            ]
+        );
+        assert_eq!(aggregated.body[1].values[1], DntValue::from("Archer"));
+
+        assert!(matches!(
+            groups.aggregate(&[("_Job", DntAggregate::Sum)]),
+            Err(DntError::InvalidValue(_))
+        ));
+    }
+
+    #[test]
+    fn does_not_group_by_the_id() {
+        let table = table();
+        assert!(matches!(
+            table.group_by("id"),
+            Err(DntError::InvalidValue(_))
+        ));
+    }
+}
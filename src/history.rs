@@ -0,0 +1,383 @@
+use std::collections::VecDeque;
+
+use crate::{DntColumn, DntDataType, DntError, DntRow, DntTable, DntValue, RowKey};
+
+/// How many changes `DntHistory::new` keeps.
+const DEFAULT_LIMIT: usize = 100;
+
+/// One recorded change, with what is needed to undo and redo it.
+#[derive(Debug, Clone)]
+enum DntChange {
+    Cell {
+        row: usize,
+        column: usize,
+        old: DntValue,
+        new: DntValue,
+    },
+    InsertRow {
+        index: usize,
+        row: DntRow,
+    },
+    RemoveRow {
+        index: usize,
+        row: DntRow,
+    },
+    AddColumn {
+        index: usize,
+        column: DntColumn,
+        /// Filled when the change is undone, for redoing it.
+        values: Vec<Option<DntValue>>,
+    },
+    RemoveColumn {
+        index: usize,
+        column: DntColumn,
+        values: Vec<Option<DntValue>>,
+    },
+    RenameColumn {
+        index: usize,
+        old: String,
+        new: String,
+    },
+}
+
+impl DntChange {
+    fn undo(&mut self, table: &mut DntTable) {
+        match self {
+            DntChange::Cell {
+                row, column, old, ..
+            } => set_cell(table, *row, *column, old.clone()),
+            DntChange::InsertRow { index, .. } => {
+                table.body.remove(*index);
+                reindex(table);
+            }
+            DntChange::RemoveRow { index, row } => {
+                table.body.insert(*index, row.clone());
+                reindex(table);
+            }
+            DntChange::AddColumn { index, values, .. } => {
+                *values = remove_column(table, *index);
+            }
+            DntChange::RemoveColumn {
+                index,
+                column,
+                values,
+            } => insert_column(table, *index, column.clone(), values),
+            DntChange::RenameColumn { index, old, .. } => table.head[*index].text = old.clone(),
+        }
+    }
+
+    fn redo(&mut self, table: &mut DntTable) {
+        match self {
+            DntChange::Cell {
+                row, column, new, ..
+            } => set_cell(table, *row, *column, new.clone()),
+            DntChange::InsertRow { index, row } => {
+                table.body.insert(*index, row.clone());
+                reindex(table);
+            }
+            DntChange::RemoveRow { index, .. } => {
+                table.body.remove(*index);
+                reindex(table);
+            }
+            DntChange::AddColumn {
+                index,
+                column,
+                values,
+            } => insert_column(table, *index, column.clone(), values),
+            DntChange::RemoveColumn { index, values, .. } => {
+                *values = remove_column(table, *index);
+            }
+            DntChange::RenameColumn { index, new, .. } => table.head[*index].text = new.clone(),
+        }
+    }
+}
+
+fn set_cell(table: &mut DntTable, row: usize, column: usize, value: DntValue) {
+    table.body[row].values[column] = value;
+    if column == 0 {
+        reindex(table);
+    }
+}
+
+/// Undoing and redoing only bring back rows whose ids were checked when the
+/// change was first made, so rebuilding the index can't find duplicates.
+fn reindex(table: &mut DntTable) {
+    let _ = table.refresh_id_index();
+}
+
+fn remove_column(table: &mut DntTable, index: usize) -> Vec<Option<DntValue>> {
+    table.head.remove(index);
+    table
+        .body
+        .iter_mut()
+        .map(|row| (index < row.values.len()).then(|| row.values.remove(index)))
+        .collect()
+}
+
+fn insert_column(
+    table: &mut DntTable,
+    index: usize,
+    column: DntColumn,
+    values: &[Option<DntValue>],
+) {
+    table.head.insert(index, column);
+    for (row, value) in table.body.iter_mut().zip(values) {
+        if let Some(value) = value {
+            row.values.insert(index, value.clone());
+        }
+    }
+}
+
+/// A table whose edits are recorded so they can be undone and redone, for
+/// editors. Edits go through the methods of the history, which behave like
+/// those of `DntTable`; the table itself is only lent out for reading. The
+/// oldest changes are forgotten once there are more than the limit.
+#[derive(Clone)]
+pub struct DntHistory {
+    table: DntTable,
+    undo: VecDeque<DntChange>,
+    redo: Vec<DntChange>,
+    limit: usize,
+}
+
+impl DntHistory {
+    pub fn new(table: DntTable) -> Self {
+        Self {
+            table,
+            undo: VecDeque::new(),
+            redo: vec![],
+            limit: DEFAULT_LIMIT,
+        }
+    }
+
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self.forget_oldest();
+        self
+    }
+
+    pub fn table(&self) -> &DntTable {
+        &self.table
+    }
+
+    pub fn into_inner(self) -> DntTable {
+        self.table
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Reverts the last change. Returns `false` if there was none.
+    pub fn undo(&mut self) -> bool {
+        let Some(mut change) = self.undo.pop_back() else {
+            return false;
+        };
+        change.undo(&mut self.table);
+        self.redo.push(change);
+        true
+    }
+
+    /// Applies again the last change undone. Returns `false` if there was
+    /// none, or if an edit was made since.
+    pub fn redo(&mut self) -> bool {
+        let Some(mut change) = self.redo.pop() else {
+            return false;
+        };
+        change.redo(&mut self.table);
+        self.undo.push_back(change);
+        true
+    }
+
+    /// Forgets every recorded change, keeping the table as it is.
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    fn record(&mut self, change: DntChange) {
+        self.redo.clear();
+        self.undo.push_back(change);
+        self.forget_oldest();
+    }
+
+    fn forget_oldest(&mut self) {
+        while self.undo.len() > self.limit {
+            self.undo.pop_front();
+        }
+    }
+
+    pub fn set(&mut self, row: RowKey, column: &str, value: DntValue) -> Result<(), DntError> {
+        let row = self.table.row_index(row)?;
+        let column_index = self.table.column_position(column)?;
+        let old = self.table.body[row]
+            .values
+            .get(column_index)
+            .cloned()
+            .unwrap_or(DntValue::Null);
+
+        self.table.set(RowKey::Index(row), column, value.clone())?;
+        self.record(DntChange::Cell {
+            row,
+            column: column_index,
+            old,
+            new: value,
+        });
+        Ok(())
+    }
+
+    pub fn insert_row(&mut self, values: Vec<DntValue>) -> Result<i32, DntError> {
+        let id = self.table.insert_row(values)?;
+        self.record_inserted_row();
+        Ok(id)
+    }
+
+    pub fn insert_row_with_next_id(&mut self, values: Vec<DntValue>) -> Result<i32, DntError> {
+        let id = self.table.insert_row_with_next_id(values)?;
+        self.record_inserted_row();
+        Ok(id)
+    }
+
+    fn record_inserted_row(&mut self) {
+        let index = self.table.body.len() - 1;
+        let row = self.table.body[index].clone();
+        self.record(DntChange::InsertRow { index, row });
+    }
+
+    pub fn remove_row(&mut self, id: i32) -> Result<DntRow, DntError> {
+        let index = self.table.row_index(RowKey::Id(id))?;
+        let row = self.table.remove_row(id)?;
+        self.record(DntChange::RemoveRow {
+            index,
+            row: row.clone(),
+        });
+        Ok(row)
+    }
+
+    pub fn add_column(
+        &mut self,
+        name: &str,
+        data_type: DntDataType,
+        default: DntValue,
+    ) -> Result<(), DntError> {
+        self.table.add_column(name, data_type, default)?;
+        let index = self.table.head.len() - 1;
+        self.record(DntChange::AddColumn {
+            index,
+            column: self.table.head[index].clone(),
+            values: vec![],
+        });
+        Ok(())
+    }
+
+    pub fn remove_column(&mut self, name: &str) -> Result<(), DntError> {
+        let index = self.table.column_position(name)?;
+        let column = self.table.head[index].clone();
+        let values = self
+            .table
+            .body
+            .iter()
+            .map(|row| row.values.get(index).cloned())
+            .collect();
+
+        self.table.remove_column(name)?;
+        self.record(DntChange::RemoveColumn {
+            index,
+            column,
+            values,
+        });
+        Ok(())
+    }
+
+    pub fn rename_column(&mut self, old: &str, new: &str) -> Result<(), DntError> {
+        let index = self.table.column_position(old)?;
+        self.table.rename_column(old, new)?;
+        self.record(DntChange::RenameColumn {
+            index,
+            old: old.to_owned(),
+            new: new.to_owned(),
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DntDataType, DntHistory, DntTable, DntTableBuilder, DntValue, RowKey};
+
+    fn table() -> DntTable {
+        DntTableBuilder::new()
+            .column("_Name", DntDataType::String)
+            .row((1, "Sword"))
+            .row((2, "Shield"))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn undoes_and_redoes_every_change() {
+        let mut history = DntHistory::new(table());
+        assert!(!history.can_undo() && !history.undo());
+
+        history
+            .set(RowKey::Id(1), "_Name", DntValue::from("Axe"))
+            .unwrap();
+        history
+            .insert_row(vec![DntValue::Int32(3), DntValue::from("Bow")])
+            .unwrap();
+        history.remove_row(2).unwrap();
+        history
+            .add_column("_Level", DntDataType::UInt32, DntValue::UInt32(1))
+            .unwrap();
+        history.rename_column("_Name", "_Title").unwrap();
+        history.remove_column("_Level").unwrap();
+        let edited = history.table().clone();
+
+        while history.undo() {}
+        assert!(*history.table() == table());
+        assert!(history.can_redo());
+
+        while history.redo() {}
+        assert!(*history.table() == edited);
+        assert!(!history.can_redo());
+    }
+
+    #[test]
+    fn drops_redo_after_a_new_edit() {
+        let mut history = DntHistory::new(table());
+        history
+            .set(RowKey::Id(1), "_Name", DntValue::from("Axe"))
+            .unwrap();
+        assert!(history.undo());
+        assert!(history.can_redo());
+
+        history.remove_row(2).unwrap();
+        assert!(!history.can_redo());
+        assert!(!history.redo());
+        assert_eq!(history.table().body.len(), 1);
+        assert_eq!(history.table().body[0].values[1], DntValue::from("Sword"));
+    }
+
+    #[test]
+    fn keeps_only_the_last_changes() {
+        let mut history = DntHistory::new(table()).with_limit(1);
+        history
+            .set(RowKey::Id(1), "_Name", DntValue::from("Axe"))
+            .unwrap();
+        history
+            .set(RowKey::Id(2), "_Name", DntValue::from("Bow"))
+            .unwrap();
+
+        assert!(history.undo());
+        assert!(!history.undo());
+        assert_eq!(history.table().body[0].values[1], DntValue::from("Axe"));
+        assert_eq!(
+            history.into_inner().body[1].values[1],
+            DntValue::from("Shield")
+        );
+    }
+}
@@ -0,0 +1,207 @@
+use std::io::Write;
+
+use crate::{DntDataType, DntError, DntTable, DntValue, ExportOptions};
+
+/// Sorts the rows when a column header is clicked, numerically for numeric
+/// columns, and hides the rows that don't contain the text of the filter.
+const SCRIPT: &str = r##"<script>
+const table = document.querySelector("table");
+const body = table.tBodies[0];
+document.querySelector("#filter").addEventListener("input", (event) => {
+  const text = event.target.value.toLowerCase();
+  for (const row of body.rows) {
+    row.hidden = !row.textContent.toLowerCase().includes(text);
+  }
+});
+table.querySelectorAll("th").forEach((header, column) => {
+  header.addEventListener("click", () => {
+    const ascending = header.dataset.order !== "asc";
+    table.querySelectorAll("th").forEach((other) => delete other.dataset.order);
+    header.dataset.order = ascending ? "asc" : "desc";
+    const key = (row) => row.cells[column].textContent;
+    const compare = header.classList.contains("number")
+      ? (a, b) => (parseFloat(key(a)) || 0) - (parseFloat(key(b)) || 0)
+      : (a, b) => key(a).localeCompare(key(b));
+    const rows = [...body.rows].sort((a, b) => (ascending ? 1 : -1) * compare(a, b));
+    body.append(...rows);
+  });
+});
+</script>
+"##;
+
+const STYLE: &str = "<style>
+body { font-family: sans-serif; }
+table { border-collapse: collapse; }
+th, td { border: 1px solid #ccc; padding: 2px 6px; }
+th { background: #eee; cursor: pointer; }
+th[data-order=asc]::after { content: \" \\25B2\"; }
+th[data-order=desc]::after { content: \" \\25BC\"; }
+.number { text-align: right; }
+</style>
+";
+
+#[derive(Debug, Clone)]
+pub struct HtmlOptions {
+    /// The title of the page.
+    pub title: String,
+    /// Adds the inline script that sorts by a column when its header is
+    /// clicked and filters the rows with a text box. Without it the page is
+    /// plain HTML and CSS.
+    pub script: bool,
+    /// How values are written and which columns, as for CSV.
+    pub export: ExportOptions,
+}
+
+impl Default for HtmlOptions {
+    fn default() -> Self {
+        Self {
+            title: String::from("DNT table"),
+            script: true,
+            export: ExportOptions::default(),
+        }
+    }
+}
+
+impl DntTable {
+    /// Writes a standalone HTML page holding the table, for sharing data
+    /// with people who don't have the tools to open DNT files. Everything
+    /// is inline, so the page is a single file.
+    pub fn to_html(&self, options: &HtmlOptions) -> Result<String, DntError> {
+        let mut bytes = vec![];
+        self.write_html(&mut bytes, options)?;
+
+        String::from_utf8(bytes).map_err(|_| DntError::InvalidValue(String::from("invalid UTF-8")))
+    }
+
+    pub fn write_html<W: Write>(
+        &self,
+        mut writer: W,
+        options: &HtmlOptions,
+    ) -> Result<(), DntError> {
+        let export = &options.export;
+        let mapping = export.columns.resolve(self)?;
+        let numeric = mapping
+            .iter()
+            .map(|(index, _)| {
+                !matches!(
+                    self.head[*index].data_type,
+                    DntDataType::String | DntDataType::Unknown(_)
+                )
+            })
+            .collect::<Vec<bool>>();
+        let class = |numeric: bool| if numeric { " class=\"number\"" } else { "" };
+
+        writeln!(writer, "<!DOCTYPE html>")?;
+        writeln!(writer, "<html>\n<head>\n<meta charset=\"utf-8\">")?;
+        writeln!(writer, "<title>{}</title>", escape(&options.title))?;
+        write!(writer, "{}", STYLE)?;
+        writeln!(writer, "</head>\n<body>")?;
+        if options.script {
+            writeln!(
+                writer,
+                "<input id=\"filter\" type=\"search\" placeholder=\"Filter\">"
+            )?;
+        }
+
+        writeln!(writer, "<table>\n<thead>\n<tr>")?;
+        for ((_, name), numeric) in mapping.iter().zip(&numeric) {
+            writeln!(writer, "<th{}>{}</th>", class(*numeric), escape(name))?;
+        }
+        writeln!(writer, "</tr>\n</thead>\n<tbody>")?;
+
+        for row in &self.body {
+            write!(writer, "<tr>")?;
+            for ((index, _), numeric) in mapping.iter().zip(&numeric) {
+                let text =
+                    escape(&export.format_value(row.values.get(*index).unwrap_or(&DntValue::Null)));
+                write!(writer, "<td{}>{}</td>", class(*numeric), text)?;
+            }
+            writeln!(writer, "</tr>")?;
+        }
+        writeln!(writer, "</tbody>\n</table>")?;
+
+        if options.script {
+            write!(writer, "{}", SCRIPT)?;
+        }
+        writeln!(writer, "</body>\n</html>")?;
+
+        Ok(())
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            character => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ColumnMapping, DntDataType, DntTableBuilder, DntValue, ExportOptions, HtmlOptions,
+    };
+
+    #[test]
+    fn escapes_cells_and_marks_numeric_columns() {
+        let table = DntTableBuilder::new()
+            .column("_Name", DntDataType::String)
+            .column("_Level", DntDataType::UInt32)
+            .row((1, "<b>Sword</b> & \"Shield\"", 10u32))
+            .build()
+            .unwrap();
+
+        let html = table.to_html(&HtmlOptions::default()).unwrap();
+        assert!(html.contains("<th class=\"number\">id</th>\n<th>_Name</th>\n"));
+        assert!(html.contains(
+            "<tr><td class=\"number\">1</td><td>&lt;b&gt;Sword&lt;/b&gt; &amp; &quot;Shield&quot;</td><td class=\"number\">10</td></tr>"
+        ));
+        assert!(html.contains("<script>"));
+
+        let options = HtmlOptions {
+            title: String::from("Items <1>"),
+            script: false,
+            export: ExportOptions {
+                columns: ColumnMapping::new().order(["_Name"]),
+                ..ExportOptions::default()
+            },
+        };
+        let html = table.to_html(&options).unwrap();
+        assert!(html.contains("<title>Items &lt;1&gt;</title>"));
+        assert!(!html.contains("<script>") && !html.contains("id=\"filter\""));
+        assert!(!html.contains("<td class=\"number\">"));
+    }
+
+    #[test]
+    fn formats_values_like_the_other_exports() {
+        let table = DntTableBuilder::new()
+            .column("_Rate", DntDataType::Float32)
+            .column("_Name", DntDataType::String)
+            .row((1, 0.1f32, DntValue::Null))
+            .build()
+            .unwrap();
+
+        let html = table.to_html(&HtmlOptions::default()).unwrap();
+        assert!(html.contains("<td class=\"number\">0.1</td><td></td>"));
+
+        let options = HtmlOptions {
+            export: ExportOptions {
+                float_precision: Some(2),
+                trim_trailing_zeros: false,
+                null: String::from("-"),
+                ..ExportOptions::default()
+            },
+            ..HtmlOptions::default()
+        };
+        let html = table.to_html(&options).unwrap();
+        assert!(html.contains("<td class=\"number\">0.10</td><td>-</td>"));
+    }
+}
@@ -0,0 +1,137 @@
+use crate::{
+    diff::{id_index, row_id},
+    DntColumn, DntError, DntRow, DntTable,
+};
+
+impl DntTable {
+    pub fn from_parts(head: Vec<DntColumn>, body: Vec<DntRow>) -> Self {
+        Self {
+            magic: [0; 4],
+            extra_header: vec![],
+            head,
+            body,
+            trailing_bytes: vec![],
+            id_index: None,
+        }
+    }
+
+    /// Copies the bytes kept from the file `other` was read from, other than
+    /// its columns and rows.
+    pub(crate) fn copy_file_bytes(&mut self, other: &DntTable) {
+        self.magic = other.magic;
+        self.extra_header = other.extra_header.clone();
+        self.trailing_bytes = other.trailing_bytes.clone();
+    }
+
+    pub fn build_id_index(&mut self) -> Result<(), DntError> {
+        self.id_index = Some(id_index(self)?);
+        Ok(())
+    }
+
+    pub fn has_id_index(&self) -> bool {
+        self.id_index.is_some()
+    }
+
+    pub fn get_by_id(&self, id: i32) -> Option<&DntRow> {
+        self.index_of_id(id).map(|index| &self.body[index])
+    }
+
+    pub fn get_by_id_mut(&mut self, id: i32) -> Option<&mut DntRow> {
+        self.index_of_id(id).map(|index| &mut self.body[index])
+    }
+
+    /// Where the row with `id` is. With an index, only the index is asked,
+    /// so rows pushed onto `body` directly aren't found until
+    /// `build_id_index` is called again. Without one, every row is scanned.
+    pub(crate) fn index_of_id(&self, id: i32) -> Option<usize> {
+        match &self.id_index {
+            Some(ids) => ids.get(&id).copied(),
+            None => self
+                .body
+                .iter()
+                .position(|row| row_id(row).ok() == Some(id)),
+        }
+    }
+
+    /// Rebuilds the index after an edit, if there is one. When the rows no
+    /// longer have unique Int32 ids the index is dropped and the reason
+    /// returned.
+    pub(crate) fn refresh_id_index(&mut self) -> Result<(), DntError> {
+        if self.id_index.is_some() {
+            self.id_index = None;
+            self.id_index = Some(id_index(self)?);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DntDataType, DntError, DntRow, DntTable, DntTableBuilder, DntValue, RowKey};
+
+    fn table() -> DntTable {
+        let mut table = DntTableBuilder::new()
+            .column("_Name", DntDataType::String)
+            .row((1, "Fire"))
+            .build()
+            .unwrap();
+        table.build_id_index().unwrap();
+        table
+    }
+
+    #[test]
+    fn trusts_the_index_until_it_is_rebuilt() {
+        let mut table = table();
+        table.body.push(DntRow {
+            values: vec![DntValue::Int32(2), DntValue::from("Ice")],
+        });
+
+        assert!(table.get_by_id(2).is_none());
+        assert!(table.get_by_id_mut(2).is_none());
+
+        table.build_id_index().unwrap();
+        assert_eq!(table.get_by_id(2), Some(&table.body[1]));
+        assert!(table
+            .insert_row(vec![DntValue::Int32(2), DntValue::from("Ice")])
+            .is_err());
+        assert_eq!(table.body.len(), 2);
+    }
+
+    #[test]
+    fn scans_without_an_index() {
+        let mut table = table();
+        table.id_index = None;
+        table.body[0].values[0] = DntValue::Int32(5);
+
+        assert!(table.get_by_id(1).is_none());
+        assert_eq!(table.get_by_id(5), Some(&table.body[0]));
+    }
+
+    #[test]
+    fn rejects_ids_already_in_use() {
+        let mut table = table();
+        table
+            .insert_row(vec![DntValue::Int32(2), DntValue::from("Ice")])
+            .unwrap();
+
+        assert!(matches!(
+            table.set(RowKey::Id(2), "id", DntValue::Int32(1)),
+            Err(DntError::InvalidValue(_))
+        ));
+        assert!(table.has_id_index());
+        assert_eq!(table.get_by_id(2), Some(&table.body[1]));
+    }
+
+    #[test]
+    fn reports_duplicates_when_refreshing() {
+        let mut table = table();
+        table
+            .insert_row(vec![DntValue::Int32(2), DntValue::from("Ice")])
+            .unwrap();
+
+        let result = table.for_each_row_mut(|row| row.set("id", DntValue::Int32(3)));
+        assert!(matches!(result, Err(DntError::InvalidValue(_))));
+        assert!(!table.has_id_index());
+        assert!(table.build_id_index().is_err());
+    }
+}
@@ -0,0 +1,366 @@
+use std::{
+    fmt::{self, Write as _},
+    io::{Read, Seek, SeekFrom},
+};
+
+use crate::{profile, DntColumn, DntDataType, DntError, DntFileReader, DntLayout};
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InspectOptions {
+    /// How many bytes of each span to show in hex, none if 0.
+    pub hex_bytes: usize,
+}
+
+/// A stretch of the table data. Offsets are in bytes from the start of the
+/// data, after decompression for compressed files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    pub offset: u64,
+    pub length: u64,
+    /// The first `InspectOptions::hex_bytes` bytes, as hex pairs.
+    pub hex: Option<String>,
+}
+
+impl Span {
+    fn new(offset: u64, end: u64) -> Self {
+        Self {
+            offset,
+            length: end - offset,
+            hex: None,
+        }
+    }
+
+    pub fn end(&self) -> u64 {
+        self.offset + self.length
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnDefinition {
+    /// The index of the column, the implicit id column being 0.
+    pub index: usize,
+    pub name: String,
+    pub raw_data_type: u8,
+    /// The type the code stands for, with the profile of the reader.
+    pub data_type: DntDataType,
+    /// The length-prefixed name followed by the type byte.
+    pub span: Span,
+}
+
+/// Where each part of a file is. Parts that could not be found because the
+/// file is damaged are left empty, and `problem` says why.
+#[derive(Debug, Clone, Default)]
+pub struct Inspection {
+    pub compressed: bool,
+    pub magic: Option<Span>,
+    /// The column and row counts.
+    pub counts: Option<Span>,
+    /// The bytes read with `with_extra_header_length`, if any.
+    pub extra_header: Option<Span>,
+    /// The number of columns in the header, including the id.
+    pub columns_expected: usize,
+    pub rows_expected: u32,
+    pub columns: Vec<ColumnDefinition>,
+    /// Where the first row starts, right after the last column definition.
+    pub body_start: Option<u64>,
+    pub rows: Vec<Span>,
+    pub footer: Option<Span>,
+    pub trailing: Option<Span>,
+    pub problem: Option<String>,
+}
+
+impl Inspection {
+    fn spans_mut(&mut self) -> impl Iterator<Item = &mut Span> {
+        self.magic
+            .iter_mut()
+            .chain(&mut self.counts)
+            .chain(&mut self.extra_header)
+            .chain(self.columns.iter_mut().map(|column| &mut column.span))
+            .chain(&mut self.rows)
+            .chain(&mut self.footer)
+            .chain(&mut self.trailing)
+    }
+}
+
+impl<R: Read + Seek> DntFileReader<R> {
+    /// Walks the file and records the offset and size of each of its parts,
+    /// without keeping any values. Only I/O errors are returned as errors;
+    /// the first other problem stops the walk and is kept in the inspection.
+    pub fn inspect(&mut self, options: &InspectOptions) -> Result<Inspection, DntError> {
+        let mut inspection = Inspection::default();
+
+        // Sets up decompression, and checks the header the way `read` does.
+        let header = self.read_header();
+        inspection.compressed = self.inflated.is_some();
+
+        let walked = match header {
+            Err(DntError::Io(error)) => return Err(DntError::Io(error)),
+            Err(error) => self.walk(&mut inspection).and(Err(error)),
+            Ok(_) => self.walk(&mut inspection),
+        };
+        match walked {
+            Ok(()) => {}
+            Err(DntError::Io(error)) => return Err(DntError::Io(error)),
+            Err(error) => inspection.problem = Some(error.to_string()),
+        }
+
+        if options.hex_bytes > 0 {
+            for span in inspection.spans_mut() {
+                self.seek(span.offset)?;
+                let length = span.length.min(options.hex_bytes as u64);
+                let mut bytes = vec![];
+                self.input().take(length).read_to_end(&mut bytes)?;
+                span.hex = Some(hex(&bytes, length < span.length));
+            }
+        }
+
+        Ok(inspection)
+    }
+
+    fn walk(&mut self, inspection: &mut Inspection) -> Result<(), DntError> {
+        self.seek(0)?;
+        self.read_array::<4>()?;
+        inspection.magic = Some(Span::new(0, self.offset));
+
+        let columns_nb = self.read_u16()?;
+        inspection.columns_expected = columns_nb as usize + 1;
+        inspection.rows_expected = self.read_u32()?;
+        inspection.counts = Some(Span::new(4, self.offset));
+        if self.extra_header_length > 0 {
+            self.read_bytes(self.extra_header_length)?;
+            inspection.extra_header = Some(Span::new(10, self.offset));
+        }
+
+        let mut head = vec![DntColumn::new("id", DntDataType::Int32)];
+        for index in 1..=columns_nb as usize {
+            let start = self.offset;
+            let length = self.read_u16()? as usize;
+            let bytes = self.read_bytes(length)?;
+            let name = self.codec.decode(&bytes)?;
+            let raw_data_type = self.read_u8()?;
+            let data_type = profile::data_type(&self.type_codes, raw_data_type);
+
+            head.push(DntColumn {
+                text: name.clone(),
+                data_type,
+                raw_data_type,
+            });
+            inspection.columns.push(ColumnDefinition {
+                index,
+                name,
+                raw_data_type,
+                data_type,
+                span: Span::new(start, self.offset),
+            });
+        }
+        inspection.body_start = Some(self.offset);
+
+        // Every column is read, whatever `with_columns` selected, so that a
+        // row cut short is noticed.
+        let layout = DntLayout {
+            columns: head.iter().map(|column| (column.data_type, true)).collect(),
+            names: head.iter().map(|column| column.text.clone()).collect(),
+            head,
+            nulls: None,
+            endianness: self.endianness,
+        };
+        for row in 0..inspection.rows_expected {
+            let start = self.offset;
+            self.read_row(&layout, row)?;
+            inspection.rows.push(Span::new(start, self.offset));
+        }
+
+        let body_end = self.offset;
+        let end = self.seek_from(SeekFrom::End(0))?;
+        self.seek(body_end)?;
+        let footer = self.check_footer();
+        if footer.is_ok() && self.options.footer.is_some() {
+            inspection.footer = Some(Span::new(body_end, self.offset));
+        }
+        let footer_end = inspection.footer.as_ref().map_or(body_end, Span::end);
+        if end > footer_end {
+            inspection.trailing = Some(Span::new(footer_end, end));
+        }
+
+        footer.map_err(|error| error.at(body_end, None, None, || String::from("footer")))
+    }
+}
+
+fn hex(bytes: &[u8], truncated: bool) -> String {
+    let mut hex = String::with_capacity(bytes.len() * 3 + 3);
+    for (index, byte) in bytes.iter().enumerate() {
+        if index > 0 {
+            hex.push(' ');
+        }
+        let _ = write!(hex, "{:02x}", byte);
+    }
+    if truncated {
+        hex.push_str(" ..");
+    }
+    hex
+}
+
+/// One line per part: its offset, its length, what it is and its hex bytes
+/// if they were requested.
+impl fmt::Display for Inspection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut line = |span: &Span, label: &str| {
+            write!(f, "{:>10} {:>8}  {}", span.offset, span.length, label)?;
+            match &span.hex {
+                Some(hex) => writeln!(f, "  {}", hex),
+                None => writeln!(f),
+            }
+        };
+
+        if let Some(magic) = &self.magic {
+            line(magic, "magic")?;
+        }
+        if let Some(counts) = &self.counts {
+            line(
+                counts,
+                &format!(
+                    "{} columns, {} rows",
+                    self.columns_expected, self.rows_expected
+                ),
+            )?;
+        }
+        if let Some(extra_header) = &self.extra_header {
+            line(extra_header, "extra header")?;
+        }
+        for column in &self.columns {
+            line(
+                &column.span,
+                &format!(
+                    "column {} {} ({:?})",
+                    column.index, column.name, column.data_type
+                ),
+            )?;
+        }
+        for (index, row) in self.rows.iter().enumerate() {
+            line(row, &format!("row {}", index))?;
+        }
+        if let Some(footer) = &self.footer {
+            line(footer, "footer")?;
+        }
+        if let Some(trailing) = &self.trailing {
+            line(trailing, "trailing bytes")?;
+        }
+        if let Some(problem) = &self.problem {
+            writeln!(f, "{}", problem)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::{InspectOptions, Inspection, Span};
+    use crate::{DntDataType, DntFileReader, DntTable, DntTableBuilder};
+
+    fn table() -> DntTable {
+        DntTableBuilder::new()
+            .column("_Name", DntDataType::String)
+            .column("_Level", DntDataType::UInt32)
+            .row((1, "Sword", 10u32))
+            .row((2, "Bow", 20u32))
+            .build()
+            .unwrap()
+    }
+
+    fn span(offset: u64, end: u64) -> Span {
+        Span::new(offset, end)
+    }
+
+    fn inspect(bytes: &[u8], options: &InspectOptions) -> Inspection {
+        DntFileReader::new(Cursor::new(bytes))
+            .inspect(options)
+            .unwrap()
+    }
+
+    /// Magic and counts take 10 bytes, each column a 2-byte length, its name
+    /// and its type, and each row 4 bytes per number and 2 per string length
+    /// plus the string.
+    fn check_spans(inspection: &Inspection) {
+        assert_eq!(inspection.magic, Some(span(0, 4)));
+        assert_eq!(inspection.counts, Some(span(4, 10)));
+        assert_eq!(inspection.extra_header, None);
+        assert_eq!(inspection.columns_expected, 3);
+        assert_eq!(inspection.rows_expected, 2);
+
+        let columns = inspection
+            .columns
+            .iter()
+            .map(|column| {
+                (
+                    column.name.as_str(),
+                    column.raw_data_type,
+                    column.span.clone(),
+                )
+            })
+            .collect::<Vec<(&str, u8, Span)>>();
+        assert_eq!(
+            columns,
+            [("_Name", 1, span(10, 18)), ("_Level", 2, span(18, 27))]
+        );
+        assert_eq!(inspection.body_start, Some(27));
+        assert_eq!(inspection.rows, [span(27, 42), span(42, 55)]);
+        assert_eq!(inspection.footer, Some(span(55, 61)));
+        assert_eq!(inspection.trailing, None);
+        assert_eq!(inspection.problem, None);
+    }
+
+    #[test]
+    fn finds_every_part() {
+        let mut bytes = table().to_bytes().unwrap();
+        let inspection = inspect(&bytes, &InspectOptions::default());
+        assert!(!inspection.compressed);
+        check_spans(&inspection);
+        assert!(inspection.rows[0].hex.is_none());
+
+        bytes.extend([0; 2]);
+        let inspection = inspect(&bytes, &InspectOptions { hex_bytes: 4 });
+        assert_eq!(
+            inspection.trailing.as_ref().map(|span| span.length),
+            Some(2)
+        );
+        assert_eq!(
+            inspection.magic.unwrap().hex.as_deref(),
+            Some(&*hex(&bytes[..4]))
+        );
+        assert_eq!(inspection.rows[0].hex.as_deref(), Some("01 00 00 00 .."));
+    }
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+    #[test]
+    fn stops_at_a_truncated_row() {
+        let bytes = table().to_bytes().unwrap();
+        let inspection = inspect(&bytes[..50], &InspectOptions::default());
+        assert_eq!(inspection.rows, [span(27, 42)]);
+        assert_eq!(inspection.footer, None);
+        assert!(inspection.problem.is_some());
+    }
+
+    #[cfg(feature = "zlib")]
+    #[test]
+    fn gives_offsets_in_the_decompressed_data() {
+        use crate::{CompressionMode, DntFileWriter};
+
+        let mut writer =
+            DntFileWriter::new(Cursor::new(vec![])).with_compression(CompressionMode::Zlib);
+        writer.write(&table()).unwrap();
+        let bytes = writer.into_inner().unwrap().into_inner();
+
+        let inspection = inspect(&bytes, &InspectOptions::default());
+        assert!(inspection.compressed);
+        check_spans(&inspection);
+    }
+}
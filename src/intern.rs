@@ -0,0 +1,258 @@
+use std::{
+    collections::HashSet,
+    io::{Read, Seek},
+    sync::Arc,
+};
+
+use crate::{DntColumn, DntError, DntFileReader, DntRow, DntTable, DntValue};
+
+/// Hands out one shared allocation per distinct string.
+#[derive(Debug, Clone, Default)]
+pub struct DntInterner {
+    strings: HashSet<Arc<str>>,
+}
+
+impl DntInterner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn intern(&mut self, value: &str) -> Arc<str> {
+        if let Some(interned) = self.strings.get(value) {
+            return interned.clone();
+        }
+
+        let interned = Arc::<str>::from(value);
+        self.strings.insert(interned.clone());
+        interned
+    }
+
+    /// The number of distinct strings.
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+}
+
+/// A value of a `DntInternedTable`, where equal strings share their
+/// allocation.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DntInternedValue {
+    String(Arc<str>),
+    UInt32(u32),
+    Int32(i32),
+    Float32(f32),
+    Float64(f64),
+    Bytes(Vec<u8>),
+    Null,
+}
+
+impl DntInternedValue {
+    fn new(value: DntValue, interner: &mut DntInterner) -> Self {
+        match value {
+            DntValue::String(value) => DntInternedValue::String(interner.intern(&value)),
+            DntValue::UInt32(value) => DntInternedValue::UInt32(value),
+            DntValue::Int32(value) => DntInternedValue::Int32(value),
+            DntValue::Float32(value) => DntInternedValue::Float32(value),
+            DntValue::Float64(value) => DntInternedValue::Float64(value),
+            DntValue::Bytes(value) => DntInternedValue::Bytes(value),
+            DntValue::Null => DntInternedValue::Null,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            DntInternedValue::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn to_value(&self) -> DntValue {
+        match self {
+            DntInternedValue::String(value) => DntValue::String(value.as_ref().to_owned()),
+            DntInternedValue::UInt32(value) => DntValue::UInt32(*value),
+            DntInternedValue::Int32(value) => DntValue::Int32(*value),
+            DntInternedValue::Float32(value) => DntValue::Float32(*value),
+            DntInternedValue::Float64(value) => DntValue::Float64(*value),
+            DntInternedValue::Bytes(value) => DntValue::Bytes(value.clone()),
+            DntInternedValue::Null => DntValue::Null,
+        }
+    }
+}
+
+/// A table read with `read_interned`. Tables such as itemtable repeat the
+/// same icon paths and type names thousands of times; here each distinct
+/// string is stored once.
+#[derive(Debug, Clone, Default)]
+pub struct DntInternedTable {
+    pub magic: [u8; 4],
+    pub extra_header: Vec<u8>,
+    pub head: Vec<DntColumn>,
+    pub body: Vec<Vec<DntInternedValue>>,
+    pub trailing_bytes: Vec<u8>,
+    interner: DntInterner,
+}
+
+impl DntInternedTable {
+    pub fn len(&self) -> usize {
+        self.body.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.body.is_empty()
+    }
+
+    pub fn get(&self, row: usize, column: &str) -> Option<&DntInternedValue> {
+        let index = self.head.iter().position(|head| head.text == column)?;
+        self.body.get(row)?.get(index)
+    }
+
+    /// The interner holding the strings of the table, to intern new values
+    /// against when editing it.
+    pub fn interner(&mut self) -> &mut DntInterner {
+        &mut self.interner
+    }
+
+    /// Copies every value into an owned `DntTable`.
+    pub fn to_table(&self) -> DntTable {
+        let mut table = DntTable::from_parts(
+            self.head.clone(),
+            self.body
+                .iter()
+                .map(|values| DntRow {
+                    values: values.iter().map(DntInternedValue::to_value).collect(),
+                })
+                .collect(),
+        );
+        table.magic = self.magic;
+        table.extra_header = self.extra_header.clone();
+        table.trailing_bytes = self.trailing_bytes.clone();
+        table
+    }
+}
+
+impl DntTable {
+    pub fn to_interned(&self) -> DntInternedTable {
+        let mut interner = DntInterner::new();
+        let body = self
+            .body
+            .iter()
+            .map(|row| {
+                row.values
+                    .iter()
+                    .map(|value| DntInternedValue::new(value.clone(), &mut interner))
+                    .collect()
+            })
+            .collect();
+
+        DntInternedTable {
+            magic: self.magic,
+            extra_header: self.extra_header.clone(),
+            head: self.head.clone(),
+            body,
+            trailing_bytes: self.trailing_bytes.clone(),
+            interner,
+        }
+    }
+}
+
+impl<R: Read + Seek> DntFileReader<R> {
+    /// Reads the table one row at a time, interning its strings as they are
+    /// decoded, so there is never more than one row of duplicate strings in
+    /// memory. Column selection, filters and null rules apply as in `read`.
+    pub fn read_interned(&mut self) -> Result<DntInternedTable, DntError> {
+        let mut header = self.read_header()?;
+        let magic = header.magic;
+        let extra_header = std::mem::take(&mut header.extra_header);
+        let mut interner = DntInterner::new();
+
+        let rows = self.rows_from(header)?;
+        let head = rows.head().to_vec();
+        let body = rows
+            .map(|row| {
+                Ok(row?
+                    .values
+                    .into_iter()
+                    .map(|value| DntInternedValue::new(value, &mut interner))
+                    .collect())
+            })
+            .collect::<Result<Vec<Vec<DntInternedValue>>, DntError>>()?;
+
+        Ok(DntInternedTable {
+            magic,
+            extra_header,
+            head,
+            body,
+            trailing_bytes: std::mem::take(&mut self.trailing),
+            interner,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Cursor, sync::Arc};
+
+    use crate::{
+        DntDataType, DntFileReader, DntInternedValue, DntInterner, DntTable, DntTableBuilder,
+    };
+
+    fn table() -> DntTable {
+        DntTableBuilder::new()
+            .column("_Icon", DntDataType::String)
+            .column("_Level", DntDataType::UInt32)
+            .row((1, "icon.dds", 1u32))
+            .row((2, "other.dds", 2u32))
+            .row((3, "icon.dds", 3u32))
+            .build()
+            .unwrap()
+    }
+
+    fn string(value: &DntInternedValue) -> &Arc<str> {
+        match value {
+            DntInternedValue::String(value) => value,
+            _ => panic!("not a string"),
+        }
+    }
+
+    #[test]
+    fn shares_equal_strings() {
+        let mut interner = DntInterner::new();
+        let first = interner.intern("icon.dds");
+        let second = interner.intern(&String::from("icon.dds"));
+        assert!(Arc::ptr_eq(&first, &second));
+        assert!(!Arc::ptr_eq(&first, &interner.intern("other.dds")));
+        assert_eq!(interner.len(), 2);
+
+        let interned = table().to_interned();
+        assert!(Arc::ptr_eq(
+            string(interned.get(0, "_Icon").unwrap()),
+            string(interned.get(2, "_Icon").unwrap())
+        ));
+        assert!(interned.to_table() == table());
+    }
+
+    #[test]
+    fn reads_like_read() {
+        let bytes = table().to_bytes().unwrap();
+
+        let mut reader = DntFileReader::new(Cursor::new(&bytes));
+        let mut interned = reader.read_interned().unwrap();
+        assert_eq!(interned.len(), 3);
+        assert!(interned.to_table() == DntTable::parse(&bytes).unwrap());
+        assert_eq!(interned.interner().len(), 2);
+        assert!(Arc::ptr_eq(
+            string(&interned.body[0][1]),
+            string(&interned.body[2][1])
+        ));
+
+        let mut reader = DntFileReader::new(Cursor::new(&bytes)).with_columns(["_Level"]);
+        let interned = reader.read_interned().unwrap();
+        let mut reader = DntFileReader::new(Cursor::new(&bytes)).with_columns(["_Level"]);
+        reader.read().unwrap();
+        assert!(interned.to_table() == *reader.data());
+    }
+}
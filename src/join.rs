@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+
+use crate::{DntColumn, DntDataType, DntError, DntRow, DntTable, DntValue};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JoinKind {
+    /// Keeps only the rows that have a match in both tables.
+    Inner,
+    /// Keeps every row of the left table, with nulls for the right columns
+    /// when nothing matches.
+    Left,
+}
+
+impl DntTable {
+    /// Combines the rows of this table with the rows of `other` whose
+    /// `right_column` equals their `left_column`. A row matching several
+    /// rows appears once per match. The result starts with a new id
+    /// counting from 1, followed by the columns of this table prefixed with
+    /// `left.` and those of `other` prefixed with `right.`, ids included.
+    /// Null values match nothing, and `UInt32` and `Int32` values match when
+    /// they are the same number, so that `UInt32` references match ids.
+    pub fn join(
+        &self,
+        other: &DntTable,
+        left_column: &str,
+        right_column: &str,
+        kind: JoinKind,
+    ) -> Result<DntTable, DntError> {
+        let left_index = self.column_position(left_column)?;
+        let right_index = other.column_position(right_column)?;
+
+        let mut matches = HashMap::<JoinKey, Vec<&DntRow>>::new();
+        for row in &other.body {
+            if let Some(value) = row.values.get(right_index).filter(|value| !value.is_null()) {
+                matches.entry(JoinKey::from(value)).or_default().push(row);
+            }
+        }
+
+        let head = std::iter::once(DntColumn::new("id", DntDataType::Int32))
+            .chain(prefixed(&self.head, "left."))
+            .chain(prefixed(&other.head, "right."))
+            .collect::<Vec<DntColumn>>();
+
+        let mut body = vec![];
+        let mut push = |left: &DntRow, right: Option<&DntRow>| {
+            let id = i32::try_from(body.len() + 1)
+                .map_err(|_| DntError::InvalidValue(String::from("too many joined rows")))?;
+            let mut values = Vec::with_capacity(head.len());
+            values.push(DntValue::Int32(id));
+            values.extend(padded(left, self.head.len()));
+            match right {
+                Some(right) => values.extend(padded(right, other.head.len())),
+                None => values.extend((0..other.head.len()).map(|_| DntValue::Null)),
+            }
+            body.push(DntRow { values });
+            Ok::<(), DntError>(())
+        };
+
+        for left in &self.body {
+            let found = left
+                .values
+                .get(left_index)
+                .and_then(|value| matches.get(&JoinKey::from(value)));
+            match found {
+                Some(rights) => {
+                    for right in rights {
+                        push(left, Some(right))?;
+                    }
+                }
+                None if kind == JoinKind::Left => push(left, None)?,
+                None => {}
+            }
+        }
+
+        Ok(DntTable::from_parts(head, body))
+    }
+}
+
+#[derive(PartialEq, Eq, Hash)]
+enum JoinKey<'a> {
+    Integer(i64),
+    Value(&'a DntValue),
+}
+
+impl<'a> From<&'a DntValue> for JoinKey<'a> {
+    fn from(value: &'a DntValue) -> Self {
+        match value {
+            DntValue::UInt32(value) => JoinKey::Integer(i64::from(*value)),
+            DntValue::Int32(value) => JoinKey::Integer(i64::from(*value)),
+            value => JoinKey::Value(value),
+        }
+    }
+}
+
+fn prefixed<'a>(head: &'a [DntColumn], prefix: &'a str) -> impl Iterator<Item = DntColumn> + 'a {
+    head.iter().map(move |column| DntColumn {
+        text: format!("{}{}", prefix, column.text),
+        ..column.clone()
+    })
+}
+
+/// The values of a row, with nulls standing in for any it lacks.
+fn padded(row: &DntRow, length: usize) -> impl Iterator<Item = DntValue> + '_ {
+    (0..length).map(|index| row.values.get(index).cloned().unwrap_or(DntValue::Null))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DntDataType, DntTable, DntTableBuilder, DntValue, JoinKind};
+
+    fn tables() -> (DntTable, DntTable) {
+        let items = DntTableBuilder::new()
+            .column("_Name", DntDataType::String)
+            .column("_SkillID", DntDataType::UInt32)
+            .row((1, "Sword", 20u32))
+            .row((2, "Shield", 30u32))
+            .row((3, "Bow", 20u32))
+            .build()
+            .unwrap();
+        let skills = DntTableBuilder::new()
+            .column("_Skill", DntDataType::String)
+            .row((10, "Slash"))
+            .row((20, "Pierce"))
+            .build()
+            .unwrap();
+        (items, skills)
+    }
+
+    #[test]
+    fn matches_unsigned_references_to_ids() {
+        let (items, skills) = tables();
+        let joined = items
+            .join(&skills, "_SkillID", "id", JoinKind::Inner)
+            .unwrap();
+
+        let names = joined
+            .head
+            .iter()
+            .map(|column| column.text.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(
+            names,
+            [
+                "id",
+                "left.id",
+                "left._Name",
+                "left._SkillID",
+                "right.id",
+                "right._Skill"
+            ]
+        );
+        assert_eq!(joined.body.len(), 2);
+        assert_eq!(
+            joined.body[1].values,
+            vec![
+                DntValue::Int32(2),
+                DntValue::Int32(3),
+                DntValue::from("Bow"),
+                DntValue::UInt32(20),
+                DntValue::Int32(20),
+                DntValue::from("Pierce")
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_unmatched_rows_in_left_joins() {
+        let (items, skills) = tables();
+        let joined = items
+            .join(&skills, "_SkillID", "id", JoinKind::Left)
+            .unwrap();
+
+        assert_eq!(joined.body.len(), 3);
+        assert_eq!(joined.body[1].values[4..], [DntValue::Null, DntValue::Null]);
+        assert!(items
+            .join(&skills, "_Missing", "id", JoinKind::Left)
+            .is_err());
+    }
+}
@@ -0,0 +1,253 @@
+use serde_json::{Map, Number, Value};
+
+use crate::{
+    bytes::{parse_hex, to_hex},
+    DntColumn, DntDataType, DntError, DntRow, DntTable, DntValue, ExportOptions,
+};
+
+impl DntTable {
+    pub fn to_json(&self) -> Result<String, DntError> {
+        self.to_json_with(&ExportOptions::default())
+    }
+
+    /// Writes JSON with floats formatted by `options`. They stay JSON numbers,
+    /// so only `float_precision`, `trim_trailing_zeros` and `columns` apply.
+    pub fn to_json_with(&self, options: &ExportOptions) -> Result<String, DntError> {
+        Ok(serde_json::to_string(&self.json_rows(options)?)?)
+    }
+
+    pub fn from_json(json: &str) -> Result<DntTable, DntError> {
+        DntTable::from_json_rows(serde_json::from_str(json)?)
+    }
+
+    pub fn from_json_with_head(json: &str, head: &[DntColumn]) -> Result<DntTable, DntError> {
+        DntTable::from_json_rows_with_head(serde_json::from_str(json)?, head)
+    }
+
+    /// The rows as an array of objects keyed by column name, which other
+    /// formats built on the JSON data model, such as YAML, share.
+    pub(crate) fn json_rows(&self, options: &ExportOptions) -> Result<Value, DntError> {
+        let columns = options.columns.resolve(self)?;
+        let rows = self
+            .body
+            .iter()
+            .map(|row| {
+                Value::Object(
+                    columns
+                        .iter()
+                        .filter_map(|(index, name)| {
+                            let value = row.values.get(*index)?;
+                            Some((name.to_string(), value_to_json(value, options)))
+                        })
+                        .collect(),
+                )
+            })
+            .collect();
+
+        Ok(Value::Array(rows))
+    }
+
+    pub(crate) fn from_json_rows(rows: Value) -> Result<DntTable, DntError> {
+        let objects = read_json(rows)?;
+
+        let names = match objects.first() {
+            Some(object) => object.keys().cloned().collect::<Vec<String>>(),
+            None => vec![String::from("id")],
+        };
+
+        let head = names
+            .iter()
+            .enumerate()
+            .map(|(index, name)| {
+                let data_type = if index == 0 {
+                    DntDataType::Int32
+                } else {
+                    infer_data_type(objects.iter().map(|object| object.get(name)))
+                };
+                DntColumn::new(name, data_type)
+            })
+            .collect::<Vec<DntColumn>>();
+
+        let body = parse_rows(&head, &objects)?;
+
+        Ok(DntTable::from_parts(head, body))
+    }
+
+    pub(crate) fn from_json_rows_with_head(
+        rows: Value,
+        head: &[DntColumn],
+    ) -> Result<DntTable, DntError> {
+        let objects = read_json(rows)?;
+
+        if let Some(name) = objects
+            .iter()
+            .flat_map(|object| object.keys())
+            .find(|name| !head.iter().any(|column| &column.text == *name))
+        {
+            return Err(DntError::InvalidHeader(format!("unexpected key: {}", name)));
+        }
+
+        let head = head.to_vec();
+        let body = parse_rows(&head, &objects)?;
+
+        Ok(DntTable::from_parts(head, body))
+    }
+}
+
+fn read_json(rows: Value) -> Result<Vec<Map<String, Value>>, DntError> {
+    match rows {
+        Value::Array(rows) => rows
+            .into_iter()
+            .enumerate()
+            .map(|(index, row)| match row {
+                Value::Object(object) => Ok(object),
+                _ => Err(DntError::InvalidValue(format!(
+                    "row {} is not an object",
+                    index
+                ))),
+            })
+            .collect(),
+        _ => Err(DntError::InvalidValue(String::from(
+            "expected an array of objects",
+        ))),
+    }
+}
+
+fn infer_data_type<'a>(mut values: impl Iterator<Item = Option<&'a Value>> + Clone) -> DntDataType {
+    let is_number = |value: Option<&Value>, check: fn(&Number) -> bool| match value {
+        Some(Value::Number(number)) => check(number),
+        Some(Value::Null) => true,
+        _ => false,
+    };
+
+    if values.clone().all(|value| {
+        is_number(value, |number| {
+            number
+                .as_i64()
+                .is_some_and(|value| i32::try_from(value).is_ok())
+        })
+    }) {
+        DntDataType::Int32
+    } else if values.clone().all(|value| {
+        is_number(value, |number| {
+            number
+                .as_u64()
+                .is_some_and(|value| u32::try_from(value).is_ok())
+        })
+    }) {
+        DntDataType::UInt32
+    } else if values.all(|value| is_number(value, |_| true)) {
+        DntDataType::Float32
+    } else {
+        DntDataType::String
+    }
+}
+
+fn parse_rows(head: &[DntColumn], objects: &[Map<String, Value>]) -> Result<Vec<DntRow>, DntError> {
+    objects
+        .iter()
+        .enumerate()
+        .map(|(row_index, object)| {
+            let values = head
+                .iter()
+                .map(|column| {
+                    let value = object.get(&column.text).ok_or_else(|| {
+                        DntError::InvalidValue(format!(
+                            "row {} has no value for column {}",
+                            row_index, column.text
+                        ))
+                    })?;
+
+                    json_to_value(value, column.data_type).ok_or_else(|| {
+                        DntError::InvalidValue(format!(
+                            "row {}, column {}: {} is not a valid {:?}",
+                            row_index, column.text, value, column.data_type
+                        ))
+                    })
+                })
+                .collect::<Result<Vec<DntValue>, DntError>>()?;
+
+            Ok(DntRow { values })
+        })
+        .collect()
+}
+
+fn json_to_value(value: &Value, data_type: DntDataType) -> Option<DntValue> {
+    match (data_type, value) {
+        (_, Value::Null) => Some(DntValue::Null),
+        (DntDataType::String, Value::String(value)) => Some(DntValue::String(value.to_owned())),
+        (DntDataType::String, Value::Number(number)) => Some(DntValue::String(number.to_string())),
+        (DntDataType::UInt32, Value::Number(number)) => number
+            .as_u64()
+            .and_then(|value| u32::try_from(value).ok())
+            .map(DntValue::UInt32),
+        (DntDataType::Int32, Value::Number(number)) => number
+            .as_i64()
+            .and_then(|value| i32::try_from(value).ok())
+            .map(DntValue::Int32),
+        (DntDataType::Float32, Value::Number(number)) => {
+            number.as_f64().map(|value| DntValue::Float32(value as f32))
+        }
+        (DntDataType::Float64, Value::Number(number)) => number.as_f64().map(DntValue::Float64),
+        (DntDataType::Unknown(_), Value::String(value)) => parse_hex(value).map(DntValue::Bytes),
+        _ => None,
+    }
+}
+
+fn value_to_json(value: &DntValue, options: &ExportOptions) -> Value {
+    match value {
+        DntValue::String(value) => Value::String(value.to_owned()),
+        DntValue::UInt32(value) => Value::from(*value),
+        DntValue::Int32(value) => Value::from(*value),
+        DntValue::Float32(value) => float_to_json(options.format_f32(*value)),
+        DntValue::Float64(value) => float_to_json(options.format_f64(*value)),
+        DntValue::Bytes(value) => Value::String(to_hex(value)),
+        DntValue::Null => Value::Null,
+    }
+}
+
+fn float_to_json(value: String) -> Value {
+    Number::from_f64(value.parse().unwrap_or(f64::NAN))
+        .map(Value::Number)
+        .unwrap_or(Value::Null)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DntDataType, DntTable, DntTableBuilder, DntValue};
+
+    fn table() -> DntTable {
+        DntTableBuilder::new()
+            .column("_Name", DntDataType::String)
+            .column("_Level", DntDataType::UInt32)
+            .column("_Rate", DntDataType::Float32)
+            .row((1, "Sword", 10u32, 0.5f32))
+            .row(vec![
+                DntValue::Int32(2),
+                DntValue::from("Shield"),
+                DntValue::UInt32(20),
+                DntValue::Null,
+            ])
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn round_trips_with_the_head() {
+        let table = table();
+        let json = table.to_json().unwrap();
+        assert!(json.starts_with("[{"));
+
+        let read = DntTable::from_json_with_head(&json, &table.head).unwrap();
+        assert!(read == table);
+    }
+
+    #[test]
+    fn rejects_what_isnt_an_array_of_objects() {
+        assert!(DntTable::from_json("{}").is_err());
+        assert!(DntTable::from_json("[1]").is_err());
+        assert!(
+            DntTable::from_json_with_head("[{\"id\": 1, \"_Name\": 2}]", &table().head).is_err()
+        );
+    }
+}
@@ -1,227 +1,1616 @@
+mod append;
+#[cfg(feature = "arrow")]
+mod arrow;
+#[cfg(feature = "async")]
+mod async_io;
+pub mod batch;
+mod borrowed;
+mod builder;
+mod bytes;
+mod cipher;
+mod codec;
+mod columnar;
+#[cfg(feature = "zlib")]
+mod compression;
+#[cfg(feature = "csv")]
+mod csv;
+mod diff;
+mod duplicates;
+mod edit;
+mod endian;
+mod error;
+mod export;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod forward;
+mod group;
+mod history;
+mod html;
+mod index;
+pub mod inspect;
+mod intern;
+mod join;
+#[cfg(feature = "json")]
+mod json;
+mod limits;
+mod markdown;
+mod merge;
+#[cfg(all(feature = "mmap", not(target_family = "wasm")))]
+mod mmap;
+mod nulls;
+mod offsets;
+mod options;
+#[cfg(feature = "pak")]
+mod pak;
+#[cfg(feature = "rayon")]
+mod parallel;
+#[cfg(feature = "parquet")]
+mod parquet;
+mod patch;
+#[cfg(feature = "polars")]
+mod polars;
+mod print;
+mod profile;
+mod progress;
+mod query;
+mod record;
+mod recover;
+mod relation;
+mod rename;
+mod rows;
+mod schema;
+mod sort;
+mod sql;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+mod stats;
+mod stream;
+mod transaction;
+mod uistring;
+mod validate;
+mod value;
+mod verify;
+mod view;
+#[cfg(feature = "watch")]
+mod watch;
+#[cfg(feature = "xlsx")]
+mod xlsx;
+#[cfg(feature = "yaml")]
+mod yaml;
+
 use std::{
-    error::Error,
+    collections::HashMap,
     fs::File,
-    io::{Seek, SeekFrom},
+    hash::{Hash, Hasher},
+    io::{BufReader, BufWriter, Cursor, Read, Seek, SeekFrom, Write},
 };
 
-use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use byteorder::{ReadBytesExt, WriteBytesExt};
+use codec::FallbackCodec;
+use edit::default_value;
+use encoding_rs::Encoding;
+use progress::DntProgress;
 
-#[derive(Debug)]
+#[cfg(feature = "async")]
+pub use async_io::{AsyncDntReader, AsyncDntWriter};
+pub use borrowed::{DntRowsRef, DntTableRef, DntValueCow, DntValueRef};
+pub use builder::{DntTableBuilder, IntoDntRow};
+pub use cipher::{CipherStream, StreamCipher, XorCipher};
+pub use codec::{Cp949Codec, Latin1Codec, StringCodec, Utf8Codec};
+pub use columnar::{DntColumnData, DntColumnarColumn, DntColumnarTable};
+#[cfg(feature = "zlib")]
+pub use compression::CompressionMode;
+pub use diff::{diff, DntCellChange, DntDiff, DntRowChange, DntSchemaChange};
+pub use duplicates::{DedupPolicy, DntDuplicates};
+pub use edit::RowKey;
+pub use encoding_rs;
+pub use endian::Endianness;
+pub use error::DntError;
+pub use export::ExportOptions;
+pub use forward::ForwardReader;
+pub use group::{DntAggregate, DntGroupBy};
+pub use history::DntHistory;
+pub use html::HtmlOptions;
+pub use intern::{DntInternedTable, DntInternedValue, DntInterner};
+pub use join::JoinKind;
+pub use limits::DntLimits;
+pub use markdown::MarkdownOptions;
+pub use merge::{merge3, DntConflict, DntMerge, MergeStrategy};
+#[cfg(all(feature = "mmap", not(target_family = "wasm")))]
+pub use mmap::DntMmap;
+pub use nulls::DntNulls;
+pub use offsets::DntRowOffsets;
+pub use options::{ReadOptions, WriteOptions};
+#[cfg(feature = "pak")]
+pub use pak::{DntPak, DntPakEntry};
+#[cfg(feature = "parquet")]
+pub use parquet::{ParquetCompression, ParquetOptions};
+pub use patch::{DntCellPatch, DntPatch, DntRowPatch};
+pub use print::PrintOptions;
+pub use profile::FormatProfile;
+pub use progress::ProgressEvent;
+pub use query::DntQuery;
+pub use record::{DntField, DntRecord};
+pub use recover::{DntDamage, DntDamageReport, DntRecovery};
+pub use relation::DntRelation;
+pub use rename::ColumnMapping;
+pub use rows::DntRows;
+pub use schema::DntSchema;
+pub use sort::SortOrder;
+pub use sql::{SqlDialect, SqlOptions};
+#[cfg(feature = "sqlite")]
+pub use sqlite::export_sqlite;
+pub use stats::{DntColumnStats, DntColumnSummary};
+pub use stream::DntStreamWriter;
+pub use transaction::DntTransaction;
+pub use uistring::{MissingUiString, UiStringOptions, UiStringOutput, UiStrings};
+pub use validate::{DntRule, DntValidationReport, DntValidator, DntViolation};
+pub use verify::{DntProblem, DntVerifyReport};
+pub use view::{ColumnView, RowView, RowViewMut};
+#[cfg(feature = "watch")]
+pub use watch::{DntReload, DntWatcher};
+#[cfg(feature = "xlsx")]
+pub use xlsx::export_xlsx;
+
+#[cfg(feature = "derive")]
+pub use dnt_file_reader_writer_derive::DntRecord;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DntDataType {
     String,
+    UInt32,
     Int32,
     Float32,
+    /// An 8-byte float. Standard files have no code for it: code 5 holds
+    /// 4-byte floats like code 4, and the variants that store doubles under
+    /// code 5 are read with a `FormatProfile` mapping 5 to `Float64`.
+    Float64,
+    Unknown(u8),
 }
 
 impl DntDataType {
     fn from_u8(value: u8) -> Self {
         match value {
             1 => DntDataType::String,
-            2..=3 => DntDataType::Int32,
+            2 => DntDataType::UInt32,
+            3 => DntDataType::Int32,
             4..=5 => DntDataType::Float32,
-            other => panic!("Invalid column type value: {}", other),
+            other => DntDataType::Unknown(other),
+        }
+    }
+
+    fn to_u8(self) -> u8 {
+        match self {
+            DntDataType::String => 1,
+            DntDataType::UInt32 => 2,
+            DntDataType::Int32 => 3,
+            DntDataType::Float32 => 4,
+            DntDataType::Float64 => 5,
+            DntDataType::Unknown(value) => value,
         }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
+pub enum DntUnknownWidth {
+    LengthPrefixed,
+    Fixed(usize),
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "value"))]
 pub enum DntValue {
     String(String),
+    UInt32(u32),
     Int32(i32),
     Float32(f32),
+    Float64(f64),
+    Bytes(Vec<u8>),
+    /// No value. Only found in tables read with `with_nulls`; it is written as
+    /// the null value of its column, or the zero value of the column type.
+    Null,
 }
 
-#[derive(Debug)]
+impl DntValue {
+    pub(crate) fn data_type(&self) -> DntDataType {
+        match self {
+            DntValue::String(_) => DntDataType::String,
+            DntValue::UInt32(_) => DntDataType::UInt32,
+            DntValue::Int32(_) => DntDataType::Int32,
+            DntValue::Float32(_) => DntDataType::Float32,
+            DntValue::Float64(_) => DntDataType::Float64,
+            DntValue::Bytes(_) => DntDataType::Unknown(0),
+            // Null has no type of its own and matches every column.
+            DntValue::Null => DntDataType::Unknown(0),
+        }
+    }
+
+    pub(crate) fn matches(&self, data_type: DntDataType) -> bool {
+        match (self, data_type) {
+            (DntValue::Bytes(_), DntDataType::Unknown(_)) => true,
+            (DntValue::Null, _) => true,
+            _ => self.data_type() == data_type,
+        }
+    }
+}
+
+impl From<String> for DntValue {
+    fn from(value: String) -> Self {
+        DntValue::String(value)
+    }
+}
+
+impl From<&str> for DntValue {
+    fn from(value: &str) -> Self {
+        DntValue::String(value.to_owned())
+    }
+}
+
+impl From<u32> for DntValue {
+    fn from(value: u32) -> Self {
+        DntValue::UInt32(value)
+    }
+}
+
+impl From<i32> for DntValue {
+    fn from(value: i32) -> Self {
+        DntValue::Int32(value)
+    }
+}
+
+impl From<f32> for DntValue {
+    fn from(value: f32) -> Self {
+        DntValue::Float32(value)
+    }
+}
+
+impl From<f64> for DntValue {
+    fn from(value: f64) -> Self {
+        DntValue::Float64(value)
+    }
+}
+
+impl From<Vec<u8>> for DntValue {
+    fn from(value: Vec<u8>) -> Self {
+        DntValue::Bytes(value)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DntColumn {
     pub text: String,
     data_type: DntDataType,
     raw_data_type: u8,
 }
 
-#[derive(Debug)]
+impl DntColumn {
+    pub(crate) fn new(text: &str, data_type: DntDataType) -> Self {
+        Self {
+            text: text.to_owned(),
+            data_type,
+            raw_data_type: data_type.to_u8(),
+        }
+    }
+
+    pub fn data_type(&self) -> DntDataType {
+        self.data_type
+    }
+
+    pub fn raw_data_type(&self) -> u8 {
+        self.raw_data_type
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DntRow {
     pub values: Vec<DntValue>,
 }
 
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DntTable {
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub magic: [u8; 4],
+    /// Bytes some variants have in the header after the row count, read with
+    /// `with_extra_header_length` and written back unchanged.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub extra_header: Vec<u8>,
     pub head: Vec<DntColumn>,
     pub body: Vec<DntRow>,
+    /// Bytes that followed the footer, kept with
+    /// `ReadOptions::keep_trailing_bytes`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub trailing_bytes: Vec<u8>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    id_index: Option<HashMap<i32, usize>>,
+}
+
+/// Tables are equal when their magic, extra header bytes, columns, rows and
+/// trailing bytes are, in the same order. Values compare as described on `DntValue`.
+impl PartialEq for DntTable {
+    fn eq(&self, other: &Self) -> bool {
+        self.magic == other.magic
+            && self.extra_header == other.extra_header
+            && self.head == other.head
+            && self.body == other.body
+            && self.trailing_bytes == other.trailing_bytes
+    }
+}
+
+impl Eq for DntTable {}
+
+impl Hash for DntTable {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.magic.hash(state);
+        self.extra_header.hash(state);
+        self.head.hash(state);
+        self.body.hash(state);
+        self.trailing_bytes.hash(state);
+    }
+}
+
+#[derive(Debug)]
+pub struct DntHeader {
+    pub magic: [u8; 4],
+    pub extra_header: Vec<u8>,
+    pub head: Vec<DntColumn>,
+    pub rows_nb: u32,
+}
+
+type DntRowFilter = dyn FnMut(&RowView<'_>) -> bool + Send;
+
+#[derive(Clone)]
+pub(crate) struct DntLayout {
+    head: Vec<DntColumn>,
+    columns: Vec<(DntDataType, bool)>,
+    names: Vec<String>,
+    nulls: Option<Vec<Option<DntValue>>>,
+    endianness: Endianness,
+}
+
+impl DntLayout {
+    pub(crate) fn head(&self) -> &[DntColumn] {
+        &self.head
+    }
+
+    pub(crate) fn apply_nulls(&self, row: &mut DntRow) {
+        if let Some(sentinels) = &self.nulls {
+            nulls::apply(sentinels, &mut row.values);
+        }
+    }
+
+    /// Locates an error in the value of `column` in `row`, which starts at
+    /// `start`. Once `read` bytes of it were read, the error is past its
+    /// length prefix, if it has one.
+    pub(crate) fn value_error(
+        &self,
+        error: DntError,
+        row: u32,
+        column: usize,
+        start: u64,
+        read: u64,
+        unknown_width: DntUnknownWidth,
+    ) -> DntError {
+        let data_type = self.columns[column].0;
+        let prefixed = match data_type {
+            DntDataType::String => true,
+            DntDataType::Unknown(_) => matches!(unknown_width, DntUnknownWidth::LengthPrefixed),
+            _ => false,
+        };
+
+        let (offset, item) = match data_type {
+            _ if prefixed && read == 0 => (start, format!("{} length", value_kind(data_type))),
+            _ if prefixed => (start + 2, String::from(value_kind(data_type))),
+            _ => (start, String::from(value_kind(data_type))),
+        };
+        error.at(offset, Some(row), Some(column), || {
+            format!(
+                "{} for column '{}' in row {}",
+                item, self.names[column], row
+            )
+        })
+    }
+}
+
+fn value_kind(data_type: DntDataType) -> &'static str {
+    match data_type {
+        DntDataType::String => "string",
+        DntDataType::UInt32 => "UInt32 value",
+        DntDataType::Int32 => "Int32 value",
+        DntDataType::Float32 => "Float32 value",
+        DntDataType::Float64 => "Float64 value",
+        DntDataType::Unknown(_) => "value",
+    }
 }
 
-pub struct DntFileReader {
-    file: File,
+pub struct DntFileReader<R = File> {
+    reader: BufReader<R>,
+    inflated: Option<Cursor<Vec<u8>>>,
     data: DntTable,
+    unknown_width: DntUnknownWidth,
+    codec: Box<dyn StringCodec>,
+    columns: Option<Vec<String>>,
+    filter: Option<Box<DntRowFilter>>,
+    options: ReadOptions,
+    extra_header_length: usize,
+    type_codes: Vec<(u8, DntDataType)>,
+    /// Where the next byte will be read from, kept without asking the stream
+    /// so that errors can say where they happened.
+    offset: u64,
+    trailing_bytes: u64,
+    trailing: Vec<u8>,
+    progress: Option<Box<DntProgress>>,
+    row_offsets: Option<DntRowOffsets>,
+    row_layout: Option<DntLayout>,
+    nulls: Option<DntNulls>,
+    endianness: Endianness,
+    limits: DntLimits,
 }
 
-impl DntFileReader {
-    pub fn new(file: File) -> Self {
+impl<R: Read + Seek> DntFileReader<R> {
+    pub fn new(reader: R) -> Self {
         Self {
-            file,
-            data: DntTable {
-                head: vec![],
-                body: vec![],
-            },
+            reader: BufReader::new(reader),
+            inflated: None,
+            data: DntTable::default(),
+            unknown_width: DntUnknownWidth::LengthPrefixed,
+            codec: Box::new(Latin1Codec),
+            columns: None,
+            filter: None,
+            options: ReadOptions::default(),
+            extra_header_length: 0,
+            type_codes: vec![],
+            offset: 0,
+            trailing_bytes: 0,
+            trailing: vec![],
+            progress: None,
+            row_offsets: None,
+            row_layout: None,
+            nulls: None,
+            endianness: Endianness::Little,
+            limits: DntLimits::default(),
         }
     }
 
-    pub fn read(&mut self) -> Result<(), Box<dyn Error>> {
-        self.seek(4);
+    pub fn with_unknown_width(mut self, width: DntUnknownWidth) -> Self {
+        self.unknown_width = width;
+        self
+    }
 
-        let mut data = DntTable {
-            head: vec![DntColumn {
-                text: String::from("id"),
-                data_type: DntDataType::from_u8(3),
-                raw_data_type: 3,
-            }],
-            body: vec![],
-        };
+    pub fn with_codec(mut self, codec: impl StringCodec + 'static) -> Self {
+        self.codec = Box::new(codec);
+        self.apply_fallback();
+        self
+    }
+
+    pub fn with_encoding(self, encoding: &'static Encoding) -> Self {
+        self.with_codec(encoding)
+    }
 
-        let columns_nb = self.read_u16() + 1;
-        let rows_nb = self.read_u32();
+    pub fn with_limits(mut self, limits: DntLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    pub fn with_columns<I, S>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.columns = Some(columns.into_iter().map(Into::into).collect());
+        self
+    }
+
+    pub fn with_filter<F>(mut self, filter: F) -> Self
+    where
+        F: FnMut(&RowView<'_>) -> bool + Send + 'static,
+    {
+        self.filter = Some(Box::new(filter));
+        self
+    }
 
-        for _ in 1..columns_nb {
-            let text = self.read_string();
-            let raw_data_type = self.read_u8();
-            let data_type = DntDataType::from_u8(raw_data_type);
-            let column = DntColumn {
+    pub fn with_lenient_footer(mut self, lenient: bool) -> Self {
+        self.options.lenient_footer = lenient;
+        self
+    }
+
+    pub fn with_options(mut self, options: ReadOptions) -> Self {
+        self.options = options;
+        self.apply_fallback();
+        self
+    }
+
+    fn apply_fallback(&mut self) {
+        if let Some(fallback) = self.options.fallback_encoding {
+            let codec = std::mem::replace(&mut self.codec, Box::new(Latin1Codec));
+            self.codec = Box::new(FallbackCodec { codec, fallback });
+        }
+    }
+
+    pub fn with_progress<F>(mut self, progress: F) -> Self
+    where
+        F: FnMut(ProgressEvent) + Send + 'static,
+    {
+        self.progress = Some(Box::new(progress));
+        self
+    }
+
+    /// Reads the values standing for null in each column as `DntValue::Null`.
+    pub fn with_nulls(mut self, nulls: DntNulls) -> Self {
+        self.nulls = Some(nulls);
+        self
+    }
+
+    pub fn with_endianness(mut self, endianness: Endianness) -> Self {
+        self.endianness = endianness;
+        self
+    }
+
+    /// Reads `length` bytes of unknown meaning after the row count, which some
+    /// variants have, into `DntTable::extra_header`.
+    pub fn with_extra_header_length(mut self, length: usize) -> Self {
+        self.extra_header_length = length;
+        self
+    }
+
+    pub fn read(&mut self) -> Result<(), DntError> {
+        let mut header = self.read_header()?;
+        let magic = header.magic;
+        let extra_header = std::mem::take(&mut header.extra_header);
+        let rows_nb = header.rows_nb;
+        let layout = self.layout(header)?;
+
+        let body = self.read_body(&layout, rows_nb)?;
+        self.read_footer()?;
+
+        self.data = DntTable::from_parts(layout.head, body);
+        self.data.magic = magic;
+        self.data.extra_header = extra_header;
+        self.data.trailing_bytes = std::mem::take(&mut self.trailing);
+
+        Ok(())
+    }
+
+    pub fn rows(&mut self) -> Result<DntRows<'_, R>, DntError> {
+        let header = self.read_header()?;
+        self.rows_from(header)
+    }
+
+    pub(crate) fn rows_from(&mut self, header: DntHeader) -> Result<DntRows<'_, R>, DntError> {
+        let rows_nb = header.rows_nb;
+        let layout = self.layout(header)?;
+        self.report_progress(0, rows_nb)?;
+        if rows_nb == 0 && !self.options.lenient_row_count {
+            self.read_footer()?;
+        }
+
+        Ok(DntRows::new(self, layout, rows_nb))
+    }
+
+    pub fn read_records<T: DntRecord>(&mut self) -> Result<Vec<T>, DntError> {
+        let rows = self.rows()?;
+        let indexes = record::record_indexes::<T>(rows.head())?;
+
+        rows.map(|row| record::record_from_row(&row?, &indexes))
+            .collect()
+    }
+
+    pub fn data(&mut self) -> &mut DntTable {
+        &mut self.data
+    }
+
+    pub fn trailing_bytes(&self) -> u64 {
+        self.trailing_bytes
+    }
+
+    pub fn read_header(&mut self) -> Result<DntHeader, DntError> {
+        self.seek(0)?;
+
+        let magic = self
+            .read_array()
+            .map_err(|error| error.at(0, None, None, || String::from("magic")))?;
+
+        #[cfg(feature = "zlib")]
+        if self.inflated.is_none() {
+            if let Some(payload) = compression::inflate(&mut self.reader, self.limits.max_bytes)? {
+                let mut bytes = magic.to_vec();
+                bytes.extend(payload);
+
+                let mut inflated = Cursor::new(bytes);
+                inflated.set_position(4);
+                self.inflated = Some(inflated);
+            }
+        }
+
+        let mut head = vec![DntColumn::new("id", DntDataType::Int32)];
+
+        let columns_nb = self
+            .read_u16()
+            .map_err(|error| error.at(4, None, None, || String::from("column count")))?
+            as usize
+            + 1;
+        let rows_nb = self
+            .read_u32()
+            .map_err(|error| error.at(6, None, None, || String::from("row count")))?;
+        let extra_header = self
+            .read_bytes(self.extra_header_length)
+            .map_err(|error| error.at(10, None, None, || String::from("extra header bytes")))?;
+        DntLimits::check(
+            "the column count",
+            columns_nb as u64,
+            self.limits.max_columns as u64,
+        )?;
+        DntLimits::check(
+            "the row count",
+            u64::from(rows_nb),
+            u64::from(self.limits.max_rows),
+        )?;
+
+        for index in 1..columns_nb {
+            let start = self.offset;
+            let text = self.read_string().map_err(|error| {
+                let offset = if self.offset == start {
+                    start
+                } else {
+                    start + 2
+                };
+                error.at(offset, None, Some(index), || {
+                    format!("name of column {}", index)
+                })
+            })?;
+
+            let start = self.offset;
+            let type_error = |error: DntError| {
+                error.at(start, None, Some(index), || {
+                    format!("type of column '{}'", text)
+                })
+            };
+            let raw_data_type = self.read_u8().map_err(type_error)?;
+            let data_type = profile::data_type(&self.type_codes, raw_data_type);
+            if !self.options.allow_unknown_types && matches!(data_type, DntDataType::Unknown(_)) {
+                return Err(type_error(DntError::InvalidColumnType(raw_data_type)));
+            }
+            head.push(DntColumn {
                 text,
                 data_type,
                 raw_data_type,
+            });
+        }
+
+        Ok(DntHeader {
+            magic,
+            extra_header,
+            head,
+            rows_nb,
+        })
+    }
+
+    pub(crate) fn layout(&self, header: DntHeader) -> Result<DntLayout, DntError> {
+        let selected = match &self.columns {
+            Some(columns) => {
+                if let Some(missing) = columns
+                    .iter()
+                    .find(|name| !header.head.iter().any(|column| &column.text == *name))
+                {
+                    return Err(DntError::ColumnNotFound(missing.to_owned()));
+                }
+
+                header
+                    .head
+                    .iter()
+                    .enumerate()
+                    .map(|(index, column)| index == 0 || columns.contains(&column.text))
+                    .collect()
+            }
+            None => vec![true; header.head.len()],
+        };
+
+        let columns = header
+            .head
+            .iter()
+            .zip(&selected)
+            .map(|(column, &selected)| (column.data_type, selected))
+            .collect();
+        let names = header
+            .head
+            .iter()
+            .map(|column| column.text.clone())
+            .collect();
+
+        let head = header
+            .head
+            .into_iter()
+            .zip(selected)
+            .filter_map(|(column, selected)| selected.then_some(column))
+            .collect::<Vec<DntColumn>>();
+
+        let nulls = self.nulls.as_ref().map(|nulls| nulls.sentinels(&head));
+
+        Ok(DntLayout {
+            head,
+            columns,
+            names,
+            nulls,
+            endianness: self.endianness,
+        })
+    }
+
+    fn read_body(&mut self, layout: &DntLayout, rows_nb: u32) -> Result<Vec<DntRow>, DntError> {
+        #[cfg(feature = "rayon")]
+        if !self.options.lenient_row_count
+            && rows_nb >= parallel::MIN_PARALLEL_ROWS
+            && rayon::current_num_threads() > 1
+        {
+            let body_start = self.position()?;
+            match self.read_body_parallel(layout, rows_nb) {
+                Ok(mut body) => {
+                    body.retain(|row| self.accepts(layout, row));
+                    return Ok(body);
+                }
+                Err(error @ (DntError::Io(_) | DntError::LimitExceeded { .. })) => {
+                    return Err(error)
+                }
+                // Reading the rows again one by one tells where the error is.
+                Err(error) => {
+                    if self.seek(body_start).is_err() {
+                        return Err(error);
+                    }
+                }
+            }
+        }
+
+        let mut body = vec![];
+        self.report_progress(0, rows_nb)?;
+        let mut index = 0;
+        while self.more_rows(index, rows_nb)? {
+            let row = self.read_row(layout, index)?;
+            if self.accepts(layout, &row) {
+                body.push(row);
+            }
+            index += 1;
+            self.report_progress(index, rows_nb.max(index))?;
+        }
+
+        Ok(body)
+    }
+
+    /// Reads the row at index `index` in the file.
+    pub(crate) fn read_row(&mut self, layout: &DntLayout, index: u32) -> Result<DntRow, DntError> {
+        let mut row = DntRow {
+            values: Vec::with_capacity(layout.head.len()),
+        };
+
+        for (column, &(data_type, selected)) in layout.columns.iter().enumerate() {
+            let start = self.offset;
+            let value = if selected {
+                self.read_value(data_type).map(Some)
+            } else {
+                self.skip_value(data_type).map(|_| None)
             };
-            data.head.push(column);
+
+            match value {
+                Ok(Some(value)) => row.values.push(value),
+                Ok(None) => {}
+                Err(error) => {
+                    let read = self.offset - start;
+                    return Err(layout.value_error(
+                        error,
+                        index,
+                        column,
+                        start,
+                        read,
+                        self.unknown_width,
+                    ));
+                }
+            }
         }
+        layout.apply_nulls(&mut row);
 
-        for _ in 0..rows_nb {
-            let mut row = DntRow { values: vec![] };
-            for column in &data.head {
-                let value = match column.data_type {
-                    DntDataType::String => DntValue::String(self.read_string()),
-                    DntDataType::Int32 => DntValue::Int32(self.read_i32()),
-                    DntDataType::Float32 => DntValue::Float32(self.read_f32()),
-                };
-                row.values.push(value);
+        if self.limits.max_bytes < u64::MAX {
+            let position = self.position()?;
+            DntLimits::check("the data size", position, self.limits.max_bytes)?;
+        }
+
+        Ok(row)
+    }
+
+    fn read_value(&mut self, data_type: DntDataType) -> Result<DntValue, DntError> {
+        Ok(match data_type {
+            DntDataType::String => DntValue::String(self.read_string()?),
+            DntDataType::UInt32 => DntValue::UInt32(self.read_u32()?),
+            DntDataType::Int32 => DntValue::Int32(self.read_i32()?),
+            DntDataType::Float32 => DntValue::Float32(self.read_f32()?),
+            DntDataType::Float64 => DntValue::Float64(self.read_f64()?),
+            DntDataType::Unknown(_) => DntValue::Bytes(self.read_unknown()?),
+        })
+    }
+
+    pub(crate) fn read_footer(&mut self) -> Result<(), DntError> {
+        let body_end = self.position()?;
+
+        if let Err(error) = self.check_footer() {
+            if !self.options.lenient_footer {
+                return Err(error.at(body_end, None, None, || String::from("footer")));
             }
-            data.body.push(row);
+            self.seek(body_end)?;
         }
 
-        self.data = data;
+        let footer_end = self.position()?;
+        self.read_trailing(footer_end)?;
+        if self.trailing_bytes > 0 && !self.options.allow_trailing_bytes {
+            return Err(DntError::InvalidFooter(format!(
+                "unexpected bytes after the footer ({})",
+                self.trailing_bytes
+            )));
+        }
 
         Ok(())
     }
 
-    pub fn data(&mut self) -> &mut DntTable {
-        &mut self.data
-    }
+    /// Counts the bytes from `footer_end` to the end of the data, and keeps
+    /// them with `ReadOptions::keep_trailing_bytes`.
+    pub(crate) fn read_trailing(&mut self, footer_end: u64) -> Result<(), DntError> {
+        if !self.options.keep_trailing_bytes {
+            self.trailing = vec![];
+            self.trailing_bytes = self.seek_from(SeekFrom::End(0))? - footer_end;
+            return Ok(());
+        }
+
+        let max_bytes = self.limits.max_bytes;
+        let mut trailing = vec![];
+        self.input()
+            .take(max_bytes.saturating_sub(footer_end).saturating_add(1))
+            .read_to_end(&mut trailing)?;
+        self.offset += trailing.len() as u64;
+        DntLimits::check("the data size", self.offset, max_bytes)?;
 
-    fn seek(&mut self, amount: u64) {
-        self.file.seek(SeekFrom::Start(amount)).unwrap();
+        self.trailing_bytes = trailing.len() as u64;
+        self.trailing = trailing;
+        Ok(())
     }
 
-    fn read_u16(&mut self) -> u16 {
-        self.file.read_u16::<LittleEndian>().unwrap()
+    pub(crate) fn report_progress(&mut self, rows: u32, total_rows: u32) -> Result<(), DntError> {
+        if self.progress.is_none() || !progress::is_due(rows, total_rows) {
+            return Ok(());
+        }
+
+        let bytes = self.position()?;
+        if let Some(progress) = &mut self.progress {
+            progress(ProgressEvent {
+                rows,
+                total_rows,
+                bytes,
+            });
+        }
+        Ok(())
     }
 
-    fn read_u32(&mut self) -> u32 {
-        self.file.read_u32::<LittleEndian>().unwrap()
+    fn check_footer(&mut self) -> Result<(), DntError> {
+        let expected = match &self.options.footer {
+            Some(text) => self.codec.encode(text)?.into_owned(),
+            None => return Ok(()),
+        };
+        let footer = match self.read_u8() {
+            Ok(length) => self.read_bytes(length as usize),
+            Err(error) => Err(error),
+        };
+
+        match footer {
+            Ok(footer) if footer == expected => Ok(()),
+            Ok(footer) => Err(DntError::InvalidFooter(format!(
+                "expected {}, got {:?}",
+                String::from_utf8_lossy(&expected),
+                String::from_utf8_lossy(&footer)
+            ))),
+            Err(DntError::UnexpectedEof) => Err(DntError::InvalidFooter(format!(
+                "the file ends before the {} footer",
+                String::from_utf8_lossy(&expected)
+            ))),
+            Err(error) => Err(error),
+        }
     }
 
-    fn read_i32(&mut self) -> i32 {
-        self.file.read_i32::<LittleEndian>().unwrap()
+    /// Whether another row follows the `read` rows read so far. Unless the
+    /// row count is lenient, that is whether the header announced more.
+    pub(crate) fn more_rows(&mut self, read: u32, rows_nb: u32) -> Result<bool, DntError> {
+        if !self.options.lenient_row_count {
+            return Ok(read < rows_nb);
+        }
+        self.row_follows()
     }
 
-    fn read_f32(&mut self) -> f32 {
-        self.file.read_f32::<LittleEndian>().unwrap()
+    /// Whether a row starts here, rather than the footer or the end of the
+    /// data. The position doesn't change.
+    pub(crate) fn row_follows(&mut self) -> Result<bool, DntError> {
+        let position = self.position()?;
+        let at_end = self.input().read(&mut [0])? == 0;
+        self.rewind(position)?;
+        if at_end || self.options.footer.is_none() {
+            return Ok(!at_end);
+        }
+
+        let footer = self.check_footer();
+        self.rewind(position)?;
+        match footer {
+            Ok(()) => Ok(false),
+            Err(DntError::Io(error)) => Err(DntError::Io(error)),
+            Err(_) => Ok(true),
+        }
     }
 
-    fn read_u8(&mut self) -> u8 {
-        self.file.read_u8().unwrap()
+    pub(crate) fn accepts(&mut self, layout: &DntLayout, row: &DntRow) -> bool {
+        match &mut self.filter {
+            Some(filter) => filter(&RowView::new(&layout.head, row)),
+            None => true,
+        }
     }
 
-    fn read_string(&mut self) -> String {
-        let length = self.read_u16() as usize;
+    /// Skips a value and returns its size in bytes, including any length
+    /// prefix.
+    fn skip_value(&mut self, data_type: DntDataType) -> Result<u64, DntError> {
+        let (prefix, length) = match data_type {
+            DntDataType::String => (2, self.read_u16()? as i64),
+            DntDataType::UInt32 | DntDataType::Int32 | DntDataType::Float32 => (0, 4),
+            DntDataType::Float64 => (0, 8),
+            DntDataType::Unknown(_) => match self.unknown_width {
+                DntUnknownWidth::LengthPrefixed => (2, self.read_u16()? as i64),
+                DntUnknownWidth::Fixed(width) => (0, width as i64),
+            },
+        };
 
-        if length > 0 {
-            let mut result = String::with_capacity(length);
-            for index in 0..length {
-                result.insert(index, self.read_u8() as char);
+        match &mut self.inflated {
+            Some(inflated) => {
+                inflated.seek(SeekFrom::Current(length))?;
             }
-            result
-        } else {
-            String::from("")
+            None => self.reader.seek_relative(length)?,
+        }
+        self.offset += length as u64;
+        Ok(prefix + length as u64)
+    }
+
+    /// Goes back to `position` without dropping what is buffered.
+    fn rewind(&mut self, position: u64) -> Result<(), DntError> {
+        let current = self.position()?;
+        match &mut self.inflated {
+            Some(inflated) => inflated.set_position(position),
+            None => self
+                .reader
+                .seek_relative(position as i64 - current as i64)?,
+        }
+        self.offset = position;
+        Ok(())
+    }
+
+    fn seek(&mut self, amount: u64) -> Result<(), DntError> {
+        self.seek_from(SeekFrom::Start(amount))?;
+        Ok(())
+    }
+
+    fn seek_from(&mut self, position: SeekFrom) -> Result<u64, DntError> {
+        self.offset = match &mut self.inflated {
+            Some(inflated) => inflated.seek(position)?,
+            None => self.reader.seek(position)?,
+        };
+        Ok(self.offset)
+    }
+
+    pub(crate) fn position(&mut self) -> Result<u64, DntError> {
+        Ok(match &mut self.inflated {
+            Some(inflated) => inflated.position(),
+            None => self.reader.stream_position()?,
+        })
+    }
+
+    fn input(&mut self) -> &mut dyn Read {
+        match &mut self.inflated {
+            Some(inflated) => inflated,
+            None => &mut self.reader,
         }
     }
+
+    fn read_u16(&mut self) -> Result<u16, DntError> {
+        let bytes = self.read_array()?;
+        Ok(self.endianness.read_u16(bytes))
+    }
+
+    fn read_u32(&mut self) -> Result<u32, DntError> {
+        let bytes = self.read_array()?;
+        Ok(self.endianness.read_u32(bytes))
+    }
+
+    fn read_i32(&mut self) -> Result<i32, DntError> {
+        let bytes = self.read_array()?;
+        Ok(self.endianness.read_i32(bytes))
+    }
+
+    fn read_f32(&mut self) -> Result<f32, DntError> {
+        let bytes = self.read_array()?;
+        Ok(self.endianness.read_f32(bytes))
+    }
+
+    fn read_f64(&mut self) -> Result<f64, DntError> {
+        let bytes = self.read_array()?;
+        Ok(self.endianness.read_f64(bytes))
+    }
+
+    fn read_u8(&mut self) -> Result<u8, DntError> {
+        let value = self.input().read_u8()?;
+        self.offset += 1;
+        Ok(value)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], DntError> {
+        let mut result = [0; N];
+        self.input().read_exact(&mut result)?;
+        self.offset += N as u64;
+        Ok(result)
+    }
+
+    fn read_bytes(&mut self, length: usize) -> Result<Vec<u8>, DntError> {
+        let mut result = vec![0; length];
+        self.input().read_exact(&mut result)?;
+        self.offset += length as u64;
+        Ok(result)
+    }
+
+    fn read_unknown(&mut self) -> Result<Vec<u8>, DntError> {
+        let length = match self.unknown_width {
+            DntUnknownWidth::LengthPrefixed => self.read_u16()? as usize,
+            DntUnknownWidth::Fixed(width) => width,
+        };
+        DntLimits::check(
+            "a string length",
+            length as u64,
+            self.limits.max_string_length as u64,
+        )?;
+        self.read_bytes(length)
+    }
+
+    fn read_string(&mut self) -> Result<String, DntError> {
+        let length = self.read_u16()? as usize;
+        DntLimits::check(
+            "a string length",
+            length as u64,
+            self.limits.max_string_length as u64,
+        )?;
+        let bytes = self.read_bytes(length)?;
+
+        self.codec.decode(&bytes)
+    }
 }
 
-pub struct DntFileWriter {
-    file: File,
+pub struct DntFileWriter<W: Write = File> {
+    writer: BufWriter<W>,
+    payload: Option<Vec<u8>>,
+    magic: Option<[u8; 4]>,
+    extra_header: Option<Vec<u8>>,
+    extra_header_length: Option<usize>,
+    type_codes: Option<Vec<(u8, DntDataType)>>,
+    trailing_bytes: bool,
+    options: WriteOptions,
+    #[cfg(feature = "zlib")]
+    compression: CompressionMode,
+    unknown_width: DntUnknownWidth,
+    codec: Box<dyn StringCodec>,
+    progress: Option<Box<DntProgress>>,
+    written: u64,
+    nulls: Option<DntNulls>,
+    endianness: Endianness,
 }
 
-impl DntFileWriter {
-    pub fn new(file: File) -> Self {
-        Self { file }
+impl<W: Write> DntFileWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer: BufWriter::new(writer),
+            payload: None,
+            magic: None,
+            extra_header: None,
+            extra_header_length: None,
+            type_codes: None,
+            trailing_bytes: false,
+            options: WriteOptions::default(),
+            #[cfg(feature = "zlib")]
+            compression: CompressionMode::None,
+            unknown_width: DntUnknownWidth::LengthPrefixed,
+            codec: Box::new(Latin1Codec),
+            progress: None,
+            written: 0,
+            nulls: None,
+            endianness: Endianness::Little,
+        }
     }
 
-    pub fn write(&mut self, table: &DntTable) -> Result<(), Box<dyn Error>> {
-        self.write_u8(0);
-        self.write_u8(0);
-        self.write_u8(0);
-        self.write_u8(0);
+    pub fn with_unknown_width(mut self, width: DntUnknownWidth) -> Self {
+        self.unknown_width = width;
+        self
+    }
 
-        self.write_u16(table.head.len() as u16 - 1);
-        self.write_u32(table.body.len() as u32);
+    pub fn with_codec(mut self, codec: impl StringCodec + 'static) -> Self {
+        self.codec = Box::new(codec);
+        self
+    }
+
+    pub fn with_encoding(self, encoding: &'static Encoding) -> Self {
+        self.with_codec(encoding)
+    }
+
+    pub fn with_progress<F>(mut self, progress: F) -> Self
+    where
+        F: FnMut(ProgressEvent) + Send + 'static,
+    {
+        self.progress = Some(Box::new(progress));
+        self
+    }
 
-        for column_index in 1..table.head.len() {
-            let column = table.head.get(column_index).unwrap();
-            self.write_string(column.text.to_owned());
-            self.write_u8(column.raw_data_type);
+    /// Writes `DntValue::Null` as the null value of its column, instead of
+    /// the zero value of the column type.
+    pub fn with_nulls(mut self, nulls: DntNulls) -> Self {
+        self.nulls = Some(nulls);
+        self
+    }
+
+    pub fn with_endianness(mut self, endianness: Endianness) -> Self {
+        self.endianness = endianness;
+        self
+    }
+
+    pub fn with_magic(mut self, magic: [u8; 4]) -> Self {
+        self.magic = Some(magic);
+        self
+    }
+
+    /// Writes these bytes after the row count of every table written, instead
+    /// of `DntTable::extra_header`.
+    pub fn with_extra_header(mut self, extra_header: Vec<u8>) -> Self {
+        self.extra_header = Some(extra_header);
+        self
+    }
+
+    /// Writes `DntTable::trailing_bytes` after the footer.
+    pub fn with_trailing_bytes(mut self, write: bool) -> Self {
+        self.trailing_bytes = write;
+        self
+    }
+
+    pub fn with_options(mut self, options: WriteOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    #[cfg(feature = "zlib")]
+    pub fn with_compression(mut self, compression: CompressionMode) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    pub fn into_inner(self) -> Result<W, DntError> {
+        self.writer
+            .into_inner()
+            .map_err(|error| DntError::Io(error.into_error()))
+    }
+
+    pub fn write(&mut self, table: &DntTable) -> Result<(), DntError> {
+        check_id_column(&table.head)?;
+
+        let rows_nb = u32::try_from(table.body.len())
+            .map_err(|_| DntError::InvalidHeader(format!("too many rows: {}", table.body.len())))?;
+
+        check_rows(&table.head, &table.body)?;
+
+        self.write_header(&table.head, table.magic, &table.extra_header, rows_nb)?;
+
+        self.report_progress(0, rows_nb);
+        for (index, row) in table.body.iter().enumerate() {
+            self.write_row(&table.head, row)?;
+            self.report_progress(index as u32 + 1, rows_nb);
         }
 
-        for row in &table.body {
-            for value in &row.values {
-                match value {
-                    DntValue::String(value) => self.write_string(value.to_owned()),
-                    DntValue::Int32(value) => self.write_i32(value.to_owned()),
-                    DntValue::Float32(value) => self.write_f32(value.to_owned()),
-                }
+        self.write_footer()?;
+        if self.trailing_bytes {
+            self.write_bytes(&table.trailing_bytes)?;
+        }
+        self.finish_payload()
+    }
+
+    pub fn write_records<T: DntRecord>(&mut self, records: &[T]) -> Result<(), DntError> {
+        let table = DntTable::from_parts(
+            record::record_head::<T>()?,
+            records
+                .iter()
+                .map(|record| DntRow {
+                    values: record.to_values(),
+                })
+                .collect(),
+        );
+
+        self.write(&table)
+    }
+
+    fn write_header(
+        &mut self,
+        head: &[DntColumn],
+        magic: [u8; 4],
+        extra_header: &[u8],
+        rows_nb: u32,
+    ) -> Result<(), DntError> {
+        let columns_nb = u16::try_from(head.len() - 1)
+            .map_err(|_| DntError::InvalidHeader(format!("too many columns: {}", head.len())))?;
+
+        self.writer.write_all(&self.magic.unwrap_or(magic))?;
+        self.written = 4;
+
+        #[cfg(feature = "zlib")]
+        if self.compression != CompressionMode::None {
+            self.payload = Some(vec![]);
+        }
+
+        self.write_u16(columns_nb)?;
+        self.write_u32(rows_nb)?;
+        let mut extra_header = self
+            .extra_header
+            .clone()
+            .unwrap_or_else(|| extra_header.to_vec());
+        if let Some(length) = self.extra_header_length {
+            if extra_header.is_empty() {
+                extra_header = vec![0; length];
+            } else if extra_header.len() != length {
+                return Err(DntError::InvalidHeader(format!(
+                    "the extra header is {} bytes long, the profile expects {}",
+                    extra_header.len(),
+                    length
+                )));
             }
         }
+        self.write_bytes(&extra_header)?;
+
+        for column in &head[1..] {
+            self.write_string(&column.text)?;
+            self.write_u8(profile::column_code(self.type_codes.as_deref(), column)?)?;
+        }
+
+        Ok(())
+    }
 
-        let closing_text = String::from("THEND");
+    fn finish_payload(&mut self) -> Result<(), DntError> {
+        #[cfg(feature = "zlib")]
+        if let Some(payload) = self.payload.take() {
+            compression::deflate(&mut self.writer, &payload, self.compression)?;
+        }
 
-        self.write_u8(closing_text.len() as u8);
-        self.write_string_bytes(closing_text);
+        self.writer.flush()?;
 
         Ok(())
     }
 
-    fn write_u16(&mut self, value: u16) {
-        self.file.write_u16::<LittleEndian>(value).unwrap()
+    fn write_row(&mut self, head: &[DntColumn], row: &DntRow) -> Result<(), DntError> {
+        for (column, value) in head.iter().zip(&row.values) {
+            self.write_value(column, value)?;
+        }
+        Ok(())
+    }
+
+    fn write_value(&mut self, column: &DntColumn, value: &DntValue) -> Result<(), DntError> {
+        match value {
+            DntValue::String(value) => self.write_string(value),
+            DntValue::UInt32(value) => self.write_u32(*value),
+            DntValue::Int32(value) => self.write_i32(*value),
+            DntValue::Float32(value) => self.write_f32(*value),
+            DntValue::Float64(value) => self.write_f64(*value),
+            DntValue::Bytes(value) => self.write_unknown(value),
+            DntValue::Null => {
+                let value = self
+                    .nulls
+                    .as_ref()
+                    .and_then(|nulls| nulls.sentinel(column))
+                    .unwrap_or_else(|| default_value(column.data_type));
+                self.write_value(column, &value)
+            }
+        }
+    }
+
+    fn write_footer(&mut self) -> Result<(), DntError> {
+        let closing_text = match &self.options.footer {
+            Some(text) => self.codec.encode(text)?.into_owned(),
+            None => return Ok(()),
+        };
+        let length = u8::try_from(closing_text.len()).map_err(|_| {
+            DntError::InvalidValue(format!(
+                "the footer is {} bytes long, at most 255 fit",
+                closing_text.len()
+            ))
+        })?;
+
+        self.write_u8(length)?;
+        self.write_bytes(&closing_text)
+    }
+
+    fn report_progress(&mut self, rows: u32, total_rows: u32) {
+        if let Some(progress) = &mut self.progress {
+            if progress::is_due(rows, total_rows) {
+                progress(ProgressEvent {
+                    rows,
+                    total_rows,
+                    bytes: self.written,
+                });
+            }
+        }
+    }
+
+    fn write_u16(&mut self, value: u16) -> Result<(), DntError> {
+        let bytes = self.endianness.u16_bytes(value);
+        self.output().write_all(&bytes)?;
+        self.written += 2;
+        Ok(())
     }
 
-    fn write_u32(&mut self, value: u32) {
-        self.file.write_u32::<LittleEndian>(value).unwrap()
+    fn write_u32(&mut self, value: u32) -> Result<(), DntError> {
+        let bytes = self.endianness.u32_bytes(value);
+        self.output().write_all(&bytes)?;
+        self.written += 4;
+        Ok(())
     }
 
-    fn write_i32(&mut self, value: i32) {
-        self.file.write_i32::<LittleEndian>(value).unwrap()
+    fn write_i32(&mut self, value: i32) -> Result<(), DntError> {
+        let bytes = self.endianness.i32_bytes(value);
+        self.output().write_all(&bytes)?;
+        self.written += 4;
+        Ok(())
     }
 
-    fn write_f32(&mut self, value: f32) {
-        self.file.write_f32::<LittleEndian>(value).unwrap()
+    fn write_f32(&mut self, value: f32) -> Result<(), DntError> {
+        let bytes = self.endianness.f32_bytes(value);
+        self.output().write_all(&bytes)?;
+        self.written += 4;
+        Ok(())
     }
 
-    fn write_u8(&mut self, value: u8) {
-        self.file.write_u8(value).unwrap()
+    fn write_f64(&mut self, value: f64) -> Result<(), DntError> {
+        let bytes = self.endianness.f64_bytes(value);
+        self.output().write_all(&bytes)?;
+        self.written += 8;
+        Ok(())
     }
 
-    fn write_string(&mut self, value: String) {
-        self.write_u16(value.len() as u16);
-        self.write_string_bytes(value);
+    fn write_u8(&mut self, value: u8) -> Result<(), DntError> {
+        self.output().write_u8(value)?;
+        self.written += 1;
+        Ok(())
     }
 
-    fn write_string_bytes(&mut self, value: String) {
-        for index in 0..value.len() {
-            self.write_u8(value.chars().nth(index as usize).unwrap() as u8);
+    fn write_unknown(&mut self, value: &[u8]) -> Result<(), DntError> {
+        match self.unknown_width {
+            DntUnknownWidth::LengthPrefixed => {
+                let length =
+                    u16::try_from(value.len()).map_err(|_| DntError::StringTooLong(value.len()))?;
+                self.write_u16(length)?;
+            }
+            DntUnknownWidth::Fixed(width) if width != value.len() => {
+                return Err(DntError::InvalidValue(format!(
+                    "expected {} bytes for unknown column type, got {}",
+                    width,
+                    value.len()
+                )));
+            }
+            DntUnknownWidth::Fixed(_) => {}
         }
+
+        self.write_bytes(value)
+    }
+
+    fn write_string(&mut self, value: &str) -> Result<(), DntError> {
+        let bytes = self.codec.encode(value)?;
+        let length =
+            u16::try_from(bytes.len()).map_err(|_| DntError::StringTooLong(bytes.len()))?;
+
+        self.write_u16(length)?;
+        self.write_bytes(&bytes)
+    }
+
+    fn write_bytes(&mut self, value: &[u8]) -> Result<(), DntError> {
+        self.output().write_all(value)?;
+        self.written += value.len() as u64;
+        Ok(())
+    }
+
+    fn output(&mut self) -> &mut dyn Write {
+        match &mut self.payload {
+            Some(payload) => payload,
+            None => &mut self.writer,
+        }
+    }
+}
+
+pub(crate) fn check_id_column(head: &[DntColumn]) -> Result<(), DntError> {
+    match head.first() {
+        None => Err(DntError::InvalidHeader(String::from(
+            "table has no id column",
+        ))),
+        Some(column) if column.data_type != DntDataType::Int32 => Err(DntError::InvalidHeader(
+            String::from("the id column must be Int32"),
+        )),
+        Some(_) => Ok(()),
+    }
+}
+
+pub(crate) fn check_rows(head: &[DntColumn], body: &[DntRow]) -> Result<(), DntError> {
+    for (index, row) in body.iter().enumerate() {
+        check_row(head, index, row)?;
+    }
+
+    Ok(())
+}
+
+pub(crate) fn check_row(head: &[DntColumn], index: usize, row: &DntRow) -> Result<(), DntError> {
+    if row.values.len() != head.len() {
+        return Err(DntError::InvalidRowLength {
+            row: index,
+            expected: head.len(),
+            found: row.values.len(),
+        });
+    }
+
+    for (column, value) in head.iter().zip(&row.values) {
+        if !value.matches(column.data_type) {
+            return Err(DntError::InvalidCell {
+                row: index,
+                column: column.text.clone(),
+                expected: column.data_type,
+                found: value.data_type(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use crate::{
+        DntDataType, DntError, DntFileReader, DntFileWriter, DntTable, DntTableBuilder, DntValue,
+        FormatProfile,
+    };
+
+    /// A file in the standard layout with one row, whose columns have the
+    /// raw types given, and whose values are given as their bytes.
+    fn file(columns: &[(&str, u8)], values: &[u8]) -> Vec<u8> {
+        let mut bytes = vec![0; 4];
+        bytes.extend((columns.len() as u16).to_le_bytes());
+        bytes.extend(1u32.to_le_bytes());
+        for (name, code) in columns {
+            bytes.extend((name.len() as u16).to_le_bytes());
+            bytes.extend(name.as_bytes());
+            bytes.push(*code);
+        }
+        bytes.extend(7i32.to_le_bytes());
+        bytes.extend(values);
+        bytes.push(5);
+        bytes.extend(b"THEND");
+        bytes
+    }
+
+    #[test]
+    fn reads_type_5_as_a_4_byte_float() {
+        let mut values = 0.5f32.to_le_bytes().to_vec();
+        values.extend(42i32.to_le_bytes());
+        let bytes = file(&[("_Rate", 5), ("_Level", 3)], &values);
+
+        let table = DntTable::parse(&bytes).unwrap();
+        assert_eq!(table.head[1].data_type, DntDataType::Float32);
+        assert_eq!(
+            table.body[0].values,
+            vec![
+                DntValue::Int32(7),
+                DntValue::Float32(0.5),
+                DntValue::Int32(42)
+            ]
+        );
+        assert_eq!(table.to_bytes().unwrap(), bytes);
+    }
+
+    #[test]
+    fn reads_type_5_as_a_double_with_a_profile() {
+        let mut values = 0.1f64.to_le_bytes().to_vec();
+        values.extend(42i32.to_le_bytes());
+        let bytes = file(&[("_Rate", 5), ("_Level", 3)], &values);
+        let profile = FormatProfile {
+            type_codes: vec![(5, DntDataType::Float64)],
+            ..FormatProfile::default()
+        };
+
+        let mut reader = DntFileReader::new(Cursor::new(&bytes)).with_profile(&profile);
+        reader.read().unwrap();
+        let table = std::mem::take(reader.data());
+        assert_eq!(table.head[1].data_type, DntDataType::Float64);
+        assert_eq!(
+            table.body[0].values,
+            vec![
+                DntValue::Int32(7),
+                DntValue::Float64(0.1),
+                DntValue::Int32(42)
+            ]
+        );
+
+        let mut writer = DntFileWriter::new(Cursor::new(vec![])).with_profile(&profile);
+        writer.write(&table).unwrap();
+        assert_eq!(writer.into_inner().unwrap().into_inner(), bytes);
+    }
+
+    #[test]
+    fn writes_doubles_only_with_a_profile() {
+        let table = DntTableBuilder::new()
+            .column("_Rate", DntDataType::Float64)
+            .row((1, 0.1f64))
+            .build()
+            .unwrap();
+        assert!(matches!(table.to_bytes(), Err(DntError::InvalidHeader(_))));
+
+        let profile = FormatProfile {
+            type_codes: vec![(5, DntDataType::Float64)],
+            ..FormatProfile::default()
+        };
+        let mut writer = DntFileWriter::new(Cursor::new(vec![])).with_profile(&profile);
+        writer.write(&table).unwrap();
+        let bytes = writer.into_inner().unwrap().into_inner();
+
+        let mut reader = DntFileReader::new(Cursor::new(&bytes)).with_profile(&profile);
+        reader.read().unwrap();
+        assert!(*reader.data() == table);
+    }
+
+    #[test]
+    fn round_trips_every_type_code() {
+        let mut values = b"\x05\x00Sword".to_vec();
+        values.extend(u32::MAX.to_le_bytes());
+        values.extend((-3i32).to_le_bytes());
+        values.extend(2.5f32.to_le_bytes());
+        values.extend(0.25f32.to_le_bytes());
+        values.extend(b"\x02\x00\xAB\xCD");
+        let columns = [
+            ("_Name", 1),
+            ("_Price", 2),
+            ("_Level", 3),
+            ("_Rate", 4),
+            ("_Chance", 5),
+            ("_Extra", 9),
+        ];
+        let bytes = file(&columns, &values);
+
+        let table = DntTable::parse(&bytes).unwrap();
+        let types = table
+            .head
+            .iter()
+            .map(|column| column.data_type)
+            .collect::<Vec<DntDataType>>();
+        assert_eq!(
+            types,
+            [
+                DntDataType::Int32,
+                DntDataType::String,
+                DntDataType::UInt32,
+                DntDataType::Int32,
+                DntDataType::Float32,
+                DntDataType::Float32,
+                DntDataType::Unknown(9)
+            ]
+        );
+        assert_eq!(
+            table.body[0].values,
+            vec![
+                DntValue::Int32(7),
+                DntValue::String(String::from("Sword")),
+                DntValue::UInt32(u32::MAX),
+                DntValue::Int32(-3),
+                DntValue::Float32(2.5),
+                DntValue::Float32(0.25),
+                DntValue::Bytes(vec![0xAB, 0xCD])
+            ]
+        );
+        assert_eq!(table.to_bytes().unwrap(), bytes);
     }
 }
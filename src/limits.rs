@@ -0,0 +1,37 @@
+use crate::DntError;
+
+/// Caps on what a file may declare, so that a crafted or corrupted header
+/// fails with `DntError::LimitExceeded` instead of exhausting memory. Every
+/// limit is off by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DntLimits {
+    /// The row count in the header.
+    pub max_rows: u32,
+    /// The number of columns, including the id.
+    pub max_columns: usize,
+    /// The length of any string or unknown value, column names included.
+    pub max_string_length: usize,
+    /// The size of the table data, after decompression for compressed
+    /// files.
+    pub max_bytes: u64,
+}
+
+impl Default for DntLimits {
+    fn default() -> Self {
+        Self {
+            max_rows: u32::MAX,
+            max_columns: usize::MAX,
+            max_string_length: usize::MAX,
+            max_bytes: u64::MAX,
+        }
+    }
+}
+
+impl DntLimits {
+    pub(crate) fn check(limit: &'static str, found: u64, max: u64) -> Result<(), DntError> {
+        if found > max {
+            return Err(DntError::LimitExceeded { limit, found, max });
+        }
+        Ok(())
+    }
+}
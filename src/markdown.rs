@@ -0,0 +1,125 @@
+use std::io::Write;
+
+use crate::{DntDataType, DntError, DntTable, DntValue, ExportOptions};
+
+#[derive(Debug, Clone, Default)]
+pub struct MarkdownOptions {
+    /// The number of rows written, or `None` for every row. The rows left
+    /// out are counted in a line after the table.
+    pub max_rows: Option<usize>,
+    /// How values are written and which columns, as for CSV.
+    pub export: ExportOptions,
+}
+
+impl DntTable {
+    /// Writes the table as a GitHub-flavored Markdown table, for wiki pages
+    /// and pull requests. Numeric columns are aligned to the right, and `|`
+    /// and line breaks in strings are escaped so that they don't break the
+    /// table; line breaks become `<br>` unless `escape_newlines` is on.
+    pub fn to_markdown(&self, options: &MarkdownOptions) -> Result<String, DntError> {
+        let mut bytes = vec![];
+        self.write_markdown(&mut bytes, options)?;
+
+        String::from_utf8(bytes).map_err(|_| DntError::InvalidValue(String::from("invalid UTF-8")))
+    }
+
+    pub fn write_markdown<W: Write>(
+        &self,
+        mut writer: W,
+        options: &MarkdownOptions,
+    ) -> Result<(), DntError> {
+        let export = &options.export;
+        let mapping = export.columns.resolve(self)?;
+
+        let names = mapping
+            .iter()
+            .map(|(_, name)| escape(name))
+            .collect::<Vec<String>>();
+        writeln!(writer, "| {} |", names.join(" | "))?;
+
+        let separators = mapping
+            .iter()
+            .map(|(index, _)| match self.head[*index].data_type {
+                DntDataType::String | DntDataType::Unknown(_) => "---",
+                _ => "---:",
+            })
+            .collect::<Vec<&str>>();
+        writeln!(writer, "| {} |", separators.join(" | "))?;
+
+        let shown = options
+            .max_rows
+            .map_or(self.body.len(), |max_rows| max_rows.min(self.body.len()));
+        for row in &self.body[..shown] {
+            let cells = mapping
+                .iter()
+                .map(|(index, _)| {
+                    escape(&export.format_value(row.values.get(*index).unwrap_or(&DntValue::Null)))
+                })
+                .collect::<Vec<String>>();
+            writeln!(writer, "| {} |", cells.join(" | "))?;
+        }
+
+        let hidden = self.body.len() - shown;
+        if hidden > 0 {
+            let noun = if hidden == 1 { "row" } else { "rows" };
+            // The blank line ends the table, which would otherwise take the
+            // line as another row.
+            writeln!(writer, "\n... {} more {}", hidden, noun)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace(['\n', '\r'], "<br>")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ColumnMapping, DntDataType, DntTableBuilder, DntValue, ExportOptions, MarkdownOptions,
+    };
+
+    #[test]
+    fn formats_values_with_the_export_options() {
+        let table = DntTableBuilder::new()
+            .column("_Name", DntDataType::String)
+            .column("_Rate", DntDataType::Float32)
+            .row((1, "a|b\nc", 1.0f32 / 3.0))
+            .row(vec![
+                DntValue::Int32(2),
+                DntValue::Null,
+                DntValue::Float32(2.5),
+            ])
+            .build()
+            .unwrap();
+
+        let options = MarkdownOptions {
+            max_rows: Some(1),
+            export: ExportOptions {
+                float_precision: Some(2),
+                columns: ColumnMapping::new().rename("_Name", "Name"),
+                ..ExportOptions::default()
+            },
+        };
+        assert_eq!(
+            table.to_markdown(&options).unwrap(),
+            "| id | Name | _Rate |\n| ---: | --- | ---: |\n| 1 | a\\|b<br>c | 0.33 |\n\n... 1 more row\n"
+        );
+
+        let options = MarkdownOptions {
+            export: ExportOptions {
+                null: String::from("-"),
+                ..ExportOptions::default()
+            },
+            ..MarkdownOptions::default()
+        };
+        assert!(table
+            .to_markdown(&options)
+            .unwrap()
+            .ends_with("| 2 | - | 2.5 |\n"));
+    }
+}
@@ -0,0 +1,277 @@
+use crate::{
+    diff::{id_index, row_id, same_value},
+    schema::check_head,
+    DntError, DntRow, DntTable, DntValue,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeStrategy {
+    PreferSelf,
+    PreferOther,
+    FailOnConflict,
+}
+
+impl DntTable {
+    pub fn merge(&mut self, other: &DntTable, strategy: MergeStrategy) -> Result<(), DntError> {
+        check_head(&self.head, &other.head)?;
+
+        let ids = id_index(self)?;
+        id_index(other)?;
+
+        let mut replaced = vec![];
+        let mut added = vec![];
+        for row in &other.body {
+            match ids.get(&row_id(row)?) {
+                None => added.push(row.clone()),
+                Some(&index) if !same_row(&self.body[index], row) => match strategy {
+                    MergeStrategy::PreferSelf => {}
+                    MergeStrategy::PreferOther => replaced.push((index, row.clone())),
+                    MergeStrategy::FailOnConflict => {
+                        return Err(DntError::MergeConflict(row_id(row)?))
+                    }
+                },
+                Some(_) => {}
+            }
+        }
+
+        for (index, row) in replaced {
+            self.body[index] = row;
+        }
+        self.body.extend(added);
+        self.refresh_id_index()?;
+
+        Ok(())
+    }
+}
+
+pub struct DntMerge {
+    pub table: DntTable,
+    pub conflicts: Vec<DntConflict>,
+}
+
+/// A cell changed differently on both sides, or changed on one side in a
+/// row the other side deleted, in which case that side's value is `None`.
+#[derive(Debug, Clone)]
+pub struct DntConflict {
+    pub id: i32,
+    pub column: Option<String>,
+    pub base: Option<DntValue>,
+    pub ours: Option<DntValue>,
+    pub theirs: Option<DntValue>,
+}
+
+pub fn merge3(base: &DntTable, ours: &DntTable, theirs: &DntTable) -> Result<DntMerge, DntError> {
+    check_head(&base.head, &ours.head)?;
+    check_head(&base.head, &theirs.head)?;
+
+    let base_ids = id_index(base)?;
+    let our_ids = id_index(ours)?;
+    let their_ids = id_index(theirs)?;
+
+    let mut body = vec![];
+    let mut conflicts = vec![];
+
+    for row in &ours.body {
+        let id = row_id(row)?;
+        let base_row = base_ids.get(&id).map(|&index| &base.body[index]);
+        let their_row = their_ids.get(&id).map(|&index| &theirs.body[index]);
+
+        match (base_row, their_row) {
+            (Some(base_row), None) => {
+                if !same_row(base_row, row) {
+                    row_conflicts(base, id, base_row, Some(row), None, &mut conflicts);
+                    body.push(row.clone());
+                }
+            }
+            (base_row, Some(their_row)) => body.push(merge_row(
+                base,
+                id,
+                base_row,
+                row,
+                their_row,
+                &mut conflicts,
+            )),
+            (None, None) => body.push(row.clone()),
+        }
+    }
+
+    for row in &theirs.body {
+        let id = row_id(row)?;
+        if our_ids.contains_key(&id) {
+            continue;
+        }
+
+        match base_ids.get(&id) {
+            Some(&index) if same_row(&base.body[index], row) => {}
+            Some(&index) => {
+                row_conflicts(base, id, &base.body[index], None, Some(row), &mut conflicts);
+                body.push(row.clone());
+            }
+            None => body.push(row.clone()),
+        }
+    }
+
+    let mut table = DntTable::from_parts(ours.head.clone(), body);
+    table.copy_file_bytes(ours);
+
+    Ok(DntMerge { table, conflicts })
+}
+
+fn merge_row(
+    table: &DntTable,
+    id: i32,
+    base: Option<&DntRow>,
+    ours: &DntRow,
+    theirs: &DntRow,
+    conflicts: &mut Vec<DntConflict>,
+) -> DntRow {
+    let values = ours
+        .values
+        .iter()
+        .zip(&theirs.values)
+        .enumerate()
+        .map(|(index, (our_value, their_value))| {
+            let base_value = base.and_then(|row| row.values.get(index));
+
+            if same_value(our_value, their_value)
+                || base_value.is_some_and(|value| same_value(value, their_value))
+            {
+                return our_value.clone();
+            }
+            if base_value.is_some_and(|value| same_value(value, our_value)) {
+                return their_value.clone();
+            }
+
+            conflicts.push(DntConflict {
+                id,
+                column: Some(table.head[index].text.clone()),
+                base: base_value.cloned(),
+                ours: Some(our_value.clone()),
+                theirs: Some(their_value.clone()),
+            });
+            our_value.clone()
+        })
+        .collect();
+
+    DntRow { values }
+}
+
+/// Reports a row deleted on one side and changed on the other with one
+/// conflict per changed cell, `None` standing for the side that deleted it.
+/// A row whose only change is its number of values gets a single conflict
+/// without a column.
+fn row_conflicts(
+    table: &DntTable,
+    id: i32,
+    base: &DntRow,
+    ours: Option<&DntRow>,
+    theirs: Option<&DntRow>,
+    conflicts: &mut Vec<DntConflict>,
+) {
+    let Some(changed) = ours.or(theirs) else {
+        return;
+    };
+    let count = conflicts.len();
+
+    for (index, value) in changed.values.iter().enumerate() {
+        let base_value = base.values.get(index);
+        if base_value.is_some_and(|base_value| same_value(base_value, value)) {
+            continue;
+        }
+
+        conflicts.push(DntConflict {
+            id,
+            column: table.head.get(index).map(|column| column.text.clone()),
+            base: base_value.cloned(),
+            ours: ours.map(|_| value.clone()),
+            theirs: theirs.map(|_| value.clone()),
+        });
+    }
+
+    if conflicts.len() == count {
+        conflicts.push(DntConflict {
+            id,
+            column: None,
+            base: None,
+            ours: None,
+            theirs: None,
+        });
+    }
+}
+
+fn same_row(row: &DntRow, other: &DntRow) -> bool {
+    row.values.len() == other.values.len()
+        && row
+            .values
+            .iter()
+            .zip(&other.values)
+            .all(|(value, other_value)| same_value(value, other_value))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{merge3, DntDataType, DntTable, DntTableBuilder, DntValue};
+
+    fn table(rows: &[(i32, &str, u32)]) -> DntTable {
+        rows.iter()
+            .fold(
+                DntTableBuilder::new()
+                    .column("_Name", DntDataType::String)
+                    .column("_Level", DntDataType::UInt32),
+                |builder, row| builder.row(*row),
+            )
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn merges_cells_changed_on_one_side() {
+        let base = table(&[(1, "Fire", 1), (2, "Ice", 2)]);
+        let ours = table(&[(1, "Flame", 1), (2, "Ice", 2)]);
+        let theirs = table(&[(1, "Fire", 10), (2, "Ice", 2), (3, "Wind", 3)]);
+
+        let merged = merge3(&base, &ours, &theirs).unwrap();
+        assert!(merged.conflicts.is_empty());
+        assert!(merged.table == table(&[(1, "Flame", 10), (2, "Ice", 2), (3, "Wind", 3)]));
+    }
+
+    #[test]
+    fn reports_cells_changed_on_both_sides() {
+        let base = table(&[(1, "Fire", 1)]);
+        let ours = table(&[(1, "Flame", 1)]);
+        let theirs = table(&[(1, "Blaze", 1)]);
+
+        let merged = merge3(&base, &ours, &theirs).unwrap();
+        assert!(merged.table == ours);
+        assert_eq!(merged.conflicts.len(), 1);
+        let conflict = &merged.conflicts[0];
+        assert_eq!(conflict.id, 1);
+        assert_eq!(conflict.column.as_deref(), Some("_Name"));
+        assert_eq!(conflict.base, Some(DntValue::from("Fire")));
+        assert_eq!(conflict.ours, Some(DntValue::from("Flame")));
+        assert_eq!(conflict.theirs, Some(DntValue::from("Blaze")));
+    }
+
+    #[test]
+    fn reports_the_values_of_rows_deleted_on_one_side() {
+        let base = table(&[(1, "Fire", 1), (2, "Ice", 2)]);
+        let edited = table(&[(1, "Fire", 1), (2, "Ice", 20)]);
+        let deleted = table(&[(1, "Fire", 1)]);
+
+        let merged = merge3(&base, &edited, &deleted).unwrap();
+        assert!(merged.table == edited);
+        assert_eq!(merged.conflicts.len(), 1);
+        let conflict = &merged.conflicts[0];
+        assert_eq!(conflict.id, 2);
+        assert_eq!(conflict.column.as_deref(), Some("_Level"));
+        assert_eq!(conflict.base, Some(DntValue::UInt32(2)));
+        assert_eq!(conflict.ours, Some(DntValue::UInt32(20)));
+        assert_eq!(conflict.theirs, None);
+
+        let merged = merge3(&base, &deleted, &edited).unwrap();
+        assert!(merged.table == edited);
+        let conflict = &merged.conflicts[0];
+        assert_eq!(conflict.ours, None);
+        assert_eq!(conflict.theirs, Some(DntValue::UInt32(20)));
+    }
+}
@@ -0,0 +1,41 @@
+use std::{fs::File, io::Cursor, path::Path};
+
+use memmap2::Mmap;
+
+use crate::{DntError, DntFileReader};
+
+pub struct DntMmap {
+    mmap: Mmap,
+}
+
+impl DntMmap {
+    /// Maps the file into memory. Reading from the mapping reads the file
+    /// itself, so changes made to it while it is mapped show up in
+    /// `bytes()`.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be truncated or modified, by this process or any
+    /// other, until the `DntMmap` and everything borrowed from it are
+    /// dropped. Truncating it makes accesses to the mapping fault, and
+    /// modifying it changes data that is borrowed as immutable, both of
+    /// which are undefined behavior. Use `DntFileReader` on the file instead
+    /// when another program may write to it, such as the game client.
+    pub unsafe fn open(path: impl AsRef<Path>) -> Result<Self, DntError> {
+        let file = File::open(path)?;
+
+        // SAFETY: the caller guarantees that the file stays unchanged for as
+        // long as the mapping lives.
+        let mmap = unsafe { Mmap::map(&file)? };
+
+        Ok(Self { mmap })
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        &self.mmap
+    }
+
+    pub fn reader(&self) -> DntFileReader<Cursor<&[u8]>> {
+        DntFileReader::new(Cursor::new(self.bytes()))
+    }
+}
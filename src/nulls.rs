@@ -0,0 +1,196 @@
+use crate::{diff::same_value, edit::default_value, DntColumn, DntDataType, DntTable, DntValue};
+
+/// Which values of a table stand for "no value". DNT files have no nulls of
+/// their own and use 0 or an empty string instead; with these rules those
+/// values are read as `DntValue::Null`, and `Null` is written back as them.
+/// The id column never holds nulls.
+#[derive(Debug, Clone, Default)]
+pub struct DntNulls {
+    zeros: bool,
+    columns: Vec<(String, Option<DntValue>)>,
+}
+
+impl DntNulls {
+    /// No value is null unless a column is given with `column`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The zero value of every column is null: 0, 0.0, an empty string or
+    /// no bytes, depending on the column type.
+    pub fn zeros() -> Self {
+        Self {
+            zeros: true,
+            columns: vec![],
+        }
+    }
+
+    /// `sentinel` is the null value of `column`, in place of its zero value.
+    pub fn column(mut self, column: impl Into<String>, sentinel: impl Into<DntValue>) -> Self {
+        self.columns.push((column.into(), Some(sentinel.into())));
+        self
+    }
+
+    /// `column` has no null value, even when zeros are null.
+    pub fn except(mut self, column: impl Into<String>) -> Self {
+        self.columns.push((column.into(), None));
+        self
+    }
+
+    /// The value standing for null in a column, in the type of the column.
+    pub fn sentinel(&self, column: &DntColumn) -> Option<DntValue> {
+        let rule = self
+            .columns
+            .iter()
+            .rev()
+            .find(|(name, _)| *name == column.text);
+
+        match rule {
+            Some((_, Some(sentinel))) => convert(sentinel, column.data_type()),
+            Some((_, None)) => None,
+            None if self.zeros => Some(default_value(column.data_type())),
+            None => None,
+        }
+    }
+
+    /// The null value of every column but the id.
+    pub(crate) fn sentinels(&self, head: &[DntColumn]) -> Vec<Option<DntValue>> {
+        head.iter()
+            .enumerate()
+            .map(|(index, column)| {
+                if index == 0 {
+                    None
+                } else {
+                    self.sentinel(column)
+                }
+            })
+            .collect()
+    }
+}
+
+fn convert(value: &DntValue, data_type: DntDataType) -> Option<DntValue> {
+    match data_type {
+        DntDataType::String => value.as_str().map(DntValue::from),
+        DntDataType::UInt32 => value.as_u32().map(DntValue::UInt32),
+        DntDataType::Int32 => value.as_i32().map(DntValue::Int32),
+        DntDataType::Float32 => value.as_f64().map(|value| DntValue::Float32(value as f32)),
+        DntDataType::Float64 => value.as_f64().map(DntValue::Float64),
+        DntDataType::Unknown(_) => value
+            .as_bytes()
+            .map(|bytes| DntValue::Bytes(bytes.to_vec())),
+    }
+}
+
+/// Replaces the values equal to the null value of their column with `Null`.
+pub(crate) fn apply(sentinels: &[Option<DntValue>], values: &mut [DntValue]) {
+    for (value, sentinel) in values.iter_mut().zip(sentinels) {
+        if sentinel
+            .as_ref()
+            .is_some_and(|sentinel| same_value(value, sentinel))
+        {
+            *value = DntValue::Null;
+        }
+    }
+}
+
+impl DntTable {
+    /// Replaces the values standing for null with `DntValue::Null`.
+    pub fn apply_nulls(&mut self, nulls: &DntNulls) {
+        let sentinels = nulls.sentinels(&self.head);
+        for row in &mut self.body {
+            apply(&sentinels, &mut row.values);
+        }
+    }
+
+    /// Replaces `DntValue::Null` with the null value of its column, or the
+    /// zero value of the column type if it has none.
+    pub fn fill_nulls(&mut self, nulls: &DntNulls) {
+        let sentinels = nulls.sentinels(&self.head);
+        for row in &mut self.body {
+            for ((value, sentinel), column) in row.values.iter_mut().zip(&sentinels).zip(&self.head)
+            {
+                if let DntValue::Null = value {
+                    *value = sentinel
+                        .clone()
+                        .unwrap_or_else(|| default_value(column.data_type()));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use crate::{
+        DntDataType, DntFileReader, DntFileWriter, DntNulls, DntTable, DntTableBuilder, DntValue,
+    };
+
+    fn table() -> DntTable {
+        DntTableBuilder::new()
+            .column("_Name", DntDataType::String)
+            .column("_Level", DntDataType::UInt32)
+            .column("_Job", DntDataType::Int32)
+            .row((1, "", 0u32, -1))
+            .row((2, "Sword", 5u32, 0))
+            .build()
+            .unwrap()
+    }
+
+    fn nulls() -> DntNulls {
+        DntNulls::zeros().column("_Job", -1).except("_Level")
+    }
+
+    #[test]
+    fn reads_and_writes_sentinels() {
+        let bytes = table().to_bytes().unwrap();
+
+        let mut reader = DntFileReader::new(Cursor::new(&bytes)).with_nulls(nulls());
+        reader.read().unwrap();
+        let read = std::mem::take(reader.data());
+        assert_eq!(
+            read.body[0].values,
+            [
+                DntValue::Int32(1),
+                DntValue::Null,
+                DntValue::UInt32(0),
+                DntValue::Null
+            ]
+        );
+        assert_eq!(read.body[1].values[3], DntValue::Int32(0));
+
+        let mut writer = DntFileWriter::new(Cursor::new(vec![])).with_nulls(nulls());
+        writer.write(&read).unwrap();
+        assert_eq!(writer.into_inner().unwrap().into_inner(), bytes);
+    }
+
+    #[test]
+    fn applies_and_fills_in_memory() {
+        let mut table = table();
+        table.apply_nulls(&nulls());
+        assert_eq!(table.body[0].values[1], DntValue::Null);
+        assert_eq!(table.body[0].values[2], DntValue::UInt32(0));
+
+        table.fill_nulls(&nulls());
+        assert!(table == self::table());
+    }
+
+    #[test]
+    fn converts_sentinels_to_the_column_type() {
+        let table = table();
+        let nulls = DntNulls::new().column("_Level", 5);
+        assert_eq!(nulls.sentinel(&table.head[1]), None);
+        assert_eq!(nulls.sentinel(&table.head[2]), Some(DntValue::UInt32(5)));
+        assert_eq!(nulls.sentinels(&table.head)[0], None);
+        assert_eq!(
+            DntNulls::zeros().sentinels(&table.head),
+            [
+                None,
+                Some(DntValue::from("")),
+                Some(DntValue::UInt32(0)),
+                Some(DntValue::Int32(0))
+            ]
+        );
+    }
+}
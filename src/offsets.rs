@@ -0,0 +1,228 @@
+use std::io::{BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+
+use crate::{DntError, DntFileReader, DntHeader, DntRow};
+
+const MAGIC: &[u8; 4] = b"DNTO";
+const VERSION: u8 = 1;
+
+/// Byte offsets of the rows of a table, from the start of the table data.
+/// They can be saved next to a file to skip the first pass on later runs.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DntRowOffsets {
+    offsets: Vec<u64>,
+    body_end: u64,
+}
+
+impl DntRowOffsets {
+    pub fn len(&self) -> usize {
+        self.offsets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+
+    pub fn get(&self, row: usize) -> Option<u64> {
+        self.offsets.get(row).copied()
+    }
+
+    /// Offset of the first byte after the last row.
+    pub fn body_end(&self) -> u64 {
+        self.body_end
+    }
+
+    pub fn write<W: Write>(&self, writer: W) -> Result<(), DntError> {
+        let mut writer = BufWriter::new(writer);
+
+        writer.write_all(MAGIC)?;
+        writer.write_u8(VERSION)?;
+        writer.write_u32::<LittleEndian>(self.offsets.len() as u32)?;
+        for &offset in &self.offsets {
+            writer.write_u64::<LittleEndian>(offset)?;
+        }
+        writer.write_u64::<LittleEndian>(self.body_end)?;
+
+        writer.flush()?;
+        Ok(())
+    }
+
+    pub fn read<R: Read>(reader: R) -> Result<Self, DntError> {
+        let mut reader = BufReader::new(reader);
+
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(DntError::InvalidHeader(String::from(
+                "not a DNT row offset index",
+            )));
+        }
+
+        let version = reader.read_u8()?;
+        if version != VERSION {
+            return Err(DntError::InvalidHeader(format!(
+                "unsupported row offset index version {}",
+                version
+            )));
+        }
+
+        let offsets = (0..reader.read_u32::<LittleEndian>()?)
+            .map(|_| reader.read_u64::<LittleEndian>())
+            .collect::<Result<Vec<u64>, _>>()?;
+        let body_end = reader.read_u64::<LittleEndian>()?;
+
+        Ok(Self { offsets, body_end })
+    }
+}
+
+impl<R: Read + Seek> DntFileReader<R> {
+    /// Walks the file once, skipping over the values, to find where each row
+    /// starts. The offsets are kept for `read_row_at` and returned so they can
+    /// be cached.
+    pub fn build_row_offsets(&mut self) -> Result<&DntRowOffsets, DntError> {
+        let header = self.read_header()?;
+
+        let mut offset = self.position()?;
+        let mut offsets = Vec::with_capacity(header.rows_nb as usize);
+        for _ in 0..header.rows_nb {
+            offsets.push(offset);
+            for column in &header.head {
+                offset += self.skip_value(column.data_type)?;
+            }
+        }
+
+        self.seek_from(SeekFrom::Start(offset))?;
+        self.read_footer()?;
+
+        self.row_layout = Some(self.layout(header)?);
+        Ok(self.row_offsets.insert(DntRowOffsets {
+            offsets,
+            body_end: offset,
+        }))
+    }
+
+    /// Uses offsets built earlier, for example read from a cache, instead of
+    /// walking the file. They are checked against the row count of the file
+    /// on the first call to `read_row_at`.
+    pub fn with_row_offsets(mut self, offsets: DntRowOffsets) -> Self {
+        self.row_offsets = Some(offsets);
+        self.row_layout = None;
+        self
+    }
+
+    pub fn row_offsets(&self) -> Option<&DntRowOffsets> {
+        self.row_offsets.as_ref()
+    }
+
+    /// Reads a single row without parsing the rows before it. The row offsets
+    /// are built on the first call unless they were given with
+    /// `with_row_offsets`.
+    pub fn read_row_at(&mut self, row: usize) -> Result<DntRow, DntError> {
+        if self.row_offsets.is_none() {
+            self.build_row_offsets()?;
+        }
+
+        let layout = match self.row_layout.take() {
+            Some(layout) => layout,
+            None => {
+                let header = self.read_header()?;
+                self.check_row_offsets(&header)?;
+                self.layout(header)?
+            }
+        };
+
+        let offset = self
+            .row_offsets
+            .as_ref()
+            .and_then(|offsets| offsets.get(row))
+            .ok_or_else(|| DntError::InvalidValue(format!("row {} is out of range", row)))?;
+
+        self.seek_from(SeekFrom::Start(offset))?;
+        let result = self.read_row(&layout, row as u32);
+        self.row_layout = Some(layout);
+
+        result
+    }
+
+    fn check_row_offsets(&self, header: &DntHeader) -> Result<(), DntError> {
+        let rows = self.row_offsets.as_ref().map_or(0, DntRowOffsets::len);
+        if rows != header.rows_nb as usize {
+            return Err(DntError::InvalidHeader(format!(
+                "row offsets cover {} rows, the file has {}",
+                rows, header.rows_nb
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::DntRowOffsets;
+    use crate::{DntDataType, DntError, DntFileReader, DntTable, DntTableBuilder};
+
+    fn table() -> DntTable {
+        DntTableBuilder::new()
+            .column("_Name", DntDataType::String)
+            .row((1, "Sword"))
+            .row((2, "Bow"))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn finds_where_each_row_starts() {
+        let bytes = table().to_bytes().unwrap();
+        let mut reader = DntFileReader::new(Cursor::new(&bytes[..]));
+        let offsets = reader.build_row_offsets().unwrap().clone();
+
+        // An 18-byte header, then an id and a string of 2 + 5 and 2 + 3 bytes.
+        assert_eq!(offsets.len(), 2);
+        assert_eq!(offsets.get(0), Some(18));
+        assert_eq!(offsets.get(1), Some(29));
+        assert_eq!(offsets.get(2), None);
+        assert_eq!(offsets.body_end(), 38);
+        assert_eq!(&bytes[38..], b"\x05THEND");
+
+        for (row, id) in [(0, 1i32), (1, 2)] {
+            let offset = offsets.get(row).unwrap() as usize;
+            assert_eq!(bytes[offset..offset + 4], id.to_le_bytes());
+        }
+
+        assert_eq!(reader.read_row_at(1).unwrap(), table().body[1]);
+        assert_eq!(reader.read_row_at(0).unwrap(), table().body[0]);
+        assert!(reader.read_row_at(2).is_err());
+    }
+
+    #[test]
+    fn reads_back_saved_offsets() {
+        let bytes = table().to_bytes().unwrap();
+        let offsets = DntFileReader::new(Cursor::new(&bytes[..]))
+            .build_row_offsets()
+            .unwrap()
+            .clone();
+
+        let mut saved = vec![];
+        offsets.write(&mut saved).unwrap();
+        let read = DntRowOffsets::read(&saved[..]).unwrap();
+        assert_eq!(read, offsets);
+
+        let mut reader = DntFileReader::new(Cursor::new(&bytes[..])).with_row_offsets(read);
+        assert_eq!(reader.read_row_at(1).unwrap(), table().body[1]);
+    }
+
+    #[test]
+    fn rejects_offsets_for_another_row_count() {
+        let bytes = table().to_bytes().unwrap();
+        let mut reader =
+            DntFileReader::new(Cursor::new(&bytes[..])).with_row_offsets(DntRowOffsets::default());
+        assert!(matches!(
+            reader.read_row_at(0),
+            Err(DntError::InvalidHeader(_))
+        ));
+    }
+}
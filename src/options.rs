@@ -0,0 +1,85 @@
+use encoding_rs::{Encoding, WINDOWS_1252};
+
+/// The text of the footer written after the last row.
+pub(crate) const FOOTER: &str = "THEND";
+
+/// How forgiving `DntFileReader` is with files that don't follow the format
+/// to the letter. The default reads what the game client reads; `strict`
+/// suits validators and `lenient` salvage tools.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReadOptions {
+    /// Reads columns of a type the crate doesn't know as `DntValue::Bytes`.
+    /// When off, such a column fails with `DntError::InvalidColumnType`.
+    pub allow_unknown_types: bool,
+    /// Accepts a missing or different footer, see `with_lenient_footer`.
+    pub lenient_footer: bool,
+    /// The text the footer holds, or `None` for files without a footer,
+    /// which end after the last row.
+    pub footer: Option<String>,
+    /// Accepts bytes after the footer. When off, they fail with
+    /// `DntError::InvalidFooter`.
+    pub allow_trailing_bytes: bool,
+    /// Keeps the bytes after the footer in `DntTable::trailing_bytes`, for
+    /// `DntFileWriter::with_trailing_bytes` to write back.
+    pub keep_trailing_bytes: bool,
+    /// Reads rows until the footer or the end of the data instead of
+    /// trusting the row count in the header, so files with more or fewer
+    /// rows than announced can be read.
+    pub lenient_row_count: bool,
+    /// Decodes strings the codec rejects with this encoding instead of
+    /// failing.
+    pub fallback_encoding: Option<&'static Encoding>,
+}
+
+impl Default for ReadOptions {
+    fn default() -> Self {
+        Self {
+            allow_unknown_types: true,
+            lenient_footer: false,
+            footer: Some(String::from(FOOTER)),
+            allow_trailing_bytes: true,
+            keep_trailing_bytes: false,
+            lenient_row_count: false,
+            fallback_encoding: None,
+        }
+    }
+}
+
+impl ReadOptions {
+    /// Rejects anything the format doesn't define: unknown column types,
+    /// a missing footer and trailing bytes.
+    pub fn strict() -> Self {
+        Self {
+            allow_unknown_types: false,
+            allow_trailing_bytes: false,
+            ..Self::default()
+        }
+    }
+
+    /// Reads as much as it can: the footer and row count are not checked and
+    /// undecodable strings fall back to Windows-1252, which accepts any byte.
+    pub fn lenient() -> Self {
+        Self {
+            lenient_footer: true,
+            lenient_row_count: true,
+            fallback_encoding: Some(WINDOWS_1252),
+            ..Self::default()
+        }
+    }
+}
+
+/// How `DntFileWriter` lays out what surrounds the rows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WriteOptions {
+    /// The text of the footer written after the last row, at most 255 bytes
+    /// once encoded, or `None` to end the file after the last row.
+    pub footer: Option<String>,
+}
+
+impl Default for WriteOptions {
+    fn default() -> Self {
+        Self {
+            footer: Some(String::from(FOOTER)),
+        }
+    }
+}
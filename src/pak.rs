@@ -0,0 +1,271 @@
+use std::{
+    fs::File,
+    io::{BufReader, Cursor, Read, Seek, SeekFrom},
+    path::Path,
+};
+
+use byteorder::{LittleEndian, ReadBytesExt};
+use flate2::read::ZlibDecoder;
+
+use crate::{DntError, DntFileReader};
+
+const SIGNATURE: &[u8] = b"EyedentityGames Packing File";
+const SIGNATURE_LENGTH: usize = 256;
+const PATH_LENGTH: usize = 256;
+const ENTRY_RESERVED: i64 = 40;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DntPakEntry {
+    /// Path inside the archive, as stored, e.g. `\resource\ext\skilltable.dnt`.
+    pub path: String,
+    pub size: u32,
+    pub stored_size: u32,
+    pub offset: u32,
+}
+
+impl DntPakEntry {
+    pub fn is_dnt(&self) -> bool {
+        self.path.to_ascii_lowercase().ends_with(".dnt")
+    }
+
+    /// The file name without its directories.
+    pub fn name(&self) -> &str {
+        self.path.rsplit(['\\', '/']).next().unwrap_or(&self.path)
+    }
+}
+
+/// A Dragon Nest `.pak` resource archive. The archive starts with a
+/// NUL-padded `EyedentityGames Packing File` signature, followed by the file
+/// count and the offset of the file table, whose entries give the path, sizes
+/// and offset of each zlib-compressed file.
+pub struct DntPak<R = File> {
+    reader: BufReader<R>,
+    entries: Vec<DntPakEntry>,
+}
+
+impl DntPak<File> {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, DntError> {
+        Self::new(File::open(path)?)
+    }
+}
+
+impl<R: Read + Seek> DntPak<R> {
+    pub fn new(reader: R) -> Result<Self, DntError> {
+        let mut reader = BufReader::new(reader);
+
+        let mut signature = [0; SIGNATURE_LENGTH];
+        reader.read_exact(&mut signature)?;
+        if !signature.starts_with(SIGNATURE) {
+            return Err(DntError::InvalidHeader(String::from("not a pak archive")));
+        }
+
+        let _version = reader.read_u32::<LittleEndian>()?;
+        let files_nb = reader.read_u32::<LittleEndian>()?;
+        let table_offset = reader.read_u32::<LittleEndian>()?;
+
+        reader.seek(SeekFrom::Start(table_offset as u64))?;
+
+        let entries = (0..files_nb)
+            .map(|_| {
+                let mut path = [0; PATH_LENGTH];
+                reader.read_exact(&mut path)?;
+                let length = path
+                    .iter()
+                    .position(|&byte| byte == 0)
+                    .unwrap_or(PATH_LENGTH);
+
+                let stored_size = reader.read_u32::<LittleEndian>()?;
+                let size = reader.read_u32::<LittleEndian>()?;
+                let _stored_size = reader.read_u32::<LittleEndian>()?;
+                let offset = reader.read_u32::<LittleEndian>()?;
+                let _checksum = reader.read_u32::<LittleEndian>()?;
+                reader.seek_relative(ENTRY_RESERVED)?;
+
+                Ok(DntPakEntry {
+                    path: String::from_utf8_lossy(&path[..length]).into_owned(),
+                    size,
+                    stored_size,
+                    offset,
+                })
+            })
+            .collect::<Result<Vec<DntPakEntry>, DntError>>()?;
+
+        Ok(Self { reader, entries })
+    }
+
+    pub fn entries(&self) -> &[DntPakEntry] {
+        &self.entries
+    }
+
+    pub fn dnt_entries(&self) -> impl Iterator<Item = &DntPakEntry> {
+        self.entries.iter().filter(|entry| entry.is_dnt())
+    }
+
+    /// Finds an entry by path, ignoring case, the direction of the slashes
+    /// and a leading slash. A bare file name matches too when no entry has
+    /// that path.
+    pub fn find(&self, path: &str) -> Option<&DntPakEntry> {
+        let path = normalize(path);
+
+        self.entries
+            .iter()
+            .find(|entry| normalize(&entry.path) == path)
+            .or_else(|| {
+                self.entries
+                    .iter()
+                    .find(|entry| entry.name().eq_ignore_ascii_case(&path))
+            })
+    }
+
+    /// Decompresses an entry.
+    pub fn extract(&mut self, entry: &DntPakEntry) -> Result<Vec<u8>, DntError> {
+        self.reader.seek(SeekFrom::Start(entry.offset as u64))?;
+
+        let mut stored = vec![0; entry.stored_size as usize];
+        self.reader.read_exact(&mut stored)?;
+
+        let mut bytes = Vec::with_capacity(entry.size as usize);
+        ZlibDecoder::new(stored.as_slice()).read_to_end(&mut bytes)?;
+
+        if bytes.len() != entry.size as usize {
+            return Err(DntError::InvalidValue(format!(
+                "{} should be {} bytes, got {}",
+                entry.path,
+                entry.size,
+                bytes.len()
+            )));
+        }
+
+        Ok(bytes)
+    }
+
+    /// Returns a reader over a table in the archive, found with `find`.
+    pub fn reader(&mut self, path: &str) -> Result<DntFileReader<Cursor<Vec<u8>>>, DntError> {
+        let entry = self
+            .find(path)
+            .cloned()
+            .ok_or_else(|| DntError::InvalidValue(format!("{} is not in the archive", path)))?;
+
+        Ok(DntFileReader::new(Cursor::new(self.extract(&entry)?)))
+    }
+}
+
+fn normalize(path: &str) -> String {
+    path.replace('/', "\\")
+        .trim_start_matches('\\')
+        .to_ascii_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+
+    use flate2::{write::ZlibEncoder, Compression};
+
+    use super::{DntPak, PATH_LENGTH, SIGNATURE, SIGNATURE_LENGTH};
+    use crate::{DntDataType, DntError, DntTable, DntTableBuilder};
+
+    fn table() -> DntTable {
+        DntTableBuilder::new()
+            .column("_Name", DntDataType::String)
+            .row((1, "Sword"))
+            .build()
+            .unwrap()
+    }
+
+    /// An archive of `(path, contents, size)`, where `size` is the
+    /// decompressed size written in the file table.
+    fn archive(files: &[(&str, &[u8], u32)]) -> Vec<u8> {
+        let mut bytes = SIGNATURE.to_vec();
+        bytes.resize(SIGNATURE_LENGTH, 0);
+        bytes.extend(11u32.to_le_bytes());
+        bytes.extend((files.len() as u32).to_le_bytes());
+        bytes.extend([0; 4]);
+
+        let mut entries = vec![];
+        for &(path, contents, size) in files {
+            let mut encoder = ZlibEncoder::new(vec![], Compression::default());
+            encoder.write_all(contents).unwrap();
+            let stored = encoder.finish().unwrap();
+
+            let mut entry = path.as_bytes().to_vec();
+            entry.resize(PATH_LENGTH, 0);
+            entry.extend((stored.len() as u32).to_le_bytes());
+            entry.extend(size.to_le_bytes());
+            entry.extend((stored.len() as u32).to_le_bytes());
+            entry.extend((bytes.len() as u32).to_le_bytes());
+            entry.extend([0; 4 + 40]);
+            entries.extend(entry);
+            bytes.extend(stored);
+        }
+
+        let table_offset = bytes.len() as u32;
+        bytes[SIGNATURE_LENGTH + 8..SIGNATURE_LENGTH + 12]
+            .copy_from_slice(&table_offset.to_le_bytes());
+        bytes.extend(entries);
+        bytes
+    }
+
+    fn pak() -> DntPak<Cursor<Vec<u8>>> {
+        let dnt = table().to_bytes().unwrap();
+        DntPak::new(Cursor::new(archive(&[
+            (r"\resource\ext\skilltable.dnt", &dnt, dnt.len() as u32),
+            (r"\resource\uistring\readme.txt", b"hello", 5),
+            (r"\resource\ext\broken.dnt", b"short", 9),
+        ])))
+        .unwrap()
+    }
+
+    #[test]
+    fn lists_the_entries() {
+        let pak = pak();
+        assert_eq!(pak.entries().len(), 3);
+        assert_eq!(pak.entries()[1].name(), "readme.txt");
+        let tables = pak
+            .dnt_entries()
+            .map(|entry| entry.name())
+            .collect::<Vec<&str>>();
+        assert_eq!(tables, ["skilltable.dnt", "broken.dnt"]);
+    }
+
+    #[test]
+    fn finds_entries_by_path_or_name() {
+        let pak = pak();
+        let path = r"\resource\ext\skilltable.dnt";
+        for query in [
+            path,
+            "resource/ext/SkillTable.dnt",
+            "/RESOURCE/EXT/skilltable.dnt",
+            "skilltable.dnt",
+        ] {
+            assert_eq!(pak.find(query).map(|entry| entry.path.as_str()), Some(path));
+        }
+        assert!(pak.find("ext/missing.dnt").is_none());
+    }
+
+    #[test]
+    fn extracts_entries() {
+        let mut pak = pak();
+        let entry = pak.find("readme.txt").unwrap().clone();
+        assert_eq!(pak.extract(&entry).unwrap(), b"hello");
+
+        let mut reader = pak.reader("skilltable.dnt").unwrap();
+        reader.read().unwrap();
+        assert!(*reader.data() == table());
+
+        let entry = pak.find("broken.dnt").unwrap().clone();
+        assert!(matches!(
+            pak.extract(&entry),
+            Err(DntError::InvalidValue(_))
+        ));
+        assert!(pak.reader("missing.dnt").is_err());
+    }
+
+    #[test]
+    fn rejects_other_files() {
+        assert!(matches!(
+            DntPak::new(Cursor::new(vec![0; 300])),
+            Err(DntError::InvalidHeader(_))
+        ));
+    }
+}
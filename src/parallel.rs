@@ -0,0 +1,160 @@
+use std::io::{Read, Seek, SeekFrom};
+
+use rayon::prelude::*;
+
+use crate::{
+    borrowed::DntRowsRef, DntError, DntFileReader, DntLayout, DntLimits, DntRow, DntValue,
+    ProgressEvent,
+};
+
+/// Below this many rows, splitting the body costs more than decoding it on a
+/// single thread.
+pub(crate) const MIN_PARALLEL_ROWS: u32 = 4096;
+
+const CHUNK_ROWS: u32 = 1024;
+
+impl<R: Read + Seek> DntFileReader<R> {
+    /// Loads the rest of the body into memory, finds where each chunk of rows
+    /// starts on the current thread and decodes the chunks in parallel.
+    pub(crate) fn read_body_parallel(
+        &mut self,
+        layout: &DntLayout,
+        rows_nb: u32,
+    ) -> Result<Vec<DntRow>, DntError> {
+        let body_start = self.position()?;
+
+        let max_bytes = self.limits.max_bytes;
+        let mut bytes = vec![];
+        self.input()
+            .take(max_bytes.saturating_sub(body_start).saturating_add(1))
+            .read_to_end(&mut bytes)?;
+        DntLimits::check("the data size", body_start + bytes.len() as u64, max_bytes)?;
+
+        let limits = self.limits;
+        let mut rows = DntRowsRef::new(
+            &bytes,
+            0,
+            layout.clone(),
+            self.unknown_width,
+            limits,
+            rows_nb,
+        );
+        let mut chunks = vec![];
+        let mut remaining = rows_nb;
+        while remaining > 0 {
+            let count = remaining.min(CHUNK_ROWS);
+            chunks.push((rows.position(), count));
+            for _ in 0..count {
+                rows.skip_row()?;
+            }
+            remaining -= count;
+        }
+        let body_end = body_start + rows.position() as u64;
+        let chunk_ends = chunks
+            .iter()
+            .skip(1)
+            .map(|&(position, _)| body_start + position as u64)
+            .chain([body_end])
+            .collect::<Vec<u64>>();
+
+        let codec = &*self.codec;
+        let unknown_width = self.unknown_width;
+        let chunks = chunks
+            .into_par_iter()
+            .map(|(position, count)| {
+                DntRowsRef::new(
+                    &bytes,
+                    position,
+                    layout.clone(),
+                    unknown_width,
+                    limits,
+                    count,
+                )
+                .map(|values| {
+                    let values = values?
+                        .iter()
+                        .map(|value| value.to_value(codec))
+                        .collect::<Result<Vec<DntValue>, DntError>>()?;
+                    let mut row = DntRow { values };
+                    layout.apply_nulls(&mut row);
+                    Ok(row)
+                })
+                .collect::<Result<Vec<DntRow>, DntError>>()
+            })
+            .collect::<Result<Vec<Vec<DntRow>>, DntError>>()?;
+
+        self.seek_from(SeekFrom::Start(body_end))?;
+
+        if let Some(progress) = &mut self.progress {
+            progress(ProgressEvent {
+                rows: 0,
+                total_rows: rows_nb,
+                bytes: body_start,
+            });
+
+            let mut rows = 0;
+            for (chunk, bytes) in chunks.iter().zip(chunk_ends) {
+                rows += chunk.len() as u32;
+                progress(ProgressEvent {
+                    rows,
+                    total_rows: rows_nb,
+                    bytes,
+                });
+            }
+        }
+
+        Ok(chunks.into_iter().flatten().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::Cursor,
+        sync::{Arc, Mutex},
+    };
+
+    use rayon::ThreadPoolBuilder;
+
+    use super::MIN_PARALLEL_ROWS;
+    use crate::{DntDataType, DntFileReader, DntTable, DntTableBuilder, ProgressEvent};
+
+    fn read(bytes: &[u8], threads: usize) -> (DntTable, Vec<ProgressEvent>) {
+        let events = Arc::new(Mutex::new(vec![]));
+        let recorded = Arc::clone(&events);
+        let mut reader = DntFileReader::new(Cursor::new(bytes))
+            .with_progress(move |event| recorded.lock().unwrap().push(event));
+
+        let pool = ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .unwrap();
+        pool.install(|| reader.read()).unwrap();
+
+        let table = std::mem::take(reader.data());
+        drop(reader);
+        let events = Arc::try_unwrap(events).unwrap().into_inner().unwrap();
+        (table, events)
+    }
+
+    #[test]
+    fn reads_like_a_single_thread() {
+        let rows = MIN_PARALLEL_ROWS as i32 + 500;
+        let mut builder = DntTableBuilder::new()
+            .column("_Name", DntDataType::String)
+            .column("_Level", DntDataType::UInt32);
+        for id in 1..=rows {
+            builder = builder.row((id, "x".repeat(id as usize % 7), id as u32 * 3));
+        }
+        let table = builder.build().unwrap();
+        let bytes = table.to_bytes().unwrap();
+
+        let (single, single_events) = read(&bytes, 1);
+        let (parallel, parallel_events) = read(&bytes, 4);
+        assert!(single == table);
+        assert!(parallel == table);
+        assert_eq!(parallel_events, single_events);
+        assert_eq!(single_events.len(), 1 + rows as usize / 1024 + 1);
+        assert_eq!(single_events.last().unwrap().rows, rows as u32);
+    }
+}
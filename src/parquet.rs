@@ -0,0 +1,105 @@
+use std::io::Write;
+
+use parquet::{
+    arrow::ArrowWriter,
+    basic::{Compression, GzipLevel, ZstdLevel},
+    file::properties::WriterProperties,
+};
+
+use crate::{DntError, DntTable};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParquetCompression {
+    Uncompressed,
+    Snappy,
+    Gzip,
+    Zstd,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ParquetOptions {
+    pub compression: ParquetCompression,
+}
+
+impl Default for ParquetOptions {
+    fn default() -> Self {
+        Self {
+            compression: ParquetCompression::Snappy,
+        }
+    }
+}
+
+impl DntTable {
+    pub fn to_parquet<W: Write + Send>(
+        &self,
+        writer: W,
+        options: &ParquetOptions,
+    ) -> Result<(), DntError> {
+        let batch = self.to_arrow()?;
+
+        let compression = match options.compression {
+            ParquetCompression::Uncompressed => Compression::UNCOMPRESSED,
+            ParquetCompression::Snappy => Compression::SNAPPY,
+            ParquetCompression::Gzip => Compression::GZIP(GzipLevel::default()),
+            ParquetCompression::Zstd => Compression::ZSTD(ZstdLevel::default()),
+        };
+        let properties = WriterProperties::builder()
+            .set_compression(compression)
+            .build();
+
+        let mut writer = ArrowWriter::try_new(writer, batch.schema(), Some(properties))?;
+        writer.write(&batch)?;
+        writer.close()?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{self, File};
+
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    use crate::{
+        DntDataType, DntTable, DntTableBuilder, DntValue, ParquetCompression, ParquetOptions,
+    };
+
+    fn table() -> DntTable {
+        let mut table = DntTableBuilder::new()
+            .column("_Name", DntDataType::String)
+            .column("_Rate", DntDataType::Float32)
+            .row((1, "Sword", 0.5f32))
+            .row((2, DntValue::Null, DntValue::Null))
+            .build()
+            .unwrap();
+        table.head[2].raw_data_type = 5;
+        table
+    }
+
+    #[test]
+    fn keeps_raw_types_and_nulls() {
+        let directory = std::env::temp_dir().join(format!("dnt-parquet-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+
+        for compression in [ParquetCompression::Uncompressed, ParquetCompression::Zstd] {
+            let path = directory.join("items.parquet");
+            table()
+                .to_parquet(
+                    File::create(&path).unwrap(),
+                    &ParquetOptions { compression },
+                )
+                .unwrap();
+
+            let mut batches = ParquetRecordBatchReaderBuilder::try_new(File::open(&path).unwrap())
+                .unwrap()
+                .build()
+                .unwrap();
+            let batch = batches.next().unwrap().unwrap();
+            assert!(batches.next().is_none());
+            assert!(DntTable::from_arrow(&batch).unwrap() == table());
+        }
+
+        fs::remove_dir_all(directory).unwrap();
+    }
+}
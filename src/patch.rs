@@ -0,0 +1,648 @@
+use std::{
+    collections::{HashMap, HashSet},
+    io::{BufReader, BufWriter, Read, Write},
+};
+
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+
+use crate::{
+    diff::{column_index, diff, id_index, row_id},
+    edit::default_value,
+    DntColumn, DntDataType, DntError, DntLimits, DntRow, DntSchemaChange, DntTable, DntValue,
+};
+
+const MAGIC: &[u8; 4] = b"DNTP";
+const VERSION: u8 = 2;
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DntPatch {
+    pub schema: Vec<DntSchemaChange>,
+    pub removed: Vec<i32>,
+    pub added: Vec<DntRowPatch>,
+    pub changed: Vec<DntRowPatch>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DntRowPatch {
+    pub id: i32,
+    pub cells: Vec<DntCellPatch>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DntCellPatch {
+    pub column: String,
+    pub value: DntValue,
+}
+
+impl DntPatch {
+    pub fn new(old: &DntTable, new: &DntTable) -> Result<Self, DntError> {
+        let changes = diff(old, new)?;
+
+        let added = changes
+            .added
+            .iter()
+            .map(|row| {
+                Ok(DntRowPatch {
+                    id: row_id(row)?,
+                    cells: new
+                        .head
+                        .iter()
+                        .zip(&row.values)
+                        .skip(1)
+                        .map(|(column, value)| DntCellPatch {
+                            column: column.text.clone(),
+                            value: value.clone(),
+                        })
+                        .collect(),
+                })
+            })
+            .collect::<Result<Vec<DntRowPatch>, DntError>>()?;
+
+        let removed = changes
+            .removed
+            .iter()
+            .map(row_id)
+            .collect::<Result<Vec<i32>, DntError>>()?;
+
+        let changed = changes
+            .changed
+            .into_iter()
+            .map(|row| DntRowPatch {
+                id: row.id,
+                cells: row
+                    .cells
+                    .into_iter()
+                    .map(|cell| DntCellPatch {
+                        column: cell.column,
+                        value: cell.new,
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        Ok(Self {
+            schema: changes.schema,
+            removed,
+            added,
+            changed,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.schema.is_empty()
+            && self.removed.is_empty()
+            && self.added.is_empty()
+            && self.changed.is_empty()
+    }
+
+    pub fn apply(&self, table: &mut DntTable) -> Result<(), DntError> {
+        let mut patched = table.clone();
+
+        for change in &self.schema {
+            apply_schema_change(&mut patched, change)?;
+        }
+
+        let removed = self.removed.iter().collect::<HashSet<&i32>>();
+        patched
+            .body
+            .retain(|row| row_id(row).map(|id| !removed.contains(&id)).unwrap_or(true));
+
+        let mut ids = id_index(&patched)?;
+        for row in &self.added {
+            let index = match ids.get(&row.id) {
+                Some(&index) => index,
+                None => {
+                    let mut values = patched
+                        .head
+                        .iter()
+                        .map(|column| default_value(column.data_type))
+                        .collect::<Vec<DntValue>>();
+                    values[0] = DntValue::Int32(row.id);
+
+                    patched.body.push(DntRow { values });
+                    ids.insert(row.id, patched.body.len() - 1);
+                    patched.body.len() - 1
+                }
+            };
+
+            apply_cells(&mut patched, index, &row.cells)?;
+        }
+
+        for row in &self.changed {
+            if let Some(&index) = ids.get(&row.id) {
+                apply_cells(&mut patched, index, &row.cells)?;
+            }
+        }
+
+        patched.refresh_id_index()?;
+        *table = patched;
+        Ok(())
+    }
+
+    pub fn write<W: Write>(&self, writer: W) -> Result<(), DntError> {
+        let mut writer = BufWriter::new(writer);
+
+        let mut names = vec![];
+        let mut indexes = HashMap::new();
+        let mut name_index = |name: &str| -> Result<u16, DntError> {
+            if let Some(&index) = indexes.get(name) {
+                return Ok(index);
+            }
+
+            let index = u16::try_from(names.len())
+                .map_err(|_| DntError::InvalidValue(String::from("too many columns in patch")))?;
+            names.push(name.to_owned());
+            indexes.insert(name.to_owned(), index);
+            Ok(index)
+        };
+
+        let mut schema = vec![];
+        for change in &self.schema {
+            let column = match change {
+                DntSchemaChange::Added { column, .. } | DntSchemaChange::Removed(column) => {
+                    &column.text
+                }
+                DntSchemaChange::TypeChanged { column, .. } => column,
+            };
+            schema.push((change, name_index(column)?));
+        }
+
+        let mut rows = vec![];
+        for row in self.added.iter().chain(&self.changed) {
+            rows.push(
+                row.cells
+                    .iter()
+                    .map(|cell| name_index(&cell.column))
+                    .collect::<Result<Vec<u16>, DntError>>()?,
+            );
+        }
+
+        writer.write_all(MAGIC)?;
+        writer.write_u8(VERSION)?;
+
+        writer.write_u16::<LittleEndian>(short_count(names.len())?)?;
+        for name in &names {
+            write_bytes(&mut writer, name.as_bytes())?;
+        }
+
+        writer.write_u16::<LittleEndian>(short_count(schema.len())?)?;
+        for (change, name) in schema {
+            match change {
+                DntSchemaChange::Added { column, index } => {
+                    writer.write_u8(0)?;
+                    writer.write_u16::<LittleEndian>(name)?;
+                    write_column_type(&mut writer, column)?;
+                    writer.write_u16::<LittleEndian>(short_count(*index)?)?;
+                }
+                DntSchemaChange::Removed(column) => {
+                    writer.write_u8(1)?;
+                    writer.write_u16::<LittleEndian>(name)?;
+                    write_column_type(&mut writer, column)?;
+                }
+                DntSchemaChange::TypeChanged { old, new, .. } => {
+                    writer.write_u8(2)?;
+                    writer.write_u16::<LittleEndian>(name)?;
+                    write_data_type(&mut writer, *old)?;
+                    write_data_type(&mut writer, *new)?;
+                }
+            }
+        }
+
+        writer.write_u32::<LittleEndian>(count(self.removed.len())?)?;
+        for id in &self.removed {
+            writer.write_i32::<LittleEndian>(*id)?;
+        }
+
+        let (added_columns, changed_columns) = rows.split_at(self.added.len());
+        for (rows, columns) in [
+            (&self.added, added_columns),
+            (&self.changed, changed_columns),
+        ] {
+            writer.write_u32::<LittleEndian>(count(rows.len())?)?;
+            for (row, columns) in rows.iter().zip(columns) {
+                writer.write_i32::<LittleEndian>(row.id)?;
+                writer.write_u16::<LittleEndian>(short_count(row.cells.len())?)?;
+                for (cell, column) in row.cells.iter().zip(columns) {
+                    writer.write_u16::<LittleEndian>(*column)?;
+                    write_value(&mut writer, &cell.value)?;
+                }
+            }
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+
+    pub fn read<R: Read>(reader: R) -> Result<Self, DntError> {
+        Self::read_with_limits(reader, DntLimits::default())
+    }
+
+    /// Like `read`, failing with `DntError::LimitExceeded` if the patch
+    /// declares more columns than `max_columns`, more rows than `max_rows`
+    /// in any of its lists, or a string longer than `max_string_length`.
+    /// Even without limits, memory is only allocated as the data arrives,
+    /// so a short patch cannot claim gigabytes.
+    pub fn read_with_limits<R: Read>(reader: R, limits: DntLimits) -> Result<Self, DntError> {
+        let mut reader = BufReader::new(reader);
+        let reader = &mut reader;
+
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(DntError::InvalidHeader(String::from("not a DNT patch")));
+        }
+
+        let version = reader.read_u8()?;
+        if version != VERSION {
+            return Err(DntError::InvalidHeader(format!(
+                "unsupported patch version {}",
+                version
+            )));
+        }
+
+        let name_count = reader.read_u16::<LittleEndian>()?;
+        DntLimits::check(
+            "the column count",
+            u64::from(name_count),
+            limits.max_columns as u64,
+        )?;
+        let mut names = vec![];
+        for _ in 0..name_count {
+            names.push(read_string(reader, &limits)?);
+        }
+        let name = |index: u16| {
+            names
+                .get(index as usize)
+                .cloned()
+                .ok_or_else(|| DntError::InvalidValue(format!("invalid column index {}", index)))
+        };
+
+        let mut schema = vec![];
+        for _ in 0..reader.read_u16::<LittleEndian>()? {
+            let kind = reader.read_u8()?;
+            let column = name(reader.read_u16::<LittleEndian>()?)?;
+
+            schema.push(match kind {
+                0 => DntSchemaChange::Added {
+                    column: read_column_type(reader, column)?,
+                    index: usize::from(reader.read_u16::<LittleEndian>()?),
+                },
+                1 => DntSchemaChange::Removed(read_column_type(reader, column)?),
+                2 => DntSchemaChange::TypeChanged {
+                    column,
+                    old: read_data_type(reader)?,
+                    new: read_data_type(reader)?,
+                },
+                other => {
+                    return Err(DntError::InvalidValue(format!(
+                        "invalid schema change kind {}",
+                        other
+                    )))
+                }
+            });
+        }
+
+        let mut removed = vec![];
+        for _ in 0..read_row_count(reader, &limits)? {
+            removed.push(reader.read_i32::<LittleEndian>()?);
+        }
+
+        let mut rows = [vec![], vec![]];
+        for rows in &mut rows {
+            for _ in 0..read_row_count(reader, &limits)? {
+                let id = reader.read_i32::<LittleEndian>()?;
+                let mut cells = vec![];
+                for _ in 0..reader.read_u16::<LittleEndian>()? {
+                    cells.push(DntCellPatch {
+                        column: name(reader.read_u16::<LittleEndian>()?)?,
+                        value: read_value(reader, &limits)?,
+                    });
+                }
+
+                rows.push(DntRowPatch { id, cells });
+            }
+        }
+        let [added, changed] = rows;
+
+        Ok(Self {
+            schema,
+            removed,
+            added,
+            changed,
+        })
+    }
+}
+
+fn apply_schema_change(table: &mut DntTable, change: &DntSchemaChange) -> Result<(), DntError> {
+    match change {
+        DntSchemaChange::Added { column, index } => {
+            table.insert_column(
+                *index,
+                &column.text,
+                column.data_type,
+                default_value(column.data_type),
+            )?;
+            let index = column_index(table, &column.text)
+                .ok_or_else(|| DntError::ColumnNotFound(column.text.clone()))?;
+            table.head[index].raw_data_type = column.raw_data_type;
+        }
+        DntSchemaChange::Removed(column) => table.remove_column(&column.text)?,
+        DntSchemaChange::TypeChanged { column, new, .. } => {
+            let index = column_index(table, column)
+                .ok_or_else(|| DntError::ColumnNotFound(column.clone()))?;
+
+            table.head[index] = DntColumn::new(column, *new);
+            for row in &mut table.body {
+                if let Some(value) = row.values.get_mut(index) {
+                    *value = default_value(*new);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn apply_cells(table: &mut DntTable, row: usize, cells: &[DntCellPatch]) -> Result<(), DntError> {
+    for cell in cells {
+        let index = column_index(table, &cell.column)
+            .ok_or_else(|| DntError::ColumnNotFound(cell.column.clone()))?;
+
+        let expected = table.head[index].data_type;
+        if !cell.value.matches(expected) {
+            return Err(DntError::TypeMismatch {
+                column: cell.column.clone(),
+                expected,
+                found: cell.value.data_type(),
+            });
+        }
+
+        let values = &mut table.body[row].values;
+        if index >= values.len() {
+            return Err(DntError::InvalidValue(format!(
+                "row {} has no value for column {}",
+                row, cell.column
+            )));
+        }
+        values[index] = cell.value.clone();
+    }
+
+    Ok(())
+}
+
+fn count(length: usize) -> Result<u32, DntError> {
+    u32::try_from(length)
+        .map_err(|_| DntError::InvalidValue(format!("{} entries do not fit in a patch", length)))
+}
+
+fn short_count(length: usize) -> Result<u16, DntError> {
+    u16::try_from(length)
+        .map_err(|_| DntError::InvalidValue(format!("{} entries do not fit in a patch", length)))
+}
+
+fn write_value<W: Write>(writer: &mut W, value: &DntValue) -> Result<(), DntError> {
+    match value {
+        DntValue::String(value) => {
+            writer.write_u8(1)?;
+            write_bytes(writer, value.as_bytes())?;
+        }
+        DntValue::UInt32(value) => {
+            writer.write_u8(2)?;
+            writer.write_u32::<LittleEndian>(*value)?;
+        }
+        DntValue::Int32(value) => {
+            writer.write_u8(3)?;
+            writer.write_i32::<LittleEndian>(*value)?;
+        }
+        DntValue::Float32(value) => {
+            writer.write_u8(4)?;
+            writer.write_f32::<LittleEndian>(*value)?;
+        }
+        DntValue::Float64(value) => {
+            writer.write_u8(5)?;
+            writer.write_f64::<LittleEndian>(*value)?;
+        }
+        DntValue::Bytes(value) => {
+            writer.write_u8(0)?;
+            writer.write_u32::<LittleEndian>(count(value.len())?)?;
+            writer.write_all(value)?;
+        }
+        DntValue::Null => writer.write_u8(6)?,
+    }
+
+    Ok(())
+}
+
+fn read_value<R: Read>(reader: &mut R, limits: &DntLimits) -> Result<DntValue, DntError> {
+    Ok(match reader.read_u8()? {
+        1 => DntValue::String(read_string(reader, limits)?),
+        2 => DntValue::UInt32(reader.read_u32::<LittleEndian>()?),
+        3 => DntValue::Int32(reader.read_i32::<LittleEndian>()?),
+        4 => DntValue::Float32(reader.read_f32::<LittleEndian>()?),
+        5 => DntValue::Float64(reader.read_f64::<LittleEndian>()?),
+        6 => DntValue::Null,
+        0 => DntValue::Bytes(read_bytes(reader, limits)?),
+        other => {
+            return Err(DntError::InvalidValue(format!(
+                "invalid value tag {}",
+                other
+            )))
+        }
+    })
+}
+
+fn write_bytes<W: Write>(writer: &mut W, value: &[u8]) -> Result<(), DntError> {
+    writer.write_u32::<LittleEndian>(count(value.len())?)?;
+    writer.write_all(value)?;
+    Ok(())
+}
+
+/// Reads a length and that many bytes, growing the buffer as they arrive
+/// rather than trusting the length with an allocation.
+fn read_bytes<R: Read>(reader: &mut R, limits: &DntLimits) -> Result<Vec<u8>, DntError> {
+    let length = reader.read_u32::<LittleEndian>()?;
+    DntLimits::check(
+        "a string length",
+        u64::from(length),
+        limits.max_string_length as u64,
+    )?;
+
+    let mut value = vec![];
+    reader.take(u64::from(length)).read_to_end(&mut value)?;
+    if value.len() != length as usize {
+        return Err(DntError::UnexpectedEof);
+    }
+
+    Ok(value)
+}
+
+fn read_string<R: Read>(reader: &mut R, limits: &DntLimits) -> Result<String, DntError> {
+    String::from_utf8(read_bytes(reader, limits)?)
+        .map_err(|error| DntError::InvalidValue(format!("invalid UTF-8 in patch: {}", error)))
+}
+
+fn read_row_count<R: Read>(reader: &mut R, limits: &DntLimits) -> Result<u32, DntError> {
+    let rows = reader.read_u32::<LittleEndian>()?;
+    DntLimits::check("the row count", u64::from(rows), u64::from(limits.max_rows))?;
+    Ok(rows)
+}
+
+/// Types are written with codes of their own rather than the codes of the
+/// file, whose meaning depends on the client, followed by the code of the
+/// file for unknown types.
+fn write_data_type<W: Write>(writer: &mut W, data_type: DntDataType) -> Result<(), DntError> {
+    match data_type {
+        DntDataType::String => writer.write_u8(1)?,
+        DntDataType::UInt32 => writer.write_u8(2)?,
+        DntDataType::Int32 => writer.write_u8(3)?,
+        DntDataType::Float32 => writer.write_u8(4)?,
+        DntDataType::Float64 => writer.write_u8(5)?,
+        DntDataType::Unknown(code) => {
+            writer.write_u8(0)?;
+            writer.write_u8(code)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn read_data_type<R: Read>(reader: &mut R) -> Result<DntDataType, DntError> {
+    Ok(match reader.read_u8()? {
+        1 => DntDataType::String,
+        2 => DntDataType::UInt32,
+        3 => DntDataType::Int32,
+        4 => DntDataType::Float32,
+        5 => DntDataType::Float64,
+        0 => DntDataType::Unknown(reader.read_u8()?),
+        other => {
+            return Err(DntError::InvalidValue(format!(
+                "invalid type tag {}",
+                other
+            )))
+        }
+    })
+}
+
+/// The type of a column, then the code the file it came from used for it.
+fn write_column_type<W: Write>(writer: &mut W, column: &DntColumn) -> Result<(), DntError> {
+    write_data_type(writer, column.data_type)?;
+    writer.write_u8(column.raw_data_type)?;
+    Ok(())
+}
+
+fn read_column_type<R: Read>(reader: &mut R, text: String) -> Result<DntColumn, DntError> {
+    Ok(DntColumn {
+        text,
+        data_type: read_data_type(reader)?,
+        raw_data_type: reader.read_u8()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        DntCellPatch, DntDataType, DntError, DntLimits, DntPatch, DntRowPatch, DntTable,
+        DntTableBuilder, DntValue,
+    };
+
+    fn old() -> DntTable {
+        DntTableBuilder::new()
+            .column("_Name", DntDataType::String)
+            .column("_Level", DntDataType::UInt32)
+            .column("_Old", DntDataType::Int32)
+            .row((1, "Fire", 1u32, -1))
+            .row((2, "Ice", 2u32, -2))
+            .row((3, "Wind", 3u32, -3))
+            .build()
+            .unwrap()
+    }
+
+    fn new() -> DntTable {
+        DntTableBuilder::new()
+            .column("_Name", DntDataType::String)
+            .column("_Rate", DntDataType::Float32)
+            .column("_Level", DntDataType::UInt32)
+            .column("_Bonus", DntDataType::Float64)
+            .row((1, "Fire", 0.5f32, 1u32, 0.25))
+            .row((3, "Gale", 0.0f32, 30u32, 0.0))
+            .row((4, "Stone", 1.5f32, 4u32, 2.5))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn rebuilds_the_new_table_after_a_round_trip() {
+        let patch = DntPatch::new(&old(), &new()).unwrap();
+        let mut bytes = vec![];
+        patch.write(&mut bytes).unwrap();
+        let patch = DntPatch::read(&bytes[..]).unwrap();
+
+        let mut table = old();
+        patch.apply(&mut table).unwrap();
+        assert_eq!(table.head, new().head);
+        assert_eq!(table.body, new().body);
+        assert!(table == new());
+    }
+
+    #[test]
+    fn keeps_values_of_every_type() {
+        let patch = DntPatch {
+            changed: vec![DntRowPatch {
+                id: 1,
+                cells: [
+                    DntValue::from("text"),
+                    DntValue::UInt32(u32::MAX),
+                    DntValue::Int32(i32::MIN),
+                    DntValue::Float32(0.1),
+                    DntValue::Float64(0.1),
+                    DntValue::Bytes(vec![0, 255]),
+                    DntValue::Null,
+                ]
+                .into_iter()
+                .map(|value| DntCellPatch {
+                    column: String::from("_Value"),
+                    value,
+                })
+                .collect(),
+            }],
+            ..DntPatch::default()
+        };
+
+        let mut bytes = vec![];
+        patch.write(&mut bytes).unwrap();
+        let read = DntPatch::read(&bytes[..]).unwrap();
+        let values = |patch: &DntPatch| {
+            patch.changed[0]
+                .cells
+                .iter()
+                .map(|cell| cell.value.clone())
+                .collect::<Vec<DntValue>>()
+        };
+        assert_eq!(values(&read), values(&patch));
+    }
+
+    #[test]
+    fn rejects_lengths_the_data_does_not_have() {
+        let mut bytes = b"DNTP\x02".to_vec();
+        bytes.extend(1u16.to_le_bytes());
+        bytes.extend(u32::MAX.to_le_bytes());
+        bytes.extend(b"_Name");
+
+        assert!(matches!(
+            DntPatch::read(&bytes[..]),
+            Err(DntError::UnexpectedEof)
+        ));
+
+        let limits = DntLimits {
+            max_string_length: 64,
+            ..DntLimits::default()
+        };
+        assert!(matches!(
+            DntPatch::read_with_limits(&bytes[..], limits),
+            Err(DntError::LimitExceeded { .. })
+        ));
+    }
+}
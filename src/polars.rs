@@ -0,0 +1,251 @@
+use polars::prelude::{Column, DataFrame, DataType, PlSmallStr};
+
+use crate::{DntColumn, DntDataType, DntError, DntRow, DntTable, DntValue};
+
+impl TryFrom<&DntTable> for DataFrame {
+    type Error = DntError;
+
+    fn try_from(table: &DntTable) -> Result<Self, Self::Error> {
+        let columns = table
+            .head
+            .iter()
+            .enumerate()
+            .map(|(index, column)| {
+                let name = PlSmallStr::from(column.text.as_str());
+                let values = table.body.iter().map(|row| row.values.get(index));
+
+                let mismatch = || {
+                    DntError::InvalidValue(format!(
+                        "column {} contains values that are not {:?}",
+                        column.text, column.data_type
+                    ))
+                };
+
+                Ok(match column.data_type {
+                    DntDataType::String => Column::new(
+                        name,
+                        values
+                            .map(|value| match value {
+                                Some(DntValue::String(value)) => Some(Some(value.as_str())),
+                                Some(DntValue::Null) => Some(None),
+                                _ => None,
+                            })
+                            .collect::<Option<Vec<Option<&str>>>>()
+                            .ok_or_else(mismatch)?,
+                    ),
+                    DntDataType::UInt32 => Column::new(
+                        name,
+                        values
+                            .map(|value| match value {
+                                Some(DntValue::UInt32(value)) => Some(Some(*value)),
+                                Some(DntValue::Null) => Some(None),
+                                _ => None,
+                            })
+                            .collect::<Option<Vec<Option<u32>>>>()
+                            .ok_or_else(mismatch)?,
+                    ),
+                    DntDataType::Int32 => Column::new(
+                        name,
+                        values
+                            .map(|value| match value {
+                                Some(DntValue::Int32(value)) => Some(Some(*value)),
+                                Some(DntValue::Null) => Some(None),
+                                _ => None,
+                            })
+                            .collect::<Option<Vec<Option<i32>>>>()
+                            .ok_or_else(mismatch)?,
+                    ),
+                    DntDataType::Float32 => Column::new(
+                        name,
+                        values
+                            .map(|value| match value {
+                                Some(DntValue::Float32(value)) => Some(Some(*value)),
+                                Some(DntValue::Null) => Some(None),
+                                _ => None,
+                            })
+                            .collect::<Option<Vec<Option<f32>>>>()
+                            .ok_or_else(mismatch)?,
+                    ),
+                    DntDataType::Float64 => Column::new(
+                        name,
+                        values
+                            .map(|value| match value {
+                                Some(DntValue::Float64(value)) => Some(Some(*value)),
+                                Some(DntValue::Null) => Some(None),
+                                _ => None,
+                            })
+                            .collect::<Option<Vec<Option<f64>>>>()
+                            .ok_or_else(mismatch)?,
+                    ),
+                    DntDataType::Unknown(_) => Column::new(
+                        name,
+                        values
+                            .map(|value| match value {
+                                Some(DntValue::Bytes(value)) => Some(Some(value.as_slice())),
+                                Some(DntValue::Null) => Some(None),
+                                _ => None,
+                            })
+                            .collect::<Option<Vec<Option<&[u8]>>>>()
+                            .ok_or_else(mismatch)?,
+                    ),
+                })
+            })
+            .collect::<Result<Vec<Column>, DntError>>()?;
+
+        Ok(DataFrame::new(table.body.len(), columns)?)
+    }
+}
+
+impl TryFrom<&DataFrame> for DntTable {
+    type Error = DntError;
+
+    fn try_from(frame: &DataFrame) -> Result<Self, Self::Error> {
+        let mut head = vec![];
+        let mut body = (0..frame.height())
+            .map(|_| DntRow {
+                values: Vec::with_capacity(frame.width()),
+            })
+            .collect::<Vec<DntRow>>();
+
+        for (index, column) in frame.columns().iter().enumerate() {
+            if index == 0 && column.null_count() > 0 {
+                return Err(DntError::InvalidValue(format!(
+                    "id column {} contains nulls",
+                    column.name()
+                )));
+            }
+
+            let (data_type, values): (DntDataType, Vec<DntValue>) = match column.dtype() {
+                DataType::String => (
+                    DntDataType::String,
+                    column
+                        .str()?
+                        .iter()
+                        .map(|value| {
+                            value.map_or(DntValue::Null, |value| DntValue::String(value.to_owned()))
+                        })
+                        .collect(),
+                ),
+                DataType::UInt32 => (
+                    DntDataType::UInt32,
+                    column
+                        .u32()?
+                        .iter()
+                        .map(|value| value.map_or(DntValue::Null, DntValue::UInt32))
+                        .collect(),
+                ),
+                DataType::Int32 => (
+                    DntDataType::Int32,
+                    column
+                        .i32()?
+                        .iter()
+                        .map(|value| value.map_or(DntValue::Null, DntValue::Int32))
+                        .collect(),
+                ),
+                DataType::Float32 => (
+                    DntDataType::Float32,
+                    column
+                        .f32()?
+                        .iter()
+                        .map(|value| value.map_or(DntValue::Null, DntValue::Float32))
+                        .collect(),
+                ),
+                DataType::Float64 => (
+                    DntDataType::Float64,
+                    column
+                        .f64()?
+                        .iter()
+                        .map(|value| value.map_or(DntValue::Null, DntValue::Float64))
+                        .collect(),
+                ),
+                DataType::Binary => (
+                    DntDataType::Unknown(0),
+                    column
+                        .binary()?
+                        .iter()
+                        .map(|value| {
+                            value.map_or(DntValue::Null, |value| DntValue::Bytes(value.to_vec()))
+                        })
+                        .collect(),
+                ),
+                other => {
+                    return Err(DntError::InvalidHeader(format!(
+                        "unsupported Polars type for column {}: {}",
+                        column.name(),
+                        other
+                    )))
+                }
+            };
+
+            head.push(DntColumn::new(column.name(), data_type));
+            for (row, value) in body.iter_mut().zip(values) {
+                row.values.push(value);
+            }
+        }
+
+        if head.first().map(|column| column.data_type) != Some(DntDataType::Int32) {
+            return Err(DntError::InvalidHeader(String::from(
+                "the first Polars column must be an Int32 id",
+            )));
+        }
+
+        Ok(DntTable::from_parts(head, body))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use polars::prelude::{Column, DataFrame, DataType, PlSmallStr};
+
+    use crate::{DntDataType, DntError, DntTable, DntTableBuilder, DntValue};
+
+    fn table() -> DntTable {
+        DntTableBuilder::new()
+            .column("_Name", DntDataType::String)
+            .column("_Level", DntDataType::UInt32)
+            .column("_Job", DntDataType::Int32)
+            .column("_Rate", DntDataType::Float32)
+            .column("_Scale", DntDataType::Float64)
+            .row((1, "Sword", 10u32, -1, 0.5f32, 1.5f64))
+            .row((2, DntValue::Null, DntValue::Null, 2, DntValue::Null, 2.0f64))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn converts_both_ways_keeping_nulls() {
+        let table = table();
+        let frame = DataFrame::try_from(&table).unwrap();
+        assert_eq!(frame.shape(), (2, 6));
+        assert_eq!(frame.column("_Level").unwrap().dtype(), &DataType::UInt32);
+        assert_eq!(frame.column("_Name").unwrap().null_count(), 1);
+
+        assert!(DntTable::try_from(&frame).unwrap() == table);
+    }
+
+    #[test]
+    fn needs_an_int32_id() {
+        let frame = DataFrame::new(
+            1,
+            vec![
+                Column::new(PlSmallStr::from("id"), vec![1u32]),
+                Column::new(PlSmallStr::from("_Name"), vec!["Sword"]),
+            ],
+        )
+        .unwrap();
+        assert!(matches!(
+            DntTable::try_from(&frame),
+            Err(DntError::InvalidHeader(_))
+        ));
+
+        let frame = DataFrame::new(
+            1,
+            vec![Column::new(PlSmallStr::from("id"), vec![None::<i32>])],
+        )
+        .unwrap();
+        assert!(matches!(
+            DntTable::try_from(&frame),
+            Err(DntError::InvalidValue(_))
+        ));
+    }
+}
@@ -0,0 +1,197 @@
+use std::io::{self, Write};
+
+use crate::{DntDataType, DntError, DntTable, DntValue};
+
+#[derive(Debug, Clone, Copy)]
+pub struct PrintOptions {
+    pub max_rows: Option<usize>,
+    pub max_width: Option<usize>,
+}
+
+impl Default for PrintOptions {
+    fn default() -> Self {
+        Self {
+            max_rows: Some(50),
+            max_width: Some(40),
+        }
+    }
+}
+
+impl DntTable {
+    /// Prints the table to standard output with aligned columns.
+    pub fn print(&self, options: &PrintOptions) -> Result<(), DntError> {
+        self.write_pretty(io::stdout().lock(), options)
+    }
+
+    pub fn write_pretty<W: Write>(
+        &self,
+        mut writer: W,
+        options: &PrintOptions,
+    ) -> Result<(), DntError> {
+        let shown = options
+            .max_rows
+            .map_or(self.body.len(), |max_rows| max_rows.min(self.body.len()));
+
+        let names = self
+            .head
+            .iter()
+            .map(|column| truncate(column.text.clone(), options.max_width))
+            .collect::<Vec<String>>();
+
+        let rows = self.body[..shown]
+            .iter()
+            .map(|row| {
+                row.values
+                    .iter()
+                    .map(|value| truncate(format_value(value), options.max_width))
+                    .collect::<Vec<String>>()
+            })
+            .collect::<Vec<Vec<String>>>();
+
+        let widths = names
+            .iter()
+            .enumerate()
+            .map(|(index, name)| {
+                rows.iter()
+                    .filter_map(|row| row.get(index))
+                    .chain([name])
+                    .map(|cell| cell.chars().count())
+                    .max()
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<usize>>();
+
+        let numeric = self
+            .head
+            .iter()
+            .map(|column| {
+                matches!(
+                    column.data_type,
+                    DntDataType::UInt32
+                        | DntDataType::Int32
+                        | DntDataType::Float32
+                        | DntDataType::Float64
+                )
+            })
+            .collect::<Vec<bool>>();
+
+        write_line(&mut writer, &names, &widths, &vec![false; widths.len()])?;
+        let separator = widths
+            .iter()
+            .map(|width| "-".repeat(*width))
+            .collect::<Vec<String>>();
+        write_line(&mut writer, &separator, &widths, &numeric)?;
+
+        for row in &rows {
+            write_line(&mut writer, row, &widths, &numeric)?;
+        }
+
+        let hidden = self.body.len() - shown;
+        if hidden > 0 {
+            let noun = if hidden == 1 { "row" } else { "rows" };
+            writeln!(writer, "... {} more {}", hidden, noun)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn write_line<W: Write>(
+    writer: &mut W,
+    cells: &[String],
+    widths: &[usize],
+    right_aligned: &[bool],
+) -> Result<(), DntError> {
+    let line = cells
+        .iter()
+        .zip(widths)
+        .zip(right_aligned)
+        .map(|((cell, width), right_aligned)| {
+            if *right_aligned {
+                format!("{:>width$}", cell, width = width)
+            } else {
+                format!("{:<width$}", cell, width = width)
+            }
+        })
+        .collect::<Vec<String>>();
+
+    writeln!(writer, "{}", line.join(" | ").trim_end())?;
+    Ok(())
+}
+
+fn truncate(text: String, max_width: Option<usize>) -> String {
+    match max_width {
+        Some(max_width) if text.chars().count() > max_width => {
+            let mut text = text
+                .chars()
+                .take(max_width.saturating_sub(1))
+                .collect::<String>();
+            text.push('…');
+            text
+        }
+        _ => text,
+    }
+}
+
+fn format_value(value: &DntValue) -> String {
+    match value {
+        DntValue::String(value) => value.replace(['\n', '\r', '\t'], " "),
+        DntValue::Null => String::new(),
+        value => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PrintOptions;
+    use crate::{DntDataType, DntTable, DntTableBuilder};
+
+    fn table() -> DntTable {
+        DntTableBuilder::new()
+            .column("_Name", DntDataType::String)
+            .column("_Level", DntDataType::Int32)
+            .row((1, "Sword", 5))
+            .row((2, "Long\tShield", -10))
+            .row((3, "Bow", 1))
+            .build()
+            .unwrap()
+    }
+
+    fn pretty(options: &PrintOptions) -> String {
+        let mut output = vec![];
+        table().write_pretty(&mut output, options).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn aligns_the_columns_to_the_widest_cell() {
+        let options = PrintOptions {
+            max_rows: None,
+            max_width: None,
+        };
+        assert_eq!(
+            pretty(&options),
+            "id | _Name       | _Level\n\
+             -- | ----------- | ------\n \
+             1 | Sword       |      5\n \
+             2 | Long Shield |    -10\n \
+             3 | Bow         |      1\n"
+        );
+    }
+
+    #[test]
+    fn truncates_rows_and_cells() {
+        let options = PrintOptions {
+            max_rows: Some(2),
+            max_width: Some(6),
+        };
+        assert_eq!(
+            pretty(&options),
+            "id | _Name  | _Level\n\
+             -- | ------ | ------\n \
+             1 | Sword  |      5\n \
+             2 | Long … |    -10\n\
+             ... 1 more row\n"
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use std::io::{Read, Seek, Write};
+
+use encoding_rs::Encoding;
+
+use crate::{options::FOOTER, DntColumn, DntDataType, DntError, DntFileReader, DntFileWriter};
+
+/// The format differences of one client build, so that its files can be
+/// read and written by selecting the profile instead of setting each
+/// option. The default describes the standard format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatProfile {
+    /// The number of bytes between the row count and the first column
+    /// definition.
+    pub extra_header_length: usize,
+    /// Type codes that stand for another type than in the standard format,
+    /// with the type each stands for. Other codes keep their usual meaning.
+    pub type_codes: Vec<(u8, DntDataType)>,
+    /// The encoding of strings, or `None` for Latin-1.
+    pub encoding: Option<&'static Encoding>,
+    /// The text of the footer, or `None` for files without one.
+    pub footer: Option<String>,
+}
+
+impl Default for FormatProfile {
+    fn default() -> Self {
+        Self {
+            extra_header_length: 0,
+            type_codes: vec![],
+            encoding: None,
+            footer: Some(String::from(FOOTER)),
+        }
+    }
+}
+
+impl FormatProfile {
+    /// The type a code in the file stands for.
+    pub fn data_type(&self, code: u8) -> DntDataType {
+        data_type(&self.type_codes, code)
+    }
+
+    /// The code a type is written with.
+    pub fn type_code(&self, data_type: DntDataType) -> u8 {
+        type_code(&self.type_codes, data_type)
+    }
+}
+
+pub(crate) fn data_type(type_codes: &[(u8, DntDataType)], code: u8) -> DntDataType {
+    type_codes
+        .iter()
+        .find(|(mapped, _)| *mapped == code)
+        .map_or(DntDataType::from_u8(code), |(_, data_type)| *data_type)
+}
+
+pub(crate) fn type_code(type_codes: &[(u8, DntDataType)], data_type: DntDataType) -> u8 {
+    match data_type {
+        DntDataType::Unknown(code) => code,
+        data_type => type_codes
+            .iter()
+            .find(|(_, mapped)| *mapped == data_type)
+            .map_or(data_type.to_u8(), |(code, _)| *code),
+    }
+}
+
+/// The code written for a column: its own without a profile, so that
+/// tables are written back as read, or the one the profile gives its type.
+/// `Float64` columns need a profile that gives them a code, since standard
+/// files read every code as a 4-byte type.
+pub(crate) fn column_code(
+    type_codes: Option<&[(u8, DntDataType)]>,
+    column: &DntColumn,
+) -> Result<u8, DntError> {
+    let mapped = type_codes.unwrap_or_default();
+    if column.data_type == DntDataType::Float64
+        && !mapped
+            .iter()
+            .any(|(_, data_type)| *data_type == DntDataType::Float64)
+    {
+        return Err(DntError::InvalidHeader(format!(
+            "column {} is Float64, which needs a FormatProfile mapping a type code to it",
+            column.text
+        )));
+    }
+
+    Ok(match type_codes {
+        Some(type_codes) => type_code(type_codes, column.data_type),
+        None => column.raw_data_type,
+    })
+}
+
+impl<R: Read + Seek> DntFileReader<R> {
+    /// Reads files of the client the profile describes. Options set before
+    /// that the profile also covers are replaced.
+    pub fn with_profile(mut self, profile: &FormatProfile) -> Self {
+        self.extra_header_length = profile.extra_header_length;
+        self.type_codes = profile.type_codes.clone();
+        self.options.footer = profile.footer.clone();
+        match profile.encoding {
+            Some(encoding) => self.with_encoding(encoding),
+            None => self,
+        }
+    }
+}
+
+impl<W: Write> DntFileWriter<W> {
+    /// Writes files for the client the profile describes. Column types are
+    /// written with the profile's codes, and tables without extra header
+    /// bytes get as many zeros as the profile expects.
+    pub fn with_profile(mut self, profile: &FormatProfile) -> Self {
+        self.extra_header_length = Some(profile.extra_header_length);
+        self.type_codes = Some(profile.type_codes.clone());
+        self.options.footer = profile.footer.clone();
+        match profile.encoding {
+            Some(encoding) => self.with_encoding(encoding),
+            None => self,
+        }
+    }
+}
@@ -0,0 +1,18 @@
+/// Progress of a read or write, reported once the header is done, then every
+/// 1024 rows and after the last row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProgressEvent {
+    pub rows: u32,
+    pub total_rows: u32,
+    /// Bytes of table data read or written so far, counted before
+    /// compression.
+    pub bytes: u64,
+}
+
+pub(crate) type DntProgress = dyn FnMut(ProgressEvent) + Send;
+
+const PROGRESS_INTERVAL: u32 = 1024;
+
+pub(crate) fn is_due(rows: u32, total_rows: u32) -> bool {
+    rows.is_multiple_of(PROGRESS_INTERVAL) || rows == total_rows
+}
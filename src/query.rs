@@ -0,0 +1,191 @@
+use crate::{DntColumn, DntError, DntRow, DntTable, RowView};
+
+type DntQueryFilter<'a> = dyn FnMut(&RowView<'_>) -> bool + 'a;
+
+pub struct DntQuery<'a> {
+    table: &'a DntTable,
+    columns: Option<Vec<String>>,
+    filters: Vec<Box<DntQueryFilter<'a>>>,
+    limit: Option<usize>,
+}
+
+impl DntTable {
+    pub fn query(&self) -> DntQuery<'_> {
+        DntQuery {
+            table: self,
+            columns: None,
+            filters: vec![],
+            limit: None,
+        }
+    }
+}
+
+impl<'a> DntQuery<'a> {
+    pub fn select<I, S>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.columns = Some(columns.into_iter().map(Into::into).collect());
+        self
+    }
+
+    pub fn filter<F>(mut self, filter: F) -> Self
+    where
+        F: FnMut(&RowView<'_>) -> bool + 'a,
+    {
+        self.filters.push(Box::new(filter));
+        self
+    }
+
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn count(mut self) -> usize {
+        let table = self.table;
+        let limit = self.limit.unwrap_or(usize::MAX);
+
+        table
+            .body
+            .iter()
+            .filter(|row| self.accepts(row))
+            .take(limit)
+            .count()
+    }
+
+    pub fn collect(mut self) -> Result<DntTable, DntError> {
+        let table = self.table;
+        let selected = self.selected()?;
+        let limit = self.limit.unwrap_or(usize::MAX);
+
+        let head = table
+            .head
+            .iter()
+            .zip(&selected)
+            .filter(|(_, &selected)| selected)
+            .map(|(column, _)| column.clone())
+            .collect::<Vec<DntColumn>>();
+
+        let body = table
+            .body
+            .iter()
+            .filter(|row| self.accepts(row))
+            .take(limit)
+            .map(|row| DntRow {
+                values: row
+                    .values
+                    .iter()
+                    .zip(&selected)
+                    .filter(|(_, &selected)| selected)
+                    .map(|(value, _)| value.clone())
+                    .collect(),
+            })
+            .collect();
+
+        let mut result = DntTable::from_parts(head, body);
+        result.copy_file_bytes(table);
+
+        Ok(result)
+    }
+
+    fn selected(&self) -> Result<Vec<bool>, DntError> {
+        let head = &self.table.head;
+
+        match &self.columns {
+            Some(columns) => {
+                if let Some(missing) = columns
+                    .iter()
+                    .find(|name| !head.iter().any(|column| &column.text == *name))
+                {
+                    return Err(DntError::ColumnNotFound(missing.to_owned()));
+                }
+
+                Ok(head
+                    .iter()
+                    .enumerate()
+                    .map(|(index, column)| index == 0 || columns.contains(&column.text))
+                    .collect())
+            }
+            None => Ok(vec![true; head.len()]),
+        }
+    }
+
+    fn accepts(&mut self, row: &DntRow) -> bool {
+        let view = RowView::new(&self.table.head, row);
+        self.filters.iter_mut().all(|filter| filter(&view))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DntDataType, DntError, DntTable, DntTableBuilder, DntValue};
+
+    fn table() -> DntTable {
+        DntTableBuilder::new()
+            .column("_Name", DntDataType::String)
+            .column("_Level", DntDataType::UInt32)
+            .column("_Job", DntDataType::Int32)
+            .row((1, "Sword", 10u32, 1))
+            .row((2, "Shield", 20u32, 2))
+            .row((3, "Bow", 30u32, 2))
+            .row((4, "Staff", 40u32, 3))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn selects_filters_and_limits() {
+        let table = table();
+        let result = table
+            .query()
+            .select(["_Name"])
+            .filter(|row| row.get_u32("_Level").unwrap() > 10)
+            .filter(|row| row.get_i32("_Job") != Some(3))
+            .limit(5)
+            .collect()
+            .unwrap();
+
+        let expected = DntTableBuilder::new()
+            .column("_Name", DntDataType::String)
+            .row((2, "Shield"))
+            .row((3, "Bow"))
+            .build()
+            .unwrap();
+        assert!(result == expected);
+
+        let first = table.query().limit(1).collect().unwrap();
+        assert_eq!(first.body.len(), 1);
+        assert_eq!(first.body[0].values[1], DntValue::from("Sword"));
+    }
+
+    #[test]
+    fn counts_matching_rows() {
+        let table = table();
+        assert_eq!(table.query().count(), 4);
+        assert_eq!(
+            table
+                .query()
+                .filter(|row| row.get_i32("_Job") == Some(2))
+                .count(),
+            2
+        );
+        assert_eq!(
+            table
+                .query()
+                .filter(|row| row.get_u32("_Level").unwrap() > 10)
+                .limit(2)
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_columns() {
+        assert!(matches!(
+            table().query().select(["_Missing"]).collect(),
+            Err(DntError::ColumnNotFound(name)) if name == "_Missing"
+        ));
+    }
+}
@@ -0,0 +1,131 @@
+use crate::{DntColumn, DntColumnData, DntDataType, DntError, DntRow, DntValue};
+
+pub trait DntRecord: Sized {
+    fn columns() -> Vec<(&'static str, DntDataType)>;
+
+    fn from_values(values: &[&DntValue]) -> Result<Self, DntError>;
+
+    fn to_values(&self) -> Vec<DntValue>;
+}
+
+pub trait DntField: Sized {
+    const DATA_TYPE: DntDataType;
+
+    fn from_value(value: &DntValue) -> Result<Self, DntError>;
+
+    fn to_value(&self) -> DntValue;
+
+    /// The values of a columnar column, if they are stored as this type.
+    fn column_data(_data: &DntColumnData) -> Option<&[Self]> {
+        None
+    }
+}
+
+macro_rules! impl_dnt_field {
+    ($type:ty, $data_type:expr, $variant:ident) => {
+        impl DntField for $type {
+            const DATA_TYPE: DntDataType = $data_type;
+
+            fn from_value(value: &DntValue) -> Result<Self, DntError> {
+                match value {
+                    DntValue::$variant(value) => Ok(value.clone()),
+                    DntValue::Null => Ok(<$type>::default()),
+                    other => Err(DntError::InvalidValue(format!(
+                        "expected {:?}, got {:?}",
+                        $data_type, other
+                    ))),
+                }
+            }
+
+            fn to_value(&self) -> DntValue {
+                DntValue::$variant(self.clone())
+            }
+
+            fn column_data(data: &DntColumnData) -> Option<&[Self]> {
+                match data {
+                    DntColumnData::$variant(values) => Some(values),
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+impl_dnt_field!(String, DntDataType::String, String);
+impl_dnt_field!(u32, DntDataType::UInt32, UInt32);
+impl_dnt_field!(i32, DntDataType::Int32, Int32);
+impl_dnt_field!(f32, DntDataType::Float32, Float32);
+impl_dnt_field!(f64, DntDataType::Float64, Float64);
+
+/// `None` stands for `DntValue::Null`. Fields that are not optional read
+/// `Null` as the zero value of their type.
+impl<T: DntField> DntField for Option<T> {
+    const DATA_TYPE: DntDataType = T::DATA_TYPE;
+
+    fn from_value(value: &DntValue) -> Result<Self, DntError> {
+        match value {
+            DntValue::Null => Ok(None),
+            value => T::from_value(value).map(Some),
+        }
+    }
+
+    fn to_value(&self) -> DntValue {
+        match self {
+            Some(value) => value.to_value(),
+            None => DntValue::Null,
+        }
+    }
+}
+
+pub(crate) fn record_indexes<T: DntRecord>(head: &[DntColumn]) -> Result<Vec<usize>, DntError> {
+    T::columns()
+        .into_iter()
+        .map(|(name, data_type)| {
+            let index = head
+                .iter()
+                .position(|column| column.text == name)
+                .ok_or_else(|| DntError::ColumnNotFound(name.to_owned()))?;
+
+            let found = head[index].data_type;
+            if found != data_type {
+                return Err(DntError::TypeMismatch {
+                    column: name.to_owned(),
+                    expected: data_type,
+                    found,
+                });
+            }
+
+            Ok(index)
+        })
+        .collect()
+}
+
+pub(crate) fn record_from_row<T: DntRecord>(
+    row: &DntRow,
+    indexes: &[usize],
+) -> Result<T, DntError> {
+    let values = indexes
+        .iter()
+        .map(|&index| {
+            row.values.get(index).ok_or_else(|| {
+                DntError::InvalidValue(format!("row has no value for column {}", index))
+            })
+        })
+        .collect::<Result<Vec<&DntValue>, DntError>>()?;
+
+    T::from_values(&values)
+}
+
+pub(crate) fn record_head<T: DntRecord>() -> Result<Vec<DntColumn>, DntError> {
+    let head = T::columns()
+        .into_iter()
+        .map(|(name, data_type)| DntColumn::new(name, data_type))
+        .collect::<Vec<DntColumn>>();
+
+    match head.first() {
+        Some(column) if column.data_type == DntDataType::Int32 => Ok(head),
+        _ => Err(DntError::InvalidHeader(String::from(
+            "the first field of a record must be an i32 id",
+        ))),
+    }
+}
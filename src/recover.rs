@@ -0,0 +1,362 @@
+use std::io::{Read, Seek};
+
+use crate::{
+    borrowed::{DntRowsRef, DntValueRef},
+    diff::row_id,
+    DntError, DntFileReader, DntLayout, DntRow, DntTable, DntValue,
+};
+
+/// How many rows in a row have to decode after an offset for `Resync` to
+/// trust it.
+const RESYNC_ROWS: usize = 4;
+
+/// How many bytes `Resync` reads at a time.
+const RESYNC_WINDOW: usize = 64 * 1024;
+
+/// What `read_recover` does when a row cannot be decoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DntRecovery {
+    /// Keeps the rows before the damage and stops there.
+    #[default]
+    Stop,
+    /// Looks for the next offset where rows decode again, with increasing
+    /// ids, and carries on from there.
+    Resync,
+}
+
+/// A stretch of the body that could not be decoded. Offsets are in bytes
+/// from the start of the table data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DntDamage {
+    /// Index of the row that failed, counting the rows announced before it.
+    pub row: u32,
+    pub offset: u64,
+    pub message: String,
+    /// Where reading resumed, or `None` if it stopped.
+    pub resumed_at: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DntDamageReport {
+    pub rows_expected: u32,
+    pub rows_read: u32,
+    pub damage: Vec<DntDamage>,
+    /// Why the footer could not be read, if it couldn't. It isn't looked for
+    /// when reading stopped at damage.
+    pub footer: Option<String>,
+}
+
+impl DntDamageReport {
+    pub fn is_ok(&self) -> bool {
+        self.damage.is_empty() && self.footer.is_none()
+    }
+}
+
+impl<R: Read + Seek> DntFileReader<R> {
+    /// Reads the table like `read`, but keeps the rows that decode when some
+    /// don't. The rows are in `data()` as usual and the damage is described
+    /// in the report. Only a header that cannot be read and I/O errors are
+    /// returned as errors.
+    pub fn read_recover(&mut self, recovery: DntRecovery) -> Result<DntDamageReport, DntError> {
+        let mut header = self.read_header()?;
+        let magic = header.magic;
+        let extra_header = std::mem::take(&mut header.extra_header);
+        let rows_nb = header.rows_nb;
+        let layout = self.layout(header)?;
+
+        let mut report = DntDamageReport {
+            rows_expected: rows_nb,
+            ..DntDamageReport::default()
+        };
+        let mut body = vec![];
+        let mut last_id = None;
+
+        let mut row = 0;
+        while row < rows_nb {
+            // After skipping damaged bytes, the number of rows left is
+            // unknown, so the footer ends the body.
+            if !report.damage.is_empty() && !self.row_follows()? {
+                break;
+            }
+
+            let offset = self.position()?;
+            match self.read_row(&layout, row) {
+                Ok(values) => {
+                    last_id = row_id(&values).ok().or(last_id);
+                    if self.accepts(&layout, &values) {
+                        body.push(values);
+                    }
+                    report.rows_read += 1;
+                }
+                Err(DntError::Io(error)) => return Err(DntError::Io(error)),
+                Err(error) => {
+                    let resumed_at = match recovery {
+                        DntRecovery::Stop => None,
+                        DntRecovery::Resync => self.resync(&layout, offset, last_id)?,
+                    };
+                    report.damage.push(DntDamage {
+                        row,
+                        offset,
+                        message: error.to_string(),
+                        resumed_at,
+                    });
+
+                    match resumed_at {
+                        Some(position) => self.seek(position)?,
+                        None => {
+                            self.seek(offset)?;
+                            break;
+                        }
+                    }
+                }
+            }
+            row += 1;
+        }
+
+        // When reading stopped at damage, what follows isn't the footer.
+        let stopped = report
+            .damage
+            .last()
+            .is_some_and(|damage| damage.resumed_at.is_none());
+        let body_end = self.position()?;
+        let footer = if stopped { Ok(()) } else { self.check_footer() };
+        if let Err(error) = footer {
+            if let DntError::Io(error) = error {
+                return Err(DntError::Io(error));
+            }
+            report.footer = Some(error.to_string());
+            self.seek(body_end)?;
+        }
+        let footer_end = self.position()?;
+        self.read_trailing(footer_end)?;
+
+        self.data = DntTable::from_parts(layout.head, body);
+        self.data.magic = magic;
+        self.data.extra_header = extra_header;
+        self.data.trailing_bytes = std::mem::take(&mut self.trailing);
+
+        Ok(report)
+    }
+
+    /// Tries every offset after `from` until one starts a run of
+    /// `RESYNC_ROWS` rows with increasing ids above `last_id`, or a shorter
+    /// run that ends at the footer or the end of the data. The data is read
+    /// `RESYNC_WINDOW` bytes at a time and the offsets are tried in memory.
+    fn resync(
+        &mut self,
+        layout: &DntLayout,
+        from: u64,
+        last_id: Option<i32>,
+    ) -> Result<Option<u64>, DntError> {
+        let footer = match &self.options.footer {
+            Some(text) => Some(self.codec.encode(text)?.into_owned()),
+            None => None,
+        };
+
+        let mut start = from + 1;
+        let mut window_size = RESYNC_WINDOW;
+        loop {
+            self.seek(start)?;
+            let mut window = vec![];
+            self.input()
+                .take(window_size as u64)
+                .read_to_end(&mut window)?;
+            let window = Window {
+                complete: window.len() < window_size,
+                bytes: &window,
+                footer: footer.as_deref(),
+            };
+            let mut rows = DntRowsRef::new(
+                window.bytes,
+                0,
+                layout.clone(),
+                self.unknown_width,
+                self.limits,
+                0,
+            );
+
+            let mut cut = None;
+            for offset in 0..window.bytes.len() {
+                match self.plausible_run(&mut rows, &window, offset, last_id) {
+                    Run::Plausible => return Ok(Some(start + offset as u64)),
+                    Run::Implausible => {}
+                    Run::Cut => {
+                        cut = Some(offset);
+                        break;
+                    }
+                }
+            }
+
+            match cut {
+                // The rows after the start of the window don't fit in it.
+                Some(0) => window_size *= 2,
+                Some(offset) => start += offset as u64,
+                None if window.complete => return Ok(None),
+                None => start += window.bytes.len() as u64,
+            }
+        }
+    }
+
+    fn plausible_run<'a>(
+        &self,
+        rows: &mut DntRowsRef<'a>,
+        window: &Window<'a>,
+        offset: usize,
+        last_id: Option<i32>,
+    ) -> Run {
+        rows.restart(offset, RESYNC_ROWS as u32);
+        let mut last_id = last_id;
+        for _ in 0..RESYNC_ROWS {
+            match self.plausible_row(rows, last_id) {
+                Ok(Some(id)) => last_id = Some(id),
+                Ok(None) => return Run::Implausible,
+                Err(()) if window.complete => return Run::Implausible,
+                Err(()) => return Run::Cut,
+            }
+            match window.row_follows(DntRowsRef::position(rows)) {
+                Some(true) => {}
+                Some(false) => return Run::Plausible,
+                None => return Run::Cut,
+            }
+        }
+        Run::Plausible
+    }
+
+    /// The id of the next row if it decodes with an id above `last_id`, or
+    /// `Err` if it runs past the end of the window.
+    fn plausible_row(
+        &self,
+        rows: &mut DntRowsRef<'_>,
+        last_id: Option<i32>,
+    ) -> Result<Option<i32>, ()> {
+        let values = match rows.next() {
+            Some(Ok(values)) => values,
+            Some(Err(error)) if ends_early(&error) => return Err(()),
+            _ => return Ok(None),
+        };
+        // Most offsets fail here, before their strings are decoded.
+        match values.first() {
+            Some(DntValueRef::Int32(id)) if last_id.is_none_or(|last_id| *id > last_id) => {}
+            _ => return Ok(None),
+        }
+
+        let values = values
+            .iter()
+            .map(|value| value.to_value(&*self.codec))
+            .collect::<Result<Vec<DntValue>, DntError>>();
+        let mut row = match values {
+            Ok(values) => DntRow { values },
+            Err(_) => return Ok(None),
+        };
+        rows.layout().apply_nulls(&mut row);
+
+        Ok(row_id(&row)
+            .ok()
+            .filter(|&id| last_id.is_none_or(|last_id| id > last_id)))
+    }
+}
+
+/// The bytes `resync` tries offsets in.
+struct Window<'a> {
+    bytes: &'a [u8],
+    /// Whether the window reaches the end of the data.
+    complete: bool,
+    footer: Option<&'a [u8]>,
+}
+
+impl Window<'_> {
+    /// Whether a row follows `position`, as `DntFileReader::row_follows`
+    /// tells, or `None` if that can't be told without the bytes after the
+    /// window.
+    fn row_follows(&self, position: usize) -> Option<bool> {
+        let rest = &self.bytes[position..];
+        if rest.is_empty() {
+            return if self.complete { Some(false) } else { None };
+        }
+        let footer = match self.footer {
+            Some(footer) => footer,
+            None => return Some(true),
+        };
+
+        let length = footer.len() + 1;
+        if rest.len() < length && !self.complete {
+            return None;
+        }
+        let found =
+            rest.first() == Some(&(footer.len() as u8)) && rest.get(1..length) == Some(footer);
+        Some(!found)
+    }
+}
+
+enum Run {
+    Plausible,
+    Implausible,
+    /// The run goes on past the end of the window.
+    Cut,
+}
+
+fn ends_early(error: &DntError) -> bool {
+    match error {
+        DntError::Parse { error, .. } => ends_early(error),
+        DntError::UnexpectedEof => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use crate::{
+        diff::row_id, DntDataType, DntFileReader, DntRecovery, DntTable, DntTableBuilder, Utf8Codec,
+    };
+
+    fn table(rows: i32) -> DntTable {
+        let mut builder = DntTableBuilder::new().column("_Name", DntDataType::String);
+        for id in 1..=rows {
+            builder = builder.row((id, format!("Item {}", id)));
+        }
+        builder.build().unwrap()
+    }
+
+    fn ids(table: &DntTable) -> Vec<i32> {
+        table.body.iter().map(|row| row_id(row).unwrap()).collect()
+    }
+
+    #[test]
+    fn keeps_the_rows_before_a_truncation() {
+        let mut bytes = table(3).to_bytes().unwrap();
+        bytes.truncate(bytes.len() - 10);
+
+        for recovery in [DntRecovery::Stop, DntRecovery::Resync] {
+            let mut reader = DntFileReader::new(Cursor::new(&bytes));
+            let report = reader.read_recover(recovery).unwrap();
+            assert_eq!(ids(reader.data()), [1, 2]);
+            assert_eq!(report.rows_read, 2);
+            assert_eq!(report.damage.len(), 1);
+            assert_eq!(report.damage[0].row, 2);
+            assert_eq!(report.damage[0].resumed_at, None);
+        }
+    }
+
+    #[test]
+    fn resyncs_past_damage_longer_than_a_window() {
+        let mut bytes = table(10).to_bytes().unwrap();
+        // Only the footer follows the rows.
+        let row_end = |rows| table(rows).to_bytes().unwrap().len() - b"\x05THEND".len();
+        let third_row = row_end(2);
+        let garbage = 3 * super::RESYNC_WINDOW;
+        bytes.splice(third_row..row_end(3), vec![0xFF; garbage]);
+
+        let mut reader = DntFileReader::new(Cursor::new(&bytes)).with_codec(Utf8Codec);
+        let report = reader.read_recover(DntRecovery::Resync).unwrap();
+        assert_eq!(ids(reader.data()), [1, 2, 4, 5, 6, 7, 8, 9, 10]);
+        assert_eq!(report.damage.len(), 1);
+        assert_eq!(report.damage[0].offset, third_row as u64);
+        assert_eq!(
+            report.damage[0].resumed_at,
+            Some((third_row + garbage) as u64)
+        );
+        assert_eq!(report.footer, None);
+    }
+}
@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+
+use crate::{diff::id_index, DntDataType, DntError, DntTable, DntValue, RowView};
+
+/// A column of one table holding ids of the rows of another, such as
+/// `_SkillLevelID` in a skill table pointing into the skill level table.
+/// Id 0 is taken to mean no row.
+pub struct DntRelation<'a> {
+    source: &'a DntTable,
+    target: &'a DntTable,
+    column: usize,
+    ids: HashMap<i32, usize>,
+}
+
+impl<'a> DntRelation<'a> {
+    pub fn source(&self) -> &'a DntTable {
+        self.source
+    }
+
+    pub fn target(&self) -> &'a DntTable {
+        self.target
+    }
+
+    /// The id a row of the source table refers to, if any.
+    pub fn key(&self, row: usize) -> Option<i32> {
+        match self.source.body.get(row)?.values.get(self.column)? {
+            DntValue::Int32(0) | DntValue::UInt32(0) => None,
+            DntValue::Int32(id) => Some(*id),
+            DntValue::UInt32(id) => i32::try_from(*id).ok(),
+            _ => None,
+        }
+    }
+
+    /// The row of the target table a row of the source table refers to.
+    pub fn get(&self, row: usize) -> Option<RowView<'a>> {
+        let index = *self.ids.get(&self.key(row)?)?;
+        self.target.row_view(index)
+    }
+
+    /// Every row of the source table with the row it refers to.
+    pub fn iter(&self) -> impl Iterator<Item = (RowView<'a>, Option<RowView<'a>>)> + '_ {
+        self.source
+            .body
+            .iter()
+            .enumerate()
+            .map(|(index, row)| (RowView::new(&self.source.head, row), self.get(index)))
+    }
+
+    /// Indexes of the source rows that refer to an id the target table
+    /// doesn't have.
+    pub fn dangling(&self) -> Vec<usize> {
+        (0..self.source.body.len())
+            .filter(|&row| self.key(row).is_some_and(|id| !self.ids.contains_key(&id)))
+            .collect()
+    }
+}
+
+impl DntTable {
+    /// Relates `column` of this table to the ids of `target`.
+    pub fn relation<'a>(
+        &'a self,
+        column: &str,
+        target: &'a DntTable,
+    ) -> Result<DntRelation<'a>, DntError> {
+        let index = self.column_position(column)?;
+
+        let data_type = self.head[index].data_type();
+        if !matches!(data_type, DntDataType::Int32 | DntDataType::UInt32) {
+            return Err(DntError::TypeMismatch {
+                column: column.to_owned(),
+                expected: DntDataType::Int32,
+                found: data_type,
+            });
+        }
+
+        Ok(DntRelation {
+            source: self,
+            target,
+            column: index,
+            ids: id_index(target)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DntDataType, DntError, DntTable, DntTableBuilder, DntValue};
+
+    fn levels() -> DntTable {
+        DntTableBuilder::new()
+            .column("_Level", DntDataType::UInt32)
+            .row((10, 1u32))
+            .row((20, 2u32))
+            .build()
+            .unwrap()
+    }
+
+    fn skills() -> DntTable {
+        DntTableBuilder::new()
+            .column("_SkillLevelID", DntDataType::UInt32)
+            .column("_Name", DntDataType::String)
+            .row((1, 20u32, "Fire"))
+            .row((2, 0u32, "Ice"))
+            .row((3, 30u32, "Wind"))
+            .row((4, 10u32, "Earth"))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn resolves_unsigned_ids_against_the_target() {
+        let (skills, levels) = (skills(), levels());
+        let relation = skills.relation("_SkillLevelID", &levels).unwrap();
+
+        assert_eq!(relation.key(0), Some(20));
+        assert_eq!(
+            relation.get(0).unwrap().get("_Level"),
+            Some(&DntValue::UInt32(2))
+        );
+        assert_eq!(relation.key(1), None);
+        assert!(relation.get(1).is_none());
+        assert_eq!(relation.get(3).unwrap().get_i32("id"), Some(10));
+
+        let resolved = relation
+            .iter()
+            .map(|(_, target)| target.map(|row| row.get_i32("id").unwrap()))
+            .collect::<Vec<Option<i32>>>();
+        assert_eq!(resolved, [Some(20), None, None, Some(10)]);
+    }
+
+    #[test]
+    fn finds_missing_targets() {
+        let (skills, levels) = (skills(), levels());
+        let relation = skills.relation("_SkillLevelID", &levels).unwrap();
+        assert_eq!(relation.key(2), Some(30));
+        assert!(relation.get(2).is_none());
+        assert_eq!(relation.dangling(), [2]);
+    }
+
+    #[test]
+    fn needs_an_integer_column() {
+        let (skills, levels) = (skills(), levels());
+        assert!(matches!(
+            skills.relation("_Name", &levels),
+            Err(DntError::TypeMismatch { .. })
+        ));
+    }
+}
@@ -0,0 +1,157 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{DntError, DntTable};
+
+/// Which columns an export writes, in which order and under which names, so
+/// that the files don't carry the internal names such as `_SkillName`. The
+/// default writes every column under its own name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ColumnMapping {
+    /// Exported names, by the name of the column in the table.
+    pub renames: HashMap<String, String>,
+    /// The columns to write, in this order, or `None` for every column in
+    /// the order of the table.
+    pub order: Option<Vec<String>>,
+}
+
+impl ColumnMapping {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn rename(mut self, column: impl Into<String>, name: impl Into<String>) -> Self {
+        self.renames.insert(column.into(), name.into());
+        self
+    }
+
+    pub fn order<I, S>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.order = Some(columns.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// The index in the table and the exported name of each column to
+    /// write. Naming a column the table doesn't have is an error, so a typo
+    /// doesn't go unnoticed, as are renaming the id column, which imports
+    /// look for by name, and writing two columns under the same name.
+    pub(crate) fn resolve<'a>(
+        &'a self,
+        table: &'a DntTable,
+    ) -> Result<Vec<(usize, &'a str)>, DntError> {
+        if let Some(column) = self
+            .renames
+            .keys()
+            .find(|column| table.column_position(column).is_err())
+        {
+            return Err(DntError::ColumnNotFound(column.clone()));
+        }
+        if let Some(id) = table.head.first() {
+            if self
+                .renames
+                .get(&id.text)
+                .is_some_and(|name| *name != id.text)
+            {
+                return Err(DntError::InvalidHeader(format!(
+                    "column {} can't be renamed",
+                    id.text
+                )));
+            }
+        }
+
+        let indices = match &self.order {
+            Some(order) => order
+                .iter()
+                .map(|column| table.column_position(column))
+                .collect::<Result<Vec<usize>, DntError>>()?,
+            None => (0..table.head.len()).collect(),
+        };
+
+        let columns = indices
+            .into_iter()
+            .map(|index| {
+                let column = &table.head[index].text;
+                let name = self.renames.get(column).unwrap_or(column);
+                (index, name.as_str())
+            })
+            .collect::<Vec<(usize, &str)>>();
+
+        let mut names = HashSet::new();
+        if let Some((_, name)) = columns.iter().find(|(_, name)| !names.insert(*name)) {
+            return Err(DntError::InvalidHeader(format!(
+                "column {} is written twice",
+                name
+            )));
+        }
+
+        Ok(columns)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ColumnMapping;
+    use crate::{DntDataType, DntError, DntTable, DntTableBuilder};
+
+    fn table() -> DntTable {
+        DntTableBuilder::new()
+            .column("_SkillName", DntDataType::String)
+            .column("_NeedJob", DntDataType::UInt32)
+            .row((1, "Slash", 1u32))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn renames_and_orders_columns() {
+        let table = table();
+        let mapping = ColumnMapping::new()
+            .rename("_SkillName", "skill_name")
+            .order(["_NeedJob", "id", "_SkillName"]);
+        assert_eq!(
+            mapping.resolve(&table).unwrap(),
+            [(2, "_NeedJob"), (0, "id"), (1, "skill_name")]
+        );
+
+        assert_eq!(
+            ColumnMapping::new().resolve(&table).unwrap(),
+            [(0, "id"), (1, "_SkillName"), (2, "_NeedJob")]
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_and_duplicate_names() {
+        let table = table();
+        assert!(matches!(
+            ColumnMapping::new().rename("_Skill", "skill").resolve(&table),
+            Err(DntError::ColumnNotFound(column)) if column == "_Skill"
+        ));
+        assert!(matches!(
+            ColumnMapping::new()
+                .rename("_SkillName", "_NeedJob")
+                .resolve(&table),
+            Err(DntError::InvalidHeader(_))
+        ));
+        assert!(matches!(
+            ColumnMapping::new().order(["id", "id"]).resolve(&table),
+            Err(DntError::InvalidHeader(_))
+        ));
+    }
+
+    #[test]
+    fn keeps_the_id_column_name() {
+        let table = table();
+        assert!(matches!(
+            ColumnMapping::new()
+                .rename("id", "skill_id")
+                .resolve(&table),
+            Err(DntError::InvalidHeader(_))
+        ));
+        assert!(ColumnMapping::new()
+            .rename("id", "id")
+            .resolve(&table)
+            .is_ok());
+    }
+}
@@ -0,0 +1,98 @@
+use std::io::{Read, Seek};
+
+use crate::{DntColumn, DntError, DntFileReader, DntLayout, DntRow};
+
+pub struct DntRows<'a, R> {
+    reader: &'a mut DntFileReader<R>,
+    layout: DntLayout,
+    remaining: u32,
+    total: u32,
+    footer_pending: bool,
+}
+
+impl<'a, R: Read + Seek> DntRows<'a, R> {
+    pub(crate) fn new(reader: &'a mut DntFileReader<R>, layout: DntLayout, rows_nb: u32) -> Self {
+        let footer_pending = rows_nb > 0 || reader.options.lenient_row_count;
+        Self {
+            reader,
+            layout,
+            remaining: rows_nb,
+            total: rows_nb,
+            footer_pending,
+        }
+    }
+
+    pub fn head(&self) -> &[DntColumn] {
+        self.layout.head()
+    }
+}
+
+impl<R: Read + Seek> Iterator for DntRows<'_, R> {
+    type Item = Result<DntRow, DntError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.footer_pending {
+            let read = self.total - self.remaining;
+            match self.reader.more_rows(read, self.total) {
+                Ok(true) => {}
+                Ok(false) => {
+                    self.remaining = 0;
+                    break;
+                }
+                Err(error) => {
+                    self.remaining = 0;
+                    self.footer_pending = false;
+                    return Some(Err(error));
+                }
+            }
+
+            match self.reader.read_row(&self.layout, read) {
+                Ok(row) => {
+                    // With a lenient row count, rows past the announced
+                    // count raise the total instead.
+                    if self.remaining == 0 {
+                        self.total += 1;
+                    } else {
+                        self.remaining -= 1;
+                    }
+                    let accepted = self.reader.accepts(&self.layout, &row);
+                    if let Err(error) = self
+                        .reader
+                        .report_progress(self.total - self.remaining, self.total)
+                    {
+                        self.remaining = 0;
+                        self.footer_pending = false;
+                        return Some(Err(error));
+                    }
+                    if accepted {
+                        return Some(Ok(row));
+                    }
+                }
+                Err(error) => {
+                    self.remaining = 0;
+                    self.footer_pending = false;
+                    return Some(Err(error));
+                }
+            }
+        }
+
+        if self.footer_pending {
+            self.footer_pending = false;
+            if let Err(error) = self.reader.read_footer() {
+                return Some(Err(error));
+            }
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.reader.options.lenient_row_count {
+            return (0, None);
+        }
+        (
+            0,
+            Some(self.remaining as usize + self.footer_pending as usize),
+        )
+    }
+}
@@ -0,0 +1,154 @@
+use crate::{DntColumn, DntDataType, DntError, DntTable};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DntSchema {
+    columns: Vec<DntColumn>,
+}
+
+impl DntSchema {
+    pub fn new() -> Self {
+        Self {
+            columns: vec![DntColumn::new("id", DntDataType::Int32)],
+        }
+    }
+
+    pub fn with_column(mut self, name: &str, data_type: DntDataType) -> Self {
+        self.columns.push(DntColumn::new(name, data_type));
+        self
+    }
+
+    pub fn from_table(table: &DntTable) -> Self {
+        Self {
+            columns: table.head.clone(),
+        }
+    }
+
+    pub fn columns(&self) -> &[DntColumn] {
+        &self.columns
+    }
+
+    pub fn matches(&self, table: &DntTable) -> bool {
+        self.validate(table).is_ok()
+    }
+
+    pub fn validate(&self, table: &DntTable) -> Result<(), DntError> {
+        check_head(&self.columns, &table.head)
+    }
+}
+
+impl Default for DntSchema {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DntTable {
+    pub fn new(schema: DntSchema) -> Self {
+        DntTable::from_parts(schema.columns, vec![])
+    }
+}
+
+pub(crate) fn check_head(expected: &[DntColumn], found: &[DntColumn]) -> Result<(), DntError> {
+    if expected.len() != found.len() {
+        return Err(DntError::InvalidHeader(format!(
+            "expected {} columns, got {}",
+            expected.len(),
+            found.len()
+        )));
+    }
+
+    for (column, other_column) in expected.iter().zip(found) {
+        if column.text != other_column.text {
+            return Err(DntError::InvalidHeader(format!(
+                "expected column {}, got {}",
+                column.text, other_column.text
+            )));
+        }
+
+        if column.data_type != other_column.data_type {
+            return Err(DntError::TypeMismatch {
+                column: column.text.clone(),
+                expected: column.data_type,
+                found: other_column.data_type,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DntSchema;
+    use crate::{DntDataType, DntError, DntTable, DntTableBuilder};
+
+    fn schema() -> DntSchema {
+        DntSchema::new()
+            .with_column("_Name", DntDataType::String)
+            .with_column("_Level", DntDataType::UInt32)
+    }
+
+    fn table(columns: &[(&str, DntDataType)]) -> DntTable {
+        columns
+            .iter()
+            .fold(DntTableBuilder::new(), |builder, (name, data_type)| {
+                builder.column(name, *data_type)
+            })
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn matches_its_own_tables() {
+        let empty = DntTable::new(schema());
+        assert!(empty.body.is_empty());
+        assert!(schema().matches(&empty));
+        assert!(DntSchema::from_table(&empty).columns() == schema().columns());
+    }
+
+    #[test]
+    fn finds_added_and_removed_columns() {
+        let added = table(&[
+            ("_Name", DntDataType::String),
+            ("_Level", DntDataType::UInt32),
+            ("_Price", DntDataType::UInt32),
+        ]);
+        assert!(matches!(
+            schema().validate(&added),
+            Err(DntError::InvalidHeader(_))
+        ));
+
+        let removed = table(&[("_Name", DntDataType::String)]);
+        assert!(matches!(
+            schema().validate(&removed),
+            Err(DntError::InvalidHeader(_))
+        ));
+    }
+
+    #[test]
+    fn finds_retyped_and_reordered_columns() {
+        let retyped = table(&[
+            ("_Name", DntDataType::String),
+            ("_Level", DntDataType::Int32),
+        ]);
+        assert!(matches!(
+            schema().validate(&retyped),
+            Err(DntError::TypeMismatch {
+                column,
+                expected: DntDataType::UInt32,
+                found: DntDataType::Int32,
+            }) if column == "_Level"
+        ));
+
+        let reordered = table(&[
+            ("_Level", DntDataType::UInt32),
+            ("_Name", DntDataType::String),
+        ]);
+        assert!(matches!(
+            schema().validate(&reordered),
+            Err(DntError::InvalidHeader(_))
+        ));
+        assert!(!schema().matches(&reordered));
+    }
+}
@@ -0,0 +1,147 @@
+use std::cmp::Ordering;
+
+use crate::{DntError, DntTable, DntValue};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+impl DntTable {
+    pub fn sort_by_column(&mut self, column: &str, order: SortOrder) -> Result<(), DntError> {
+        self.sort_by(&[(column, order)])
+    }
+
+    /// Sorts by several columns: rows are ordered by the first key, rows
+    /// equal on it by the second, and so on. Rows equal on every key keep
+    /// their relative order.
+    pub fn sort_by(&mut self, keys: &[(&str, SortOrder)]) -> Result<(), DntError> {
+        let keys = keys
+            .iter()
+            .map(|&(column, order)| Ok((self.column_position(column)?, order)))
+            .collect::<Result<Vec<(usize, SortOrder)>, DntError>>()?;
+
+        self.body.sort_by(|left, right| {
+            keys.iter()
+                .map(|&(index, order)| {
+                    let ordering = match (left.values.get(index), right.values.get(index)) {
+                        (Some(left), Some(right)) => compare_values(left, right),
+                        (left, right) => left.is_some().cmp(&right.is_some()),
+                    };
+
+                    match order {
+                        SortOrder::Ascending => ordering,
+                        SortOrder::Descending => ordering.reverse(),
+                    }
+                })
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or(Ordering::Equal)
+        });
+
+        self.refresh_id_index()?;
+        Ok(())
+    }
+}
+
+pub(crate) fn compare_values(left: &DntValue, right: &DntValue) -> Ordering {
+    match (left, right) {
+        (DntValue::String(left), DntValue::String(right)) => left.cmp(right),
+        (DntValue::Bytes(left), DntValue::Bytes(right)) => left.cmp(right),
+        (DntValue::Null, DntValue::Null) => Ordering::Equal,
+        (DntValue::Null, _) => Ordering::Less,
+        (_, DntValue::Null) => Ordering::Greater,
+        _ => match (number(left), number(right)) {
+            (Some(left_number), Some(right_number)) => left_number
+                .total_cmp(&right_number)
+                .then_with(|| rank(left).cmp(&rank(right))),
+            _ => rank(left).cmp(&rank(right)),
+        },
+    }
+}
+
+fn number(value: &DntValue) -> Option<f64> {
+    match value {
+        DntValue::UInt32(value) => Some(*value as f64),
+        DntValue::Int32(value) => Some(*value as f64),
+        DntValue::Float32(value) => Some(*value as f64),
+        DntValue::Float64(value) => Some(*value),
+        DntValue::String(_) | DntValue::Bytes(_) | DntValue::Null => None,
+    }
+}
+
+fn rank(value: &DntValue) -> u8 {
+    match value {
+        DntValue::UInt32(_) => 0,
+        DntValue::Int32(_) => 1,
+        DntValue::Float32(_) => 2,
+        DntValue::Float64(_) => 3,
+        DntValue::String(_) => 4,
+        DntValue::Bytes(_) => 5,
+        DntValue::Null => 6,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DntDataType, DntTable, DntTableBuilder, DntValue, SortOrder};
+
+    fn ids(table: &DntTable) -> Vec<i32> {
+        table
+            .body
+            .iter()
+            .map(|row| match row.values[0] {
+                DntValue::Int32(id) => id,
+                _ => unreachable!(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sorts_by_several_keys_and_keeps_ties_in_order() {
+        let mut table = DntTableBuilder::new()
+            .column("_Job", DntDataType::Int32)
+            .column("_Level", DntDataType::UInt32)
+            .row((1, 2, 10u32))
+            .row((2, 1, 20u32))
+            .row((3, 2, 30u32))
+            .row((4, 1, 20u32))
+            .row((5, 2, 10u32))
+            .build()
+            .unwrap();
+        table.build_id_index().unwrap();
+
+        table
+            .sort_by(&[
+                ("_Job", SortOrder::Ascending),
+                ("_Level", SortOrder::Descending),
+            ])
+            .unwrap();
+        assert_eq!(ids(&table), [2, 4, 3, 1, 5]);
+        assert_eq!(table.get_by_id(3), Some(&table.body[2]));
+
+        table.sort_by_column("id", SortOrder::Ascending).unwrap();
+        assert_eq!(ids(&table), [1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn puts_nulls_first_and_nan_last() {
+        let mut table = DntTableBuilder::new()
+            .column("_Rate", DntDataType::Float32)
+            .row((1, f32::NAN))
+            .row((2, 0.5f32))
+            .row((3, DntValue::Null))
+            .row((4, f32::INFINITY))
+            .row((5, -1.0f32))
+            .build()
+            .unwrap();
+
+        table.sort_by_column("_Rate", SortOrder::Ascending).unwrap();
+        assert_eq!(ids(&table), [3, 5, 2, 4, 1]);
+
+        table
+            .sort_by_column("_Rate", SortOrder::Descending)
+            .unwrap();
+        assert_eq!(ids(&table), [1, 4, 2, 5, 3]);
+    }
+}
@@ -0,0 +1,191 @@
+use std::io::Write;
+
+use crate::{bytes::to_hex, ColumnMapping, DntDataType, DntError, DntTable, DntValue};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqlDialect {
+    MySql,
+    Postgres,
+    Sqlite,
+}
+
+#[derive(Debug, Clone)]
+pub struct SqlOptions {
+    pub dialect: SqlDialect,
+    pub batch_size: usize,
+    /// The columns written, their order and their names.
+    pub columns: ColumnMapping,
+}
+
+impl Default for SqlOptions {
+    fn default() -> Self {
+        Self {
+            dialect: SqlDialect::Postgres,
+            batch_size: 500,
+            columns: ColumnMapping::default(),
+        }
+    }
+}
+
+impl DntTable {
+    pub fn to_sql(&self, name: &str, options: &SqlOptions) -> Result<String, DntError> {
+        let mut bytes = vec![];
+        self.write_sql(&mut bytes, name, options)?;
+
+        String::from_utf8(bytes).map_err(|_| DntError::InvalidValue(String::from("invalid UTF-8")))
+    }
+
+    pub fn write_sql<W: Write>(
+        &self,
+        mut writer: W,
+        name: &str,
+        options: &SqlOptions,
+    ) -> Result<(), DntError> {
+        let dialect = options.dialect;
+        let table = quote_identifier(name, dialect);
+        let mapping = options.columns.resolve(self)?;
+
+        let columns = mapping
+            .iter()
+            .map(|(index, name)| {
+                format!(
+                    "    {} {}",
+                    quote_identifier(name, dialect),
+                    sql_type(self.head[*index].data_type, dialect)
+                )
+            })
+            .collect::<Vec<String>>();
+
+        writeln!(
+            writer,
+            "CREATE TABLE {} (\n{}\n);",
+            table,
+            columns.join(",\n")
+        )?;
+
+        for batch in self.body.chunks(options.batch_size.max(1)) {
+            let rows = batch
+                .iter()
+                .map(|row| {
+                    let values = mapping
+                        .iter()
+                        .map(|(index, _)| {
+                            sql_literal(row.values.get(*index).unwrap_or(&DntValue::Null), dialect)
+                        })
+                        .collect::<Vec<String>>();
+                    format!("    ({})", values.join(", "))
+                })
+                .collect::<Vec<String>>();
+
+            writeln!(
+                writer,
+                "INSERT INTO {} VALUES\n{};",
+                table,
+                rows.join(",\n")
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+pub(crate) fn sql_type(data_type: DntDataType, dialect: SqlDialect) -> &'static str {
+    match (dialect, data_type) {
+        (_, DntDataType::String) => "TEXT",
+        (SqlDialect::MySql, DntDataType::UInt32) => "INT UNSIGNED",
+        (SqlDialect::Postgres, DntDataType::UInt32) => "BIGINT",
+        (SqlDialect::MySql, DntDataType::Int32) => "INT",
+        (_, DntDataType::UInt32 | DntDataType::Int32) => "INTEGER",
+        (SqlDialect::MySql, DntDataType::Float32) => "FLOAT",
+        (SqlDialect::MySql, DntDataType::Float64) => "DOUBLE",
+        (SqlDialect::Postgres, DntDataType::Float32) => "REAL",
+        (SqlDialect::Postgres, DntDataType::Float64) => "DOUBLE PRECISION",
+        (SqlDialect::Sqlite, DntDataType::Float32 | DntDataType::Float64) => "REAL",
+        (SqlDialect::Postgres, DntDataType::Unknown(_)) => "BYTEA",
+        (_, DntDataType::Unknown(_)) => "BLOB",
+    }
+}
+
+fn sql_literal(value: &DntValue, dialect: SqlDialect) -> String {
+    match value {
+        DntValue::String(value) => quote_string(value, dialect),
+        DntValue::UInt32(value) => value.to_string(),
+        DntValue::Int32(value) => value.to_string(),
+        DntValue::Float32(value) if value.is_finite() => value.to_string(),
+        DntValue::Float64(value) if value.is_finite() => value.to_string(),
+        DntValue::Float32(_) | DntValue::Float64(_) | DntValue::Null => String::from("NULL"),
+        DntValue::Bytes(value) => {
+            let hex = to_hex(value);
+            match dialect {
+                SqlDialect::Postgres => format!("'\\x{}'", hex),
+                SqlDialect::MySql | SqlDialect::Sqlite => format!("X'{}'", hex),
+            }
+        }
+    }
+}
+
+fn quote_string(value: &str, dialect: SqlDialect) -> String {
+    let escaped = match dialect {
+        SqlDialect::MySql => value.replace('\\', "\\\\").replace('\'', "''"),
+        SqlDialect::Postgres | SqlDialect::Sqlite => value.replace('\'', "''"),
+    };
+    format!("'{}'", escaped)
+}
+
+pub(crate) fn quote_identifier(identifier: &str, dialect: SqlDialect) -> String {
+    match dialect {
+        SqlDialect::MySql => format!("`{}`", identifier.replace('`', "``")),
+        SqlDialect::Postgres | SqlDialect::Sqlite => {
+            format!("\"{}\"", identifier.replace('"', "\"\""))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DntDataType, DntTable, DntTableBuilder, SqlDialect, SqlOptions};
+
+    fn table() -> DntTable {
+        DntTableBuilder::new()
+            .column("_Na\"me`", DntDataType::String)
+            .column("_Rate", DntDataType::Float32)
+            .row((1, "It's a \\ back", 0.5f32))
+            .build()
+            .unwrap()
+    }
+
+    fn sql(dialect: SqlDialect) -> String {
+        let options = SqlOptions {
+            dialect,
+            ..SqlOptions::default()
+        };
+        table().to_sql("skill\"table`", &options).unwrap()
+    }
+
+    #[test]
+    fn quotes_for_postgres() {
+        assert_eq!(
+            sql(SqlDialect::Postgres),
+            "CREATE TABLE \"skill\"\"table`\" (\n    \"id\" INTEGER,\n    \"_Na\"\"me`\" TEXT,\n    \"_Rate\" REAL\n);\n\
+             INSERT INTO \"skill\"\"table`\" VALUES\n    (1, 'It''s a \\ back', 0.5);\n"
+        );
+    }
+
+    #[test]
+    fn quotes_for_mysql() {
+        assert_eq!(
+            sql(SqlDialect::MySql),
+            "CREATE TABLE `skill\"table``` (\n    `id` INT,\n    `_Na\"me``` TEXT,\n    `_Rate` FLOAT\n);\n\
+             INSERT INTO `skill\"table``` VALUES\n    (1, 'It''s a \\\\ back', 0.5);\n"
+        );
+    }
+
+    #[test]
+    fn quotes_for_sqlite() {
+        assert_eq!(
+            sql(SqlDialect::Sqlite),
+            "CREATE TABLE \"skill\"\"table`\" (\n    \"id\" INTEGER,\n    \"_Na\"\"me`\" TEXT,\n    \"_Rate\" REAL\n);\n\
+             INSERT INTO \"skill\"\"table`\" VALUES\n    (1, 'It''s a \\ back', 0.5);\n"
+        );
+    }
+}
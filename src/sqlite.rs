@@ -0,0 +1,146 @@
+use rusqlite::{types::Value, Connection};
+
+use crate::{
+    sql::{quote_identifier, sql_type},
+    DntError, DntTable, DntValue, SqlDialect,
+};
+
+impl DntTable {
+    pub fn to_sqlite(&self, connection: &Connection, name: &str) -> Result<(), DntError> {
+        export_sqlite(connection, [(name, self)])
+    }
+}
+
+pub fn export_sqlite<'a, I>(connection: &Connection, tables: I) -> Result<(), DntError>
+where
+    I: IntoIterator<Item = (&'a str, &'a DntTable)>,
+{
+    let transaction = connection.unchecked_transaction()?;
+
+    for (name, table) in tables {
+        let columns = table
+            .head
+            .iter()
+            .map(|column| {
+                format!(
+                    "{} {}",
+                    quote_identifier(&column.text, SqlDialect::Sqlite),
+                    sql_type(column.data_type, SqlDialect::Sqlite)
+                )
+            })
+            .collect::<Vec<String>>();
+
+        transaction.execute(
+            &format!(
+                "CREATE TABLE {} ({})",
+                quote_identifier(name, SqlDialect::Sqlite),
+                columns.join(", ")
+            ),
+            [],
+        )?;
+
+        let placeholders = vec!["?"; table.head.len()].join(", ");
+        let mut statement = transaction.prepare(&format!(
+            "INSERT INTO {} VALUES ({})",
+            quote_identifier(name, SqlDialect::Sqlite),
+            placeholders
+        ))?;
+
+        for row in &table.body {
+            statement.execute(rusqlite::params_from_iter(row.values.iter().map(sql_value)))?;
+        }
+    }
+
+    transaction.commit()?;
+    Ok(())
+}
+
+fn sql_value(value: &DntValue) -> Value {
+    match value {
+        DntValue::String(value) => Value::Text(value.to_owned()),
+        DntValue::UInt32(value) => Value::Integer(*value as i64),
+        DntValue::Int32(value) => Value::Integer(*value as i64),
+        DntValue::Float32(value) => Value::Real(*value as f64),
+        DntValue::Float64(value) => Value::Real(*value),
+        DntValue::Bytes(value) => Value::Blob(value.to_owned()),
+        DntValue::Null => Value::Null,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rusqlite::Connection;
+
+    use crate::{export_sqlite, DntDataType, DntTable, DntTableBuilder, DntValue};
+
+    fn table() -> DntTable {
+        DntTableBuilder::new()
+            .column("_Name", DntDataType::String)
+            .column("_Level", DntDataType::UInt32)
+            .column("_Rate", DntDataType::Float32)
+            .row((1, "It's \"sharp\"", 3_000_000_000u32, 0.5f32))
+            .row((2, "Shield", 2u32, DntValue::Null))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn exports_rows_that_can_be_queried() {
+        let connection = Connection::open_in_memory().unwrap();
+        table().to_sqlite(&connection, "skill\"table").unwrap();
+
+        let mut statement = connection
+            .prepare("SELECT id, _Name, _Level, _Rate FROM \"skill\"\"table\" ORDER BY id")
+            .unwrap();
+        let rows = statement
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, i64>(2)?,
+                    row.get::<_, Option<f64>>(3)?,
+                ))
+            })
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(
+            rows,
+            [
+                (1, String::from("It's \"sharp\""), 3_000_000_000, Some(0.5)),
+                (2, String::from("Shield"), 2, None)
+            ]
+        );
+
+        let column_type: String = connection
+            .query_row(
+                "SELECT type FROM pragma_table_info('skill\"table') WHERE name = '_Rate'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(column_type, "REAL");
+    }
+
+    #[test]
+    fn exports_several_tables_in_one_transaction() {
+        let connection = Connection::open_in_memory().unwrap();
+        let table = table();
+        export_sqlite(&connection, [("first", &table), ("second", &table)]).unwrap();
+
+        let count: i64 = connection
+            .query_row("SELECT COUNT(*) FROM second", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 2);
+
+        assert!(export_sqlite(&connection, [("third", &table), ("first", &table)]).is_err());
+        let third: i64 = connection
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE name = 'third'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(third, 0);
+    }
+}
@@ -0,0 +1,178 @@
+use std::collections::HashSet;
+
+use crate::{DntDataType, DntTable, DntValue};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DntColumnStats {
+    pub column: String,
+    pub data_type: DntDataType,
+    pub summary: DntColumnSummary,
+}
+
+/// Summary of a column's values. String and unknown columns are summarized as
+/// text, counting raw bytes for the latter.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DntColumnSummary {
+    Numeric {
+        min: Option<f64>,
+        max: Option<f64>,
+        mean: Option<f64>,
+    },
+    Text {
+        distinct: usize,
+        empty: usize,
+    },
+}
+
+impl DntTable {
+    pub fn stats(&self) -> Vec<DntColumnStats> {
+        self.head
+            .iter()
+            .enumerate()
+            .map(|(index, column)| {
+                let values = self.body.iter().filter_map(|row| row.values.get(index));
+
+                let summary = match column.data_type {
+                    DntDataType::String | DntDataType::Unknown(_) => text_summary(values),
+                    _ => numeric_summary(values),
+                };
+
+                DntColumnStats {
+                    column: column.text.clone(),
+                    data_type: column.data_type,
+                    summary,
+                }
+            })
+            .collect()
+    }
+}
+
+fn numeric_summary<'a>(values: impl Iterator<Item = &'a DntValue>) -> DntColumnSummary {
+    let mut min = None::<f64>;
+    let mut max = None::<f64>;
+    let mut sum = 0.0;
+    let mut count = 0;
+
+    for value in values {
+        let value = match value {
+            DntValue::UInt32(value) => *value as f64,
+            DntValue::Int32(value) => *value as f64,
+            DntValue::Float32(value) => *value as f64,
+            DntValue::Float64(value) => *value,
+            _ => continue,
+        };
+
+        min = Some(min.map_or(value, |min| min.min(value)));
+        max = Some(max.map_or(value, |max| max.max(value)));
+        sum += value;
+        count += 1;
+    }
+
+    DntColumnSummary::Numeric {
+        min,
+        max,
+        mean: (count > 0).then(|| sum / count as f64),
+    }
+}
+
+fn text_summary<'a>(values: impl Iterator<Item = &'a DntValue>) -> DntColumnSummary {
+    let mut distinct = HashSet::new();
+    let mut empty = 0;
+
+    for value in values {
+        let bytes = match value {
+            DntValue::String(value) => value.as_bytes(),
+            DntValue::Bytes(value) => value.as_slice(),
+            _ => continue,
+        };
+
+        if bytes.is_empty() {
+            empty += 1;
+        }
+        distinct.insert(bytes);
+    }
+
+    DntColumnSummary::Text {
+        distinct: distinct.len(),
+        empty,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        DntColumn, DntColumnSummary, DntDataType, DntRow, DntTable, DntTableBuilder, DntValue,
+    };
+
+    #[test]
+    fn leaves_nulls_out() {
+        let table = DntTableBuilder::new()
+            .column("_Rate", DntDataType::Float32)
+            .column("_Level", DntDataType::UInt32)
+            .column("_Name", DntDataType::String)
+            .row((1, 0.5f32, DntValue::Null, "Sword"))
+            .row((2, DntValue::Null, DntValue::Null, ""))
+            .row((3, 2.5f32, DntValue::Null, "Sword"))
+            .build()
+            .unwrap();
+
+        let stats = table.stats();
+        assert_eq!(stats.len(), 4);
+        assert_eq!(stats[1].column, "_Rate");
+        assert_eq!(
+            stats[1].summary,
+            DntColumnSummary::Numeric {
+                min: Some(0.5),
+                max: Some(2.5),
+                mean: Some(1.5),
+            }
+        );
+        assert_eq!(
+            stats[2].summary,
+            DntColumnSummary::Numeric {
+                min: None,
+                max: None,
+                mean: None,
+            }
+        );
+        assert_eq!(
+            stats[3].summary,
+            DntColumnSummary::Text {
+                distinct: 2,
+                empty: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn compares_integers_of_either_type_by_number() {
+        let values = [
+            DntValue::UInt32(3_000_000_000),
+            DntValue::Int32(-10),
+            DntValue::UInt32(10),
+        ];
+        let body = values
+            .into_iter()
+            .enumerate()
+            .map(|(index, value)| DntRow {
+                values: vec![DntValue::Int32(index as i32 + 1), value],
+            })
+            .collect();
+        let table = DntTable::from_parts(
+            vec![
+                DntColumn::new("id", DntDataType::Int32),
+                DntColumn::new("_Target", DntDataType::UInt32),
+            ],
+            body,
+        );
+
+        assert_eq!(
+            table.stats()[1].summary,
+            DntColumnSummary::Numeric {
+                min: Some(-10.0),
+                max: Some(3_000_000_000.0),
+                mean: Some(1_000_000_000.0),
+            }
+        );
+    }
+}
@@ -0,0 +1,171 @@
+use std::io::{Seek, SeekFrom, Write};
+
+use crate::{check_id_column, check_row, DntColumn, DntError, DntFileWriter, DntRow, DntSchema};
+
+/// Writes a table one row at a time, returned by `DntFileWriter::begin`. The
+/// file is only complete once `finish` has been called.
+pub struct DntStreamWriter<'a, W: Write + Seek> {
+    writer: &'a mut DntFileWriter<W>,
+    head: Vec<DntColumn>,
+    expected: Option<u32>,
+    rows: u32,
+    start: u64,
+}
+
+impl<W: Write + Seek> DntFileWriter<W> {
+    /// Writes the header for `schema` and returns a writer for the rows. When
+    /// the number of rows isn't known in advance, pass `None` and the count is
+    /// filled in by `finish`.
+    pub fn begin(
+        &mut self,
+        schema: &DntSchema,
+        rows: Option<u32>,
+    ) -> Result<DntStreamWriter<'_, W>, DntError> {
+        let head = schema.columns().to_vec();
+        check_id_column(&head)?;
+
+        let start = self.writer.stream_position()?;
+        self.write_header(&head, [0; 4], &[], rows.unwrap_or(0))?;
+
+        Ok(DntStreamWriter {
+            writer: self,
+            head,
+            expected: rows,
+            rows: 0,
+            start,
+        })
+    }
+}
+
+impl<W: Write + Seek> DntStreamWriter<'_, W> {
+    pub fn write_row(&mut self, row: &DntRow) -> Result<(), DntError> {
+        check_row(&self.head, self.rows as usize, row)?;
+
+        if self.expected == Some(self.rows) {
+            return Err(DntError::InvalidValue(format!(
+                "expected {} rows, got more",
+                self.rows
+            )));
+        }
+
+        self.rows = self
+            .rows
+            .checked_add(1)
+            .ok_or_else(|| DntError::InvalidHeader(String::from("too many rows")))?;
+        self.writer.write_row(&self.head, row)
+    }
+
+    pub fn rows(&self) -> u32 {
+        self.rows
+    }
+
+    /// Writes the footer and, if the number of rows wasn't given to `begin`,
+    /// goes back to write it in the header.
+    pub fn finish(mut self) -> Result<(), DntError> {
+        match self.expected {
+            Some(expected) if expected != self.rows => {
+                return Err(DntError::InvalidValue(format!(
+                    "expected {} rows, got {}",
+                    expected, self.rows
+                )));
+            }
+            Some(_) => {}
+            None => self.patch_rows_nb()?,
+        }
+
+        self.writer.write_footer()?;
+        self.writer.finish_payload()
+    }
+
+    fn patch_rows_nb(&mut self) -> Result<(), DntError> {
+        let rows_nb = self.writer.endianness.u32_bytes(self.rows);
+
+        // The row count follows the 4-byte magic and the 2-byte column count.
+        match &mut self.writer.payload {
+            Some(payload) => payload[2..6].copy_from_slice(&rows_nb),
+            None => {
+                let end = self.writer.writer.stream_position()?;
+                self.writer.writer.seek(SeekFrom::Start(self.start + 6))?;
+                self.writer.writer.write_all(&rows_nb)?;
+                self.writer.writer.seek(SeekFrom::Start(end))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use crate::{DntDataType, DntFileWriter, DntSchema, DntTable, DntTableBuilder};
+
+    fn table() -> DntTable {
+        DntTableBuilder::new()
+            .column("_Name", DntDataType::String)
+            .column("_Rate", DntDataType::Float32)
+            .row((1, "Sword", 0.5f32))
+            .row((2, "Shield", 1.5f32))
+            .row((3, "Bow", 2.5f32))
+            .build()
+            .unwrap()
+    }
+
+    fn stream(writer: &mut DntFileWriter<Cursor<Vec<u8>>>, table: &DntTable, rows: Option<u32>) {
+        let mut stream = writer.begin(&DntSchema::from_table(table), rows).unwrap();
+        for row in &table.body {
+            stream.write_row(row).unwrap();
+        }
+        assert_eq!(stream.rows(), 3);
+        stream.finish().unwrap();
+    }
+
+    #[test]
+    fn writes_what_the_file_writer_writes() {
+        let table = table();
+        let expected = table.to_bytes().unwrap();
+
+        for rows in [Some(3), None] {
+            let mut writer = DntFileWriter::new(Cursor::new(vec![]));
+            stream(&mut writer, &table, rows);
+            assert_eq!(writer.into_inner().unwrap().into_inner(), expected);
+        }
+    }
+
+    #[cfg(feature = "zlib")]
+    #[test]
+    fn writes_what_the_file_writer_writes_compressed() {
+        use crate::CompressionMode;
+
+        let table = table();
+        let mut writer =
+            DntFileWriter::new(Cursor::new(vec![])).with_compression(CompressionMode::Zlib);
+        writer.write(&table).unwrap();
+        let expected = writer.into_inner().unwrap().into_inner();
+
+        let mut writer =
+            DntFileWriter::new(Cursor::new(vec![])).with_compression(CompressionMode::Zlib);
+        stream(&mut writer, &table, None);
+        assert_eq!(writer.into_inner().unwrap().into_inner(), expected);
+    }
+
+    #[test]
+    fn checks_the_row_count() {
+        let table = table();
+        let mut writer = DntFileWriter::new(Cursor::new(vec![]));
+
+        let mut stream = writer
+            .begin(&DntSchema::from_table(&table), Some(2))
+            .unwrap();
+        stream.write_row(&table.body[0]).unwrap();
+        stream.write_row(&table.body[1]).unwrap();
+        assert!(stream.write_row(&table.body[2]).is_err());
+
+        let mut stream = writer
+            .begin(&DntSchema::from_table(&table), Some(2))
+            .unwrap();
+        stream.write_row(&table.body[0]).unwrap();
+        assert!(stream.finish().is_err());
+    }
+}
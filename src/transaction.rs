@@ -0,0 +1,120 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::{DntError, DntTable};
+
+/// Changes to a table that are kept only if `commit` is called. The
+/// transaction derefs to the table, so every editing method can be used on
+/// it. `rollback`, or dropping the transaction without committing it, puts
+/// the table back as it was when `begin_edit` was called, for example when
+/// `?` returns early from a failed step.
+pub struct DntTransaction<'a> {
+    table: &'a mut DntTable,
+    /// A copy of the table taken by `begin_edit`, `None` once committed.
+    snapshot: Option<DntTable>,
+}
+
+impl DntTable {
+    /// Starts a transaction. The table is copied, so that it can be
+    /// restored, which costs as much memory as the table itself.
+    pub fn begin_edit(&mut self) -> DntTransaction<'_> {
+        DntTransaction {
+            snapshot: Some(self.clone()),
+            table: self,
+        }
+    }
+}
+
+impl DntTransaction<'_> {
+    /// Keeps the changes, and rebuilds the id index if the table has one,
+    /// as rows may have been changed through `body`. If the ids are no
+    /// longer unique, the changes are discarded and the duplicate returned.
+    pub fn commit(mut self) -> Result<(), DntError> {
+        self.table.refresh_id_index()?;
+        self.snapshot = None;
+        Ok(())
+    }
+
+    /// Discards the changes.
+    pub fn rollback(self) {}
+}
+
+impl Deref for DntTransaction<'_> {
+    type Target = DntTable;
+
+    fn deref(&self) -> &DntTable {
+        self.table
+    }
+}
+
+impl DerefMut for DntTransaction<'_> {
+    fn deref_mut(&mut self) -> &mut DntTable {
+        self.table
+    }
+}
+
+impl Drop for DntTransaction<'_> {
+    fn drop(&mut self) {
+        if let Some(snapshot) = self.snapshot.take() {
+            *self.table = snapshot;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DntDataType, DntError, DntRow, DntTable, DntTableBuilder, DntValue, RowKey};
+
+    fn table() -> DntTable {
+        let mut table = DntTableBuilder::new()
+            .column("_Name", DntDataType::String)
+            .row((1, "Sword"))
+            .row((2, "Shield"))
+            .build()
+            .unwrap();
+        table.build_id_index().unwrap();
+        table
+    }
+
+    #[test]
+    fn rolls_back_unless_committed() {
+        let mut table = table();
+        let mut edit = table.begin_edit();
+        edit.set(RowKey::Id(1), "_Name", DntValue::from("Axe"))
+            .unwrap();
+        edit.remove_row(2).unwrap();
+        edit.rollback();
+        assert!(table == self::table());
+        assert_eq!(table.get_by_id(2), Some(&table.body[1]));
+
+        let result: Result<(), DntError> = (|| {
+            let mut edit = table.begin_edit();
+            edit.remove_row(1)?;
+            edit.remove_row(3)?;
+            edit.commit()
+        })();
+        assert!(result.is_err());
+        assert!(table == self::table());
+    }
+
+    #[test]
+    fn commits_and_refreshes_the_id_index() {
+        let mut table = table();
+        let mut edit = table.begin_edit();
+        edit.body.insert(
+            0,
+            DntRow {
+                values: vec![DntValue::Int32(3), DntValue::from("Bow")],
+            },
+        );
+        edit.commit().unwrap();
+        assert_eq!(table.body.len(), 3);
+        assert_eq!(table.get_by_id(3), Some(&table.body[0]));
+        assert_eq!(table.get_by_id(2), Some(&table.body[2]));
+
+        let mut edit = table.begin_edit();
+        edit.body[0].values[0] = DntValue::Int32(1);
+        assert!(matches!(edit.commit(), Err(DntError::InvalidValue(_))));
+        assert_eq!(table.get_by_id(3), Some(&table.body[0]));
+        assert!(table.has_id_index());
+    }
+}
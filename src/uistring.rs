@@ -0,0 +1,412 @@
+use std::collections::HashMap;
+#[cfg(feature = "uistring")]
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    path::Path,
+};
+
+#[cfg(feature = "uistring")]
+use quick_xml::{events::Event, Reader};
+
+use crate::{DntColumn, DntDataType, DntError, DntRow, DntTable, DntValue};
+
+/// Localized text keyed by message id, as found in the game's `uistring.xml`.
+#[derive(Debug, Clone, Default)]
+pub struct UiStrings {
+    strings: HashMap<u32, String>,
+}
+
+impl UiStrings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads a `uistring.xml` file, made of `<message mid="...">` elements
+    /// whose content is the text.
+    #[cfg(feature = "uistring")]
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, DntError> {
+        Self::from_xml(BufReader::new(File::open(path)?))
+    }
+
+    #[cfg(feature = "uistring")]
+    pub fn from_xml<R: BufRead>(reader: R) -> Result<Self, DntError> {
+        let mut reader = Reader::from_reader(reader);
+        let mut buffer = vec![];
+        let mut strings = Self::new();
+        let mut message: Option<(u32, String)> = None;
+
+        loop {
+            match reader.read_event_into(&mut buffer)? {
+                Event::Start(element) if element.name().as_ref() == b"message" => {
+                    message = Some((message_id(&element)?, String::new()));
+                }
+                Event::Empty(element) if element.name().as_ref() == b"message" => {
+                    strings.insert(message_id(&element)?, String::new());
+                }
+                Event::Text(text) => {
+                    if let Some((_, message)) = &mut message {
+                        message.push_str(&text.decode().map_err(quick_xml::Error::from)?);
+                    }
+                }
+                Event::CData(text) => {
+                    if let Some((_, message)) = &mut message {
+                        message.push_str(&text.decode().map_err(quick_xml::Error::from)?);
+                    }
+                }
+                Event::GeneralRef(reference) => {
+                    if let Some((_, message)) = &mut message {
+                        let name = reference.decode().map_err(quick_xml::Error::from)?;
+                        match reference.resolve_char_ref()? {
+                            Some(character) => message.push(character),
+                            None => message.push_str(
+                                quick_xml::escape::resolve_predefined_entity(&name).ok_or_else(
+                                    || DntError::InvalidValue(format!("unknown entity &{};", name)),
+                                )?,
+                            ),
+                        }
+                    }
+                }
+                Event::End(element) if element.name().as_ref() == b"message" => {
+                    if let Some((mid, text)) = message.take() {
+                        strings.insert(mid, text);
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+            buffer.clear();
+        }
+
+        Ok(strings)
+    }
+
+    pub fn insert(&mut self, mid: u32, text: impl Into<String>) -> Option<String> {
+        self.strings.insert(mid, text.into())
+    }
+
+    pub fn get(&self, mid: u32) -> Option<&str> {
+        self.strings.get(&mid).map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+}
+
+impl<S: Into<String>> FromIterator<(u32, S)> for UiStrings {
+    fn from_iter<I: IntoIterator<Item = (u32, S)>>(iter: I) -> Self {
+        Self {
+            strings: iter
+                .into_iter()
+                .map(|(mid, text)| (mid, text.into()))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(feature = "uistring")]
+fn message_id(element: &quick_xml::events::BytesStart<'_>) -> Result<u32, DntError> {
+    let mid = element
+        .try_get_attribute("mid")
+        .map_err(quick_xml::Error::from)?
+        .ok_or_else(|| DntError::InvalidValue(String::from("message without a mid")))?
+        .value;
+    let mid = String::from_utf8_lossy(&mid);
+
+    mid.trim()
+        .parse()
+        .map_err(|_| DntError::InvalidValue(format!("invalid message id: {}", mid)))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UiStringOutput {
+    /// Replaces the id columns with string columns of the same name.
+    Replace,
+    /// Keeps the id columns and adds a string column after each of them,
+    /// named after it with this suffix.
+    Append(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MissingUiString {
+    Empty,
+    /// Uses the id itself as the text.
+    Id,
+    Error,
+}
+
+#[derive(Debug, Clone)]
+pub struct UiStringOptions {
+    /// Columns to resolve. If `None`, every integer column whose name ends
+    /// with `NameID` is resolved.
+    pub columns: Option<Vec<String>>,
+    pub output: UiStringOutput,
+    /// What to do with ids that have no text. Id 0 always resolves to an
+    /// empty string.
+    pub missing: MissingUiString,
+}
+
+impl Default for UiStringOptions {
+    fn default() -> Self {
+        Self {
+            columns: None,
+            output: UiStringOutput::Replace,
+            missing: MissingUiString::Empty,
+        }
+    }
+}
+
+impl DntTable {
+    /// Returns a copy of the table where string-table ids are replaced by, or
+    /// followed by, the text they refer to.
+    pub fn resolve_ui_strings(
+        &self,
+        strings: &UiStrings,
+        options: &UiStringOptions,
+    ) -> Result<DntTable, DntError> {
+        let resolved = self.ui_string_columns(options)?;
+
+        let mut head = vec![];
+        for (index, column) in self.head.iter().enumerate() {
+            if !resolved.contains(&index) {
+                head.push(column.clone());
+                continue;
+            }
+
+            match &options.output {
+                UiStringOutput::Replace => {
+                    head.push(DntColumn::new(&column.text, DntDataType::String));
+                }
+                UiStringOutput::Append(suffix) => {
+                    head.push(column.clone());
+                    head.push(DntColumn::new(
+                        &format!("{}{}", column.text, suffix),
+                        DntDataType::String,
+                    ));
+                }
+            }
+        }
+
+        let body = self
+            .body
+            .iter()
+            .enumerate()
+            .map(|(row_index, row)| {
+                if row.values.len() != self.head.len() {
+                    return Err(DntError::InvalidRowLength {
+                        row: row_index,
+                        expected: self.head.len(),
+                        found: row.values.len(),
+                    });
+                }
+
+                let mut values = Vec::with_capacity(head.len());
+                for (index, value) in row.values.iter().enumerate() {
+                    if !resolved.contains(&index) {
+                        values.push(value.clone());
+                        continue;
+                    }
+
+                    let text = self.ui_string(strings, options, row_index, index, value)?;
+                    if let UiStringOutput::Append(_) = options.output {
+                        values.push(value.clone());
+                    }
+                    values.push(DntValue::String(text));
+                }
+
+                Ok(DntRow { values })
+            })
+            .collect::<Result<Vec<DntRow>, DntError>>()?;
+
+        let mut table = DntTable::from_parts(head, body);
+        table.copy_file_bytes(self);
+
+        Ok(table)
+    }
+
+    fn ui_string_columns(&self, options: &UiStringOptions) -> Result<Vec<usize>, DntError> {
+        let is_id = |data_type| matches!(data_type, DntDataType::UInt32 | DntDataType::Int32);
+
+        let Some(columns) = &options.columns else {
+            return Ok(self
+                .head
+                .iter()
+                .enumerate()
+                .skip(1)
+                .filter(|(_, column)| {
+                    is_id(column.data_type())
+                        && column.text.to_ascii_lowercase().ends_with("nameid")
+                })
+                .map(|(index, _)| index)
+                .collect());
+        };
+
+        columns
+            .iter()
+            .map(|name| {
+                let index = self.column_position(name)?;
+                let data_type = self.head[index].data_type();
+                if index == 0 || !is_id(data_type) {
+                    return Err(DntError::TypeMismatch {
+                        column: name.clone(),
+                        expected: DntDataType::Int32,
+                        found: data_type,
+                    });
+                }
+
+                Ok(index)
+            })
+            .collect()
+    }
+
+    fn ui_string(
+        &self,
+        strings: &UiStrings,
+        options: &UiStringOptions,
+        row: usize,
+        column: usize,
+        value: &DntValue,
+    ) -> Result<String, DntError> {
+        let (mid, id) = match value {
+            DntValue::UInt32(mid) => (Some(*mid), *mid as i64),
+            DntValue::Int32(mid) => (u32::try_from(*mid).ok(), *mid as i64),
+            DntValue::Null => return Ok(String::new()),
+            _ => {
+                return Err(DntError::InvalidCell {
+                    row,
+                    column: self.head[column].text.clone(),
+                    expected: self.head[column].data_type(),
+                    found: value.data_type(),
+                })
+            }
+        };
+
+        if id == 0 {
+            return Ok(String::new());
+        }
+
+        match (mid.and_then(|mid| strings.get(mid)), options.missing) {
+            (Some(text), _) => Ok(text.to_owned()),
+            (None, MissingUiString::Empty) => Ok(String::new()),
+            (None, MissingUiString::Id) => Ok(id.to_string()),
+            (None, MissingUiString::Error) => Err(DntError::InvalidValue(format!(
+                "row {}, column {}: no text for id {}",
+                row, self.head[column].text, id
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        DntDataType, DntError, DntTable, DntTableBuilder, DntValue, MissingUiString,
+        UiStringOptions, UiStringOutput, UiStrings,
+    };
+
+    #[cfg(feature = "uistring")]
+    #[test]
+    fn reads_entities_cdata_and_empty_messages() {
+        let xml = br#"<?xml version="1.0" encoding="utf-8"?>
+<messages>
+  <message mid="1">Fire &amp; Ice &#65;</message>
+  <message mid=" 2 "><![CDATA[<b>bold</b>]]></message>
+  <message mid="3"/>
+</messages>"#;
+
+        let strings = UiStrings::from_xml(&xml[..]).unwrap();
+        assert_eq!(strings.len(), 3);
+        assert_eq!(strings.get(1), Some("Fire & Ice A"));
+        assert_eq!(strings.get(2), Some("<b>bold</b>"));
+        assert_eq!(strings.get(3), Some(""));
+
+        assert!(UiStrings::from_xml(&b"<message>text</message>"[..]).is_err());
+        assert!(UiStrings::from_xml(&b"<message mid=\"x\">text</message>"[..]).is_err());
+    }
+
+    fn table() -> DntTable {
+        DntTableBuilder::new()
+            .column("_NameID", DntDataType::UInt32)
+            .column("_Level", DntDataType::UInt32)
+            .row((1, 10u32, 5u32))
+            .row((2, 0u32, 6u32))
+            .row((3, 99u32, 7u32))
+            .build()
+            .unwrap()
+    }
+
+    fn strings() -> UiStrings {
+        [(10, "Fireball")].into_iter().collect()
+    }
+
+    fn names(missing: MissingUiString) -> Result<Vec<DntValue>, DntError> {
+        let options = UiStringOptions {
+            missing,
+            ..UiStringOptions::default()
+        };
+        let resolved = table().resolve_ui_strings(&strings(), &options)?;
+        assert_eq!(resolved.head[1].data_type, DntDataType::String);
+        Ok(resolved
+            .body
+            .iter()
+            .map(|row| row.values[1].clone())
+            .collect())
+    }
+
+    #[test]
+    fn handles_missing_strings_by_policy() {
+        assert_eq!(
+            names(MissingUiString::Empty).unwrap(),
+            [
+                DntValue::from("Fireball"),
+                DntValue::from(""),
+                DntValue::from("")
+            ]
+        );
+        assert_eq!(
+            names(MissingUiString::Id).unwrap(),
+            [
+                DntValue::from("Fireball"),
+                DntValue::from(""),
+                DntValue::from("99")
+            ]
+        );
+        assert!(matches!(
+            names(MissingUiString::Error),
+            Err(DntError::InvalidValue(_))
+        ));
+    }
+
+    #[test]
+    fn appends_or_resolves_named_columns() {
+        let options = UiStringOptions {
+            output: UiStringOutput::Append(String::from("_Text")),
+            ..UiStringOptions::default()
+        };
+        let resolved = table().resolve_ui_strings(&strings(), &options).unwrap();
+        assert_eq!(resolved.head[2].text, "_NameID_Text");
+        assert_eq!(
+            resolved.body[0].values,
+            [
+                DntValue::Int32(1),
+                DntValue::UInt32(10),
+                DntValue::from("Fireball"),
+                DntValue::UInt32(5)
+            ]
+        );
+
+        let options = UiStringOptions {
+            columns: Some(vec![String::from("_Level")]),
+            missing: MissingUiString::Id,
+            ..UiStringOptions::default()
+        };
+        let resolved = table().resolve_ui_strings(&strings(), &options).unwrap();
+        assert_eq!(resolved.body[0].values[1], DntValue::UInt32(10));
+        assert_eq!(resolved.body[0].values[2], DntValue::from("5"));
+    }
+}
@@ -0,0 +1,327 @@
+use std::collections::HashSet;
+
+use crate::{
+    check_id_column,
+    diff::{row_id, same_value},
+    DntError, DntTable, DntValue,
+};
+
+/// A check run on every value of a column.
+#[derive(Clone)]
+pub enum DntRule<'a> {
+    /// The value is a number between `min` and `max`, both included.
+    Range { min: f64, max: f64 },
+    /// The value is a string that isn't empty.
+    NonEmpty,
+    /// The value is one of these. Numbers are compared by value, so `1` can
+    /// be given for both `UInt32` and `Int32` columns.
+    OneOf(Vec<DntValue>),
+    /// The value is 0 or the id of a row of the table.
+    References(&'a DntTable),
+}
+
+#[derive(Debug, Clone)]
+pub struct DntViolation {
+    pub row: usize,
+    pub id: Option<i32>,
+    pub column: String,
+    pub value: DntValue,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DntValidationReport {
+    pub rows: usize,
+    pub violations: Vec<DntViolation>,
+}
+
+impl DntValidationReport {
+    pub fn is_ok(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+/// A set of rules to check tables against, registered per column.
+#[derive(Clone, Default)]
+pub struct DntValidator<'a> {
+    rules: Vec<(String, DntRule<'a>)>,
+}
+
+impl<'a> DntValidator<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn rule(mut self, column: impl Into<String>, rule: DntRule<'a>) -> Self {
+        self.rules.push((column.into(), rule));
+        self
+    }
+
+    pub fn range(self, column: impl Into<String>, min: f64, max: f64) -> Self {
+        self.rule(column, DntRule::Range { min, max })
+    }
+
+    pub fn non_empty(self, column: impl Into<String>) -> Self {
+        self.rule(column, DntRule::NonEmpty)
+    }
+
+    pub fn one_of<I, V>(self, column: impl Into<String>, values: I) -> Self
+    where
+        I: IntoIterator<Item = V>,
+        V: Into<DntValue>,
+    {
+        self.rule(
+            column,
+            DntRule::OneOf(values.into_iter().map(Into::into).collect()),
+        )
+    }
+
+    pub fn references(self, column: impl Into<String>, table: &'a DntTable) -> Self {
+        self.rule(column, DntRule::References(table))
+    }
+
+    /// Runs every rule over the table. A rule naming a column the table
+    /// doesn't have is an error; values breaking a rule are collected in
+    /// the report.
+    pub fn validate(&self, table: &DntTable) -> Result<DntValidationReport, DntError> {
+        let mut report = DntValidationReport {
+            rows: table.body.len(),
+            violations: vec![],
+        };
+
+        for (column, rule) in &self.rules {
+            let index = table.column_position(column)?;
+            let ids = match rule {
+                DntRule::References(target) => target
+                    .body
+                    .iter()
+                    .filter_map(|row| row_id(row).ok())
+                    .collect::<HashSet<i32>>(),
+                _ => HashSet::new(),
+            };
+
+            for (row, values) in table.body.iter().enumerate() {
+                let Some(value) = values.values.get(index) else {
+                    continue;
+                };
+
+                if let Some(message) = check(rule, &ids, value) {
+                    report.violations.push(DntViolation {
+                        row,
+                        id: row_id(values).ok(),
+                        column: column.clone(),
+                        value: value.clone(),
+                        message,
+                    });
+                }
+            }
+        }
+
+        report.violations.sort_by_key(|violation| violation.row);
+
+        Ok(report)
+    }
+}
+
+fn check(rule: &DntRule<'_>, ids: &HashSet<i32>, value: &DntValue) -> Option<String> {
+    if value.is_null() {
+        return match rule {
+            DntRule::NonEmpty => Some(String::from("null")),
+            _ => None,
+        };
+    }
+
+    match rule {
+        DntRule::Range { min, max } => match value.as_f64() {
+            Some(number) if number >= *min && number <= *max => None,
+            Some(number) => Some(format!("{} is not between {} and {}", number, min, max)),
+            None => Some(String::from("not a number")),
+        },
+        DntRule::NonEmpty => match value.as_str() {
+            Some("") => Some(String::from("empty string")),
+            Some(_) => None,
+            None => Some(String::from("not a string")),
+        },
+        DntRule::OneOf(allowed) => {
+            let found = allowed
+                .iter()
+                .any(|allowed| match (allowed.as_f64(), value.as_f64()) {
+                    (Some(allowed), Some(value)) => allowed == value,
+                    _ => same_value(allowed, value),
+                });
+
+            if found {
+                None
+            } else {
+                Some(String::from("not one of the allowed values"))
+            }
+        }
+        DntRule::References(_) => {
+            let id = match value {
+                DntValue::Int32(id) => Some(*id),
+                DntValue::UInt32(id) => i32::try_from(*id).ok(),
+                _ => return Some(String::from("not an id")),
+            };
+
+            match id {
+                Some(0) => None,
+                Some(id) if ids.contains(&id) => None,
+                Some(id) => Some(format!("no row with id {}", id)),
+                None => Some(String::from("no row with this id")),
+            }
+        }
+    }
+}
+
+impl DntTable {
+    /// Checks that the table can be written: the first column is the `Int32`
+    /// id, every row has a value per column and every value matches the type
+    /// of its column. Unlike `DntFileWriter::write`, which stops at the
+    /// first mismatch, every one is returned, in row order.
+    pub fn consistency_errors(&self) -> Vec<DntError> {
+        let mut errors = vec![];
+        if let Err(error) = check_id_column(&self.head) {
+            errors.push(error);
+        }
+
+        for (index, row) in self.body.iter().enumerate() {
+            if row.values.len() != self.head.len() {
+                errors.push(DntError::InvalidRowLength {
+                    row: index,
+                    expected: self.head.len(),
+                    found: row.values.len(),
+                });
+            }
+
+            for (column, value) in self.head.iter().zip(&row.values) {
+                if !value.matches(column.data_type) {
+                    errors.push(DntError::InvalidCell {
+                        row: index,
+                        column: column.text.clone(),
+                        expected: column.data_type,
+                        found: value.data_type(),
+                    });
+                }
+            }
+        }
+
+        errors
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        DntColumn, DntDataType, DntError, DntRow, DntTable, DntTableBuilder, DntValidator, DntValue,
+    };
+
+    fn table() -> DntTable {
+        DntTableBuilder::new()
+            .column("_Name", DntDataType::String)
+            .column("_Level", DntDataType::UInt32)
+            .column("_Job", DntDataType::Int32)
+            .column("_SkillID", DntDataType::UInt32)
+            .row((1, "Sword", 10u32, 1, 0u32))
+            .row((2, "", 120u32, 5, 100u32))
+            .row((3, DntValue::Null, 50u32, 2, 300u32))
+            .build()
+            .unwrap()
+    }
+
+    fn flagged(validator: DntValidator<'_>) -> Vec<(usize, String)> {
+        validator
+            .validate(&table())
+            .unwrap()
+            .violations
+            .into_iter()
+            .map(|violation| (violation.row, violation.message))
+            .collect()
+    }
+
+    #[test]
+    fn checks_ranges() {
+        assert_eq!(
+            flagged(DntValidator::new().range("_Level", 1.0, 100.0)),
+            [(1, String::from("120 is not between 1 and 100"))]
+        );
+    }
+
+    #[test]
+    fn checks_strings_are_not_empty() {
+        assert_eq!(
+            flagged(DntValidator::new().non_empty("_Name")),
+            [(1, String::from("empty string")), (2, String::from("null"))]
+        );
+    }
+
+    #[test]
+    fn checks_allowed_values_by_number() {
+        assert_eq!(
+            flagged(DntValidator::new().one_of("_Job", [DntValue::UInt32(1), DntValue::Int32(2)])),
+            [(1, String::from("not one of the allowed values"))]
+        );
+    }
+
+    #[test]
+    fn checks_references_and_allows_zero() {
+        let skills = DntTableBuilder::new()
+            .column("_Name", DntDataType::String)
+            .row((100, "Fire"))
+            .build()
+            .unwrap();
+
+        let report = DntValidator::new()
+            .references("_SkillID", &skills)
+            .validate(&table())
+            .unwrap();
+        assert_eq!(report.rows, 3);
+        assert!(!report.is_ok());
+        assert_eq!(report.violations.len(), 1);
+        let violation = &report.violations[0];
+        assert_eq!(violation.row, 2);
+        assert_eq!(violation.id, Some(3));
+        assert_eq!(violation.column, "_SkillID");
+        assert_eq!(violation.value, DntValue::UInt32(300));
+        assert_eq!(violation.message, "no row with id 300");
+    }
+
+    #[test]
+    fn rejects_unknown_columns() {
+        assert!(matches!(
+            DntValidator::new().non_empty("_Missing").validate(&table()),
+            Err(DntError::ColumnNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn lists_every_inconsistency() {
+        let table = DntTable::from_parts(
+            vec![
+                DntColumn::new("id", DntDataType::Int32),
+                DntColumn::new("_Level", DntDataType::UInt32),
+            ],
+            vec![
+                DntRow {
+                    values: vec![DntValue::Int32(1)],
+                },
+                DntRow {
+                    values: vec![DntValue::Int32(2), DntValue::from("ten")],
+                },
+            ],
+        );
+
+        let errors = table.consistency_errors();
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(
+            errors[0],
+            DntError::InvalidRowLength {
+                row: 0,
+                expected: 2,
+                found: 1
+            }
+        ));
+        assert!(
+            matches!(&errors[1], DntError::InvalidCell { row: 1, column, .. } if column == "_Level")
+        );
+    }
+}
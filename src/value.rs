@@ -0,0 +1,158 @@
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+};
+
+use crate::{bytes::to_hex, duplicates::ValueKey, DntError, DntValue};
+
+/// Typed accessors. Integers convert between `UInt32` and `Int32` when the
+/// value fits, and every numeric variant widens to `f64`; no accessor loses
+/// precision.
+impl DntValue {
+    pub fn is_null(&self) -> bool {
+        matches!(self, DntValue::Null)
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            DntValue::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_u32(&self) -> Option<u32> {
+        match self {
+            DntValue::UInt32(value) => Some(*value),
+            DntValue::Int32(value) => u32::try_from(*value).ok(),
+            _ => None,
+        }
+    }
+
+    pub fn as_i32(&self) -> Option<i32> {
+        match self {
+            DntValue::Int32(value) => Some(*value),
+            DntValue::UInt32(value) => i32::try_from(*value).ok(),
+            _ => None,
+        }
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            DntValue::UInt32(value) => Some(i64::from(*value)),
+            DntValue::Int32(value) => Some(i64::from(*value)),
+            _ => None,
+        }
+    }
+
+    pub fn as_f32(&self) -> Option<f32> {
+        match self {
+            DntValue::Float32(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            DntValue::UInt32(value) => Some(f64::from(*value)),
+            DntValue::Int32(value) => Some(f64::from(*value)),
+            DntValue::Float32(value) => Some(f64::from(*value)),
+            DntValue::Float64(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            DntValue::Bytes(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+fn conversion_error(expected: &str, value: &DntValue) -> DntError {
+    DntError::InvalidValue(format!("expected {}, got {:?}", expected, value))
+}
+
+macro_rules! impl_try_from_value {
+    ($type:ty, $accessor:ident) => {
+        impl TryFrom<&DntValue> for $type {
+            type Error = DntError;
+
+            fn try_from(value: &DntValue) -> Result<Self, Self::Error> {
+                value
+                    .$accessor()
+                    .ok_or_else(|| conversion_error(stringify!($type), value))
+            }
+        }
+
+        impl TryFrom<DntValue> for $type {
+            type Error = DntError;
+
+            fn try_from(value: DntValue) -> Result<Self, Self::Error> {
+                <$type>::try_from(&value)
+            }
+        }
+    };
+}
+
+impl_try_from_value!(u32, as_u32);
+impl_try_from_value!(i32, as_i32);
+impl_try_from_value!(i64, as_i64);
+impl_try_from_value!(f32, as_f32);
+impl_try_from_value!(f64, as_f64);
+
+impl TryFrom<DntValue> for String {
+    type Error = DntError;
+
+    fn try_from(value: DntValue) -> Result<Self, Self::Error> {
+        match value {
+            DntValue::String(value) => Ok(value),
+            other => Err(conversion_error("String", &other)),
+        }
+    }
+}
+
+impl TryFrom<DntValue> for Vec<u8> {
+    type Error = DntError;
+
+    fn try_from(value: DntValue) -> Result<Self, Self::Error> {
+        match value {
+            DntValue::Bytes(value) => Ok(value),
+            other => Err(conversion_error("bytes", &other)),
+        }
+    }
+}
+
+/// Values are equal when they have the same variant and the same contents.
+/// Floats are compared by their bits, so `NaN` equals itself while `0.0` and
+/// `-0.0` differ, and `UInt32(1)` is not `Int32(1)`. This makes equality
+/// reflexive and consistent with `Hash`, so values can be used as map keys.
+impl PartialEq for DntValue {
+    fn eq(&self, other: &Self) -> bool {
+        ValueKey::from(self) == ValueKey::from(other)
+    }
+}
+
+impl Eq for DntValue {}
+
+impl Hash for DntValue {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        ValueKey::from(self).hash(state);
+    }
+}
+
+/// Writes the value without its type: strings as they are, numbers in their
+/// shortest form, bytes in hex and `Null` as `null`.
+impl fmt::Display for DntValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DntValue::String(value) => f.write_str(value),
+            DntValue::UInt32(value) => write!(f, "{}", value),
+            DntValue::Int32(value) => write!(f, "{}", value),
+            DntValue::Float32(value) => write!(f, "{}", value),
+            DntValue::Float64(value) => write!(f, "{}", value),
+            DntValue::Bytes(value) => write!(f, "{}", to_hex(value)),
+            DntValue::Null => f.write_str("null"),
+        }
+    }
+}
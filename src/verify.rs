@@ -0,0 +1,210 @@
+use std::io::{Read, Seek, SeekFrom};
+
+use crate::{DntDataType, DntError, DntFileReader, DntUnknownWidth};
+
+#[derive(Debug, Clone, Default)]
+pub struct DntVerifyReport {
+    /// Number of columns, including the id column, or 0 if the header could
+    /// not be read.
+    pub columns: usize,
+    pub rows_expected: u32,
+    pub rows_read: u32,
+    pub problems: Vec<DntProblem>,
+}
+
+impl DntVerifyReport {
+    pub fn is_ok(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+/// A problem found by `DntFileReader::verify`. Offsets are in bytes from the
+/// start of the table data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DntProblem {
+    Header(String),
+    TruncatedRow {
+        row: u32,
+        column: String,
+        offset: u64,
+    },
+    InvalidValue {
+        row: u32,
+        column: String,
+        offset: u64,
+        message: String,
+    },
+    Footer(String),
+    TrailingBytes(u64),
+}
+
+impl<R: Read + Seek> DntFileReader<R> {
+    /// Walks the whole file without keeping any rows, checking that the header
+    /// can be read, that every row it announces is complete and decodes, and
+    /// that the file ends with the footer. Only I/O errors are returned as
+    /// errors; everything else is collected in the report.
+    pub fn verify(&mut self) -> Result<DntVerifyReport, DntError> {
+        let mut report = DntVerifyReport::default();
+
+        let header = match self.read_header() {
+            Ok(header) => header,
+            Err(DntError::Io(error)) => return Err(DntError::Io(error)),
+            Err(error) => {
+                report.problems.push(DntProblem::Header(error.to_string()));
+                return Ok(report);
+            }
+        };
+        report.columns = header.head.len();
+        report.rows_expected = header.rows_nb;
+
+        for row in 0..header.rows_nb {
+            for column in &header.head {
+                let offset = self.position()?;
+
+                match self.verify_value(column.data_type) {
+                    Ok(()) => {}
+                    Err(DntError::Io(error)) => return Err(DntError::Io(error)),
+                    Err(DntError::UnexpectedEof) => {
+                        report.problems.push(DntProblem::TruncatedRow {
+                            row,
+                            column: column.text.clone(),
+                            offset,
+                        });
+                        return Ok(report);
+                    }
+                    Err(error) => report.problems.push(DntProblem::InvalidValue {
+                        row,
+                        column: column.text.clone(),
+                        offset,
+                        message: error.to_string(),
+                    }),
+                }
+            }
+            report.rows_read += 1;
+        }
+
+        let body_end = self.position()?;
+        match self.check_footer() {
+            Ok(()) => {}
+            Err(DntError::Io(error)) => return Err(DntError::Io(error)),
+            Err(error) => {
+                report.problems.push(DntProblem::Footer(error.to_string()));
+                self.seek_from(SeekFrom::Start(body_end))?;
+            }
+        }
+
+        let footer_end = self.position()?;
+        let trailing_bytes = self.seek_from(SeekFrom::End(0))? - footer_end;
+        if trailing_bytes > 0 {
+            report
+                .problems
+                .push(DntProblem::TrailingBytes(trailing_bytes));
+        }
+
+        Ok(report)
+    }
+
+    /// Reads a value and throws it away. Unlike skipping, reading notices
+    /// when the file ends early.
+    fn verify_value(&mut self, data_type: DntDataType) -> Result<(), DntError> {
+        match data_type {
+            DntDataType::String => {
+                self.read_string()?;
+            }
+            DntDataType::UInt32 | DntDataType::Int32 | DntDataType::Float32 => {
+                self.read_bytes(4)?;
+            }
+            DntDataType::Float64 => {
+                self.read_bytes(8)?;
+            }
+            DntDataType::Unknown(_) => {
+                let length = match self.unknown_width {
+                    DntUnknownWidth::LengthPrefixed => self.read_u16()? as usize,
+                    DntUnknownWidth::Fixed(width) => width,
+                };
+                self.read_bytes(length)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use crate::{DntDataType, DntFileReader, DntProblem, DntTableBuilder, DntVerifyReport};
+
+    /// A table with two rows of 8 bytes, followed by a 6-byte footer.
+    fn bytes() -> Vec<u8> {
+        DntTableBuilder::new()
+            .column("_Level", DntDataType::UInt32)
+            .row((1, 10u32))
+            .row((2, 20u32))
+            .build()
+            .unwrap()
+            .to_bytes()
+            .unwrap()
+    }
+
+    fn verify(bytes: &[u8]) -> DntVerifyReport {
+        DntFileReader::new(Cursor::new(bytes)).verify().unwrap()
+    }
+
+    #[test]
+    fn accepts_a_complete_file() {
+        let report = verify(&bytes());
+        assert!(report.is_ok());
+        assert_eq!(report.columns, 2);
+        assert_eq!(report.rows_expected, 2);
+        assert_eq!(report.rows_read, 2);
+    }
+
+    #[test]
+    fn finds_a_truncated_row() {
+        let mut bytes = bytes();
+        let level_offset = bytes.len() as u64 - 10;
+        bytes.truncate(bytes.len() - 8);
+
+        let report = verify(&bytes);
+        assert_eq!(report.rows_read, 1);
+        assert_eq!(
+            report.problems,
+            [DntProblem::TruncatedRow {
+                row: 1,
+                column: String::from("_Level"),
+                offset: level_offset,
+            }]
+        );
+    }
+
+    #[test]
+    fn finds_a_bad_footer() {
+        let mut bytes = bytes();
+        *bytes.last_mut().unwrap() = b'X';
+
+        let report = verify(&bytes);
+        assert_eq!(report.rows_read, 2);
+        // Bytes that aren't the footer are left over after the rows.
+        assert!(matches!(
+            report.problems[..],
+            [DntProblem::Footer(_), DntProblem::TrailingBytes(6)]
+        ));
+    }
+
+    #[test]
+    fn finds_trailing_bytes() {
+        let mut bytes = bytes();
+        bytes.extend([0; 3]);
+
+        assert_eq!(verify(&bytes).problems, [DntProblem::TrailingBytes(3)]);
+    }
+
+    #[test]
+    fn reports_an_unreadable_header() {
+        let report = verify(&bytes()[..6]);
+        assert_eq!(report.columns, 0);
+        assert!(matches!(report.problems[..], [DntProblem::Header(_)]));
+    }
+}
@@ -0,0 +1,459 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+use crate::{DntColumn, DntDataType, DntError, DntField, DntRow, DntTable, DntValue};
+
+#[derive(Debug, Clone, Copy)]
+pub struct RowView<'a> {
+    head: &'a [DntColumn],
+    row: &'a DntRow,
+}
+
+impl<'a> RowView<'a> {
+    pub fn new(head: &'a [DntColumn], row: &'a DntRow) -> Self {
+        Self { head, row }
+    }
+
+    pub fn head(&self) -> &'a [DntColumn] {
+        self.head
+    }
+
+    pub fn row(&self) -> &'a DntRow {
+        self.row
+    }
+
+    pub fn get(&self, name: &str) -> Option<&'a DntValue> {
+        let index = self.head.iter().position(|column| column.text == name)?;
+        self.row.values.get(index)
+    }
+
+    pub fn get_str(&self, name: &str) -> Option<&'a str> {
+        match self.get(name)? {
+            DntValue::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn get_u32(&self, name: &str) -> Option<u32> {
+        match self.get(name)? {
+            DntValue::UInt32(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn get_i32(&self, name: &str) -> Option<i32> {
+        match self.get(name)? {
+            DntValue::Int32(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn get_f32(&self, name: &str) -> Option<f32> {
+        match self.get(name)? {
+            DntValue::Float32(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn get_f64(&self, name: &str) -> Option<f64> {
+        match self.get(name)? {
+            DntValue::Float64(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Like `get`, but a missing column is reported as
+    /// `DntError::ColumnNotFound`.
+    pub fn try_get(&self, name: &str) -> Result<&'a DntValue, DntError> {
+        self.get(name)
+            .ok_or_else(|| DntError::ColumnNotFound(name.to_owned()))
+    }
+
+    /// The value of a `String` column. A column of another type fails with
+    /// `DntError::TypeMismatch`, and a null cell with
+    /// `DntError::InvalidValue`, as do the other typed getters.
+    pub fn str(&self, name: &str) -> Result<&'a str, DntError> {
+        self.typed(name, DntDataType::String, |value| match value {
+            DntValue::String(value) => Some(value.as_str()),
+            _ => None,
+        })
+    }
+
+    pub fn u32(&self, name: &str) -> Result<u32, DntError> {
+        self.typed(name, DntDataType::UInt32, |value| match value {
+            DntValue::UInt32(value) => Some(*value),
+            _ => None,
+        })
+    }
+
+    pub fn i32(&self, name: &str) -> Result<i32, DntError> {
+        self.typed(name, DntDataType::Int32, |value| match value {
+            DntValue::Int32(value) => Some(*value),
+            _ => None,
+        })
+    }
+
+    pub fn f32(&self, name: &str) -> Result<f32, DntError> {
+        self.typed(name, DntDataType::Float32, |value| match value {
+            DntValue::Float32(value) => Some(*value),
+            _ => None,
+        })
+    }
+
+    pub fn f64(&self, name: &str) -> Result<f64, DntError> {
+        self.typed(name, DntDataType::Float64, |value| match value {
+            DntValue::Float64(value) => Some(*value),
+            _ => None,
+        })
+    }
+
+    fn typed<T>(
+        &self,
+        name: &str,
+        expected: DntDataType,
+        extract: impl FnOnce(&'a DntValue) -> Option<T>,
+    ) -> Result<T, DntError> {
+        let index = self
+            .head
+            .iter()
+            .position(|column| column.text == name)
+            .ok_or_else(|| DntError::ColumnNotFound(name.to_owned()))?;
+        let mismatch = |found| DntError::TypeMismatch {
+            column: name.to_owned(),
+            expected,
+            found,
+        };
+
+        let found = self.head[index].data_type;
+        if found != expected {
+            return Err(mismatch(found));
+        }
+
+        let value = self.row.values.get(index).ok_or_else(|| {
+            DntError::InvalidValue(format!("the row has no value for column {}", name))
+        })?;
+        if value.is_null() {
+            return Err(DntError::InvalidValue(format!("column {} is null", name)));
+        }
+        extract(value).ok_or_else(|| mismatch(value.data_type()))
+    }
+
+    /// Copies the values into a map keyed by column name.
+    pub fn to_map(&self) -> HashMap<String, DntValue> {
+        self.head
+            .iter()
+            .zip(&self.row.values)
+            .map(|(column, value)| (column.text.clone(), value.clone()))
+            .collect()
+    }
+}
+
+/// A row borrowed mutably together with the columns of its table, so its
+/// values can be set by name.
+#[derive(Debug)]
+pub struct RowViewMut<'a> {
+    head: &'a [DntColumn],
+    row: &'a mut DntRow,
+}
+
+impl<'a> RowViewMut<'a> {
+    pub fn new(head: &'a [DntColumn], row: &'a mut DntRow) -> Self {
+        Self { head, row }
+    }
+
+    /// Borrows the row for reading, with the typed getters of `RowView`.
+    pub fn view(&self) -> RowView<'_> {
+        RowView::new(self.head, self.row)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&DntValue> {
+        let index = self.head.iter().position(|column| column.text == name)?;
+        self.row.values.get(index)
+    }
+
+    /// Sets a value, checking that it matches the type of the column.
+    pub fn set(&mut self, name: &str, value: impl Into<DntValue>) -> Result<(), DntError> {
+        let value = value.into();
+        let index = self
+            .head
+            .iter()
+            .position(|column| column.text == name)
+            .ok_or_else(|| DntError::ColumnNotFound(name.to_owned()))?;
+
+        let expected = self.head[index].data_type;
+        if !value.matches(expected) {
+            return Err(DntError::TypeMismatch {
+                column: name.to_owned(),
+                expected,
+                found: value.data_type(),
+            });
+        }
+
+        let cell = self.row.values.get_mut(index).ok_or_else(|| {
+            DntError::InvalidValue(format!("row has no value for column {}", name))
+        })?;
+        *cell = value;
+        Ok(())
+    }
+}
+
+impl Index<usize> for RowView<'_> {
+    type Output = DntValue;
+
+    fn index(&self, index: usize) -> &DntValue {
+        &self.row.values[index]
+    }
+}
+
+impl Index<&str> for RowView<'_> {
+    type Output = DntValue;
+
+    fn index(&self, name: &str) -> &DntValue {
+        self.get(name)
+            .unwrap_or_else(|| panic!("no column named {}", name))
+    }
+}
+
+/// Writes the row as `{id=1, _Name="Fire", _Level=3}`. Strings are quoted so
+/// that commas and spaces in them stay readable.
+impl fmt::Display for RowView<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("{")?;
+        for (index, (column, value)) in self.head.iter().zip(&self.row.values).enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}=", column.text)?;
+            display_cell(f, value)?;
+        }
+        f.write_str("}")
+    }
+}
+
+/// Writes the values of the row as `[1, "Fire", 3]`. Use `RowView` to show
+/// the column names as well.
+impl fmt::Display for DntRow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[")?;
+        for (index, value) in self.values.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+            display_cell(f, value)?;
+        }
+        f.write_str("]")
+    }
+}
+
+pub(crate) fn display_cell(f: &mut fmt::Formatter<'_>, value: &DntValue) -> fmt::Result {
+    match value {
+        DntValue::String(value) => write!(f, "{:?}", value),
+        value => write!(f, "{}", value),
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ColumnView<'a> {
+    head: &'a [DntColumn],
+    body: &'a [DntRow],
+    index: usize,
+}
+
+impl<'a> ColumnView<'a> {
+    pub fn column(&self) -> &'a DntColumn {
+        &self.head[self.index]
+    }
+
+    pub fn len(&self) -> usize {
+        self.body.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.body.is_empty()
+    }
+
+    pub fn get(&self, row: usize) -> Option<&'a DntValue> {
+        self.body.get(row)?.values.get(self.index)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'a DntValue> + 'a {
+        let index = self.index;
+        self.body
+            .iter()
+            .filter_map(move |row| row.values.get(index))
+    }
+}
+
+impl Index<usize> for ColumnView<'_> {
+    type Output = DntValue;
+
+    fn index(&self, row: usize) -> &DntValue {
+        &self.body[row].values[self.index]
+    }
+}
+
+impl DntTable {
+    pub fn row_view(&self, index: usize) -> Option<RowView<'_>> {
+        Some(RowView::new(&self.head, self.body.get(index)?))
+    }
+
+    pub fn row_as_map(&self, index: usize) -> Option<HashMap<String, DntValue>> {
+        Some(self.row_view(index)?.to_map())
+    }
+
+    /// Copies every row into a map keyed by column name, for code that looks
+    /// values up by name rather than by position.
+    pub fn rows_as_maps(&self) -> Vec<HashMap<String, DntValue>> {
+        self.body
+            .iter()
+            .map(|row| RowView::new(&self.head, row).to_map())
+            .collect()
+    }
+
+    pub fn column_view(&self, name: &str) -> Option<ColumnView<'_>> {
+        let index = self.head.iter().position(|column| column.text == name)?;
+        Some(ColumnView {
+            head: &self.head,
+            body: &self.body,
+            index,
+        })
+    }
+
+    /// Copies the values of a column into a `Vec`, checked like
+    /// `column_iter`.
+    pub fn column<T: DntField>(&self, name: &str) -> Result<Vec<T>, DntError> {
+        Ok(self.column_iter(name)?.collect())
+    }
+
+    /// Iterates over the values of a column as `T`. The column type and every
+    /// cell are checked up front, so the iterator itself cannot fail.
+    pub fn column_iter<T: DntField>(
+        &self,
+        name: &str,
+    ) -> Result<impl Iterator<Item = T> + '_, DntError> {
+        let index = self
+            .head
+            .iter()
+            .position(|column| column.text == name)
+            .ok_or_else(|| DntError::ColumnNotFound(name.to_owned()))?;
+
+        let found = self.head[index].data_type;
+        if found != T::DATA_TYPE {
+            return Err(DntError::TypeMismatch {
+                column: name.to_owned(),
+                expected: T::DATA_TYPE,
+                found,
+            });
+        }
+
+        for (row_index, row) in self.body.iter().enumerate() {
+            let value = row.values.get(index).ok_or(DntError::InvalidRowLength {
+                row: row_index,
+                expected: self.head.len(),
+                found: row.values.len(),
+            })?;
+
+            if !value.matches(found) {
+                return Err(DntError::InvalidCell {
+                    row: row_index,
+                    column: name.to_owned(),
+                    expected: found,
+                    found: value.data_type(),
+                });
+            }
+        }
+
+        Ok(self
+            .body
+            .iter()
+            .filter_map(move |row| T::from_value(&row.values[index]).ok()))
+    }
+
+    fn column_position_or_panic(&self, name: &str) -> usize {
+        self.head
+            .iter()
+            .position(|column| column.text == name)
+            .unwrap_or_else(|| panic!("no column named {}", name))
+    }
+}
+
+impl Index<usize> for DntRow {
+    type Output = DntValue;
+
+    fn index(&self, index: usize) -> &DntValue {
+        &self.values[index]
+    }
+}
+
+impl IndexMut<usize> for DntRow {
+    fn index_mut(&mut self, index: usize) -> &mut DntValue {
+        &mut self.values[index]
+    }
+}
+
+impl Index<usize> for DntTable {
+    type Output = DntRow;
+
+    fn index(&self, index: usize) -> &DntRow {
+        &self.body[index]
+    }
+}
+
+/// Mutable indexing bypasses the id index, so it is dropped by this and by
+/// `IndexMut<(usize, &str)>` alike, and `build_id_index` builds it again.
+impl IndexMut<usize> for DntTable {
+    fn index_mut(&mut self, index: usize) -> &mut DntRow {
+        self.id_index = None;
+        &mut self.body[index]
+    }
+}
+
+impl Index<(usize, &str)> for DntTable {
+    type Output = DntValue;
+
+    fn index(&self, (row, name): (usize, &str)) -> &DntValue {
+        &self.body[row].values[self.column_position_or_panic(name)]
+    }
+}
+
+impl IndexMut<(usize, &str)> for DntTable {
+    fn index_mut(&mut self, (row, name): (usize, &str)) -> &mut DntValue {
+        let index = self.column_position_or_panic(name);
+        self.id_index = None;
+        &mut self.body[row].values[index]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DntDataType, DntTableBuilder, DntValue};
+
+    #[test]
+    fn indexes_rows_and_cells() {
+        let mut table = DntTableBuilder::new()
+            .column("_Name", DntDataType::String)
+            .row((1, "Sword"))
+            .row((2, "Shield"))
+            .build()
+            .unwrap();
+        table.build_id_index().unwrap();
+
+        assert_eq!(table[1][1], DntValue::from("Shield"));
+        table[(0, "_Name")] = DntValue::from("Axe");
+        assert_eq!(table[0].values[1], DntValue::from("Axe"));
+        assert!(!table.has_id_index());
+
+        table.build_id_index().unwrap();
+        table[1][0] = DntValue::Int32(5);
+        assert!(!table.has_id_index());
+        assert!(table.get_by_id(5).is_some());
+
+        let names = table.column_view("_Name").unwrap();
+        assert_eq!(names.len(), 2);
+        assert_eq!(names[1], DntValue::from("Shield"));
+        assert!(table.column_view("_Missing").is_none());
+    }
+}
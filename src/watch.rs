@@ -0,0 +1,200 @@
+use std::{
+    fs::File,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver},
+};
+
+use notify::{
+    event::{AccessKind, AccessMode, ModifyKind},
+    Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher,
+};
+
+use crate::{diff, DntDiff, DntError, DntFileReader, DntTable};
+
+/// A new version of a watched file.
+#[derive(Clone)]
+pub struct DntReload {
+    pub table: DntTable,
+    /// The changes since the previous version, or `None` if they could not
+    /// be computed, as for tables without valid ids.
+    pub diff: Option<DntDiff>,
+}
+
+/// Reads a file again whenever it changes on disk and sends each new
+/// version through a channel. Changes that leave the table as it was are
+/// not sent. A file caught halfway through being written may fail to read,
+/// in which case the error is sent and the next change reads it again.
+pub struct DntWatcher {
+    _watcher: RecommendedWatcher,
+    events: Receiver<Result<DntReload, DntError>>,
+}
+
+impl DntWatcher {
+    pub fn new(path: impl AsRef<Path>) -> Result<Self, DntError> {
+        Self::with_reader(path, |reader| reader)
+    }
+
+    /// Like `new`, with `configure` setting up the reader, for example with
+    /// `with_encoding`, every time the file is read. The file is read once
+    /// right away, and reading it must succeed.
+    pub fn with_reader<F>(path: impl AsRef<Path>, configure: F) -> Result<Self, DntError>
+    where
+        F: Fn(DntFileReader<File>) -> DntFileReader<File> + Send + 'static,
+    {
+        let path = path.as_ref().to_path_buf();
+        let name = path
+            .file_name()
+            .ok_or_else(|| DntError::InvalidValue(format!("{} is not a file", path.display())))?
+            .to_owned();
+        // The directory is watched rather than the file, so that files
+        // replaced by a rename, as many editors save them, are still seen.
+        let directory = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+
+        let mut previous = load(&path, &configure)?;
+        let (sender, events) = mpsc::channel();
+
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+            let event = match event {
+                Ok(event) => event,
+                Err(error) => {
+                    let _ = sender.send(Err(DntError::from(error)));
+                    return;
+                }
+            };
+            if !is_change(&event.kind)
+                || !event
+                    .paths
+                    .iter()
+                    .any(|changed| changed.file_name() == Some(&name))
+            {
+                return;
+            }
+
+            match load(&path, &configure) {
+                Ok(table) if table == previous => {}
+                Ok(table) => {
+                    let diff = diff::diff(&previous, &table).ok();
+                    previous = table.clone();
+                    let _ = sender.send(Ok(DntReload { table, diff }));
+                }
+                Err(error) => {
+                    let _ = sender.send(Err(error));
+                }
+            }
+        })?;
+        watcher.watch(&directory, RecursiveMode::NonRecursive)?;
+
+        Ok(Self {
+            _watcher: watcher,
+            events,
+        })
+    }
+
+    /// The channel the new versions and errors arrive on, for `recv` and
+    /// `recv_timeout`.
+    pub fn receiver(&self) -> &Receiver<Result<DntReload, DntError>> {
+        &self.events
+    }
+
+    /// The next new version, if one has arrived.
+    pub fn try_recv(&self) -> Option<Result<DntReload, DntError>> {
+        self.events.try_recv().ok()
+    }
+}
+
+/// Reading the file produces access events of its own, which must not cause
+/// another read.
+fn is_change(kind: &EventKind) -> bool {
+    match kind {
+        EventKind::Create(_) => true,
+        EventKind::Modify(ModifyKind::Metadata(_)) => false,
+        EventKind::Modify(_) => true,
+        EventKind::Access(AccessKind::Close(AccessMode::Write)) => true,
+        _ => false,
+    }
+}
+
+fn load<F>(path: &Path, configure: &F) -> Result<DntTable, DntError>
+where
+    F: Fn(DntFileReader<File>) -> DntFileReader<File>,
+{
+    let mut reader = configure(DntFileReader::new(File::open(path)?));
+    reader.read()?;
+    Ok(std::mem::take(reader.data()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, time::Duration};
+
+    use notify::event::{AccessKind, AccessMode, CreateKind, DataChange, MetadataKind, ModifyKind};
+    use notify::EventKind;
+
+    use super::{is_change, DntWatcher};
+    use crate::{DntDataType, DntTableBuilder, DntValue};
+
+    #[test]
+    fn only_writes_count_as_changes() {
+        assert!(is_change(&EventKind::Create(CreateKind::File)));
+        assert!(is_change(&EventKind::Modify(ModifyKind::Data(
+            DataChange::Content
+        ))));
+        assert!(is_change(&EventKind::Access(AccessKind::Close(
+            AccessMode::Write
+        ))));
+
+        assert!(!is_change(&EventKind::Modify(ModifyKind::Metadata(
+            MetadataKind::AccessTime
+        ))));
+        assert!(!is_change(&EventKind::Access(AccessKind::Open(
+            AccessMode::Read
+        ))));
+        assert!(!is_change(&EventKind::Access(AccessKind::Close(
+            AccessMode::Read
+        ))));
+    }
+
+    #[test]
+    fn sends_only_rewrites_that_change_the_table() {
+        let bytes = |level: u32| {
+            DntTableBuilder::new()
+                .column("_Level", DntDataType::UInt32)
+                .row((1, level))
+                .build()
+                .unwrap()
+                .to_bytes()
+                .unwrap()
+        };
+        let dir = std::env::temp_dir().join(format!("dnt-watch-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("table.dnt");
+        // Replaced by a rename, so the watcher never reads a half-written file.
+        let save = |level: u32| {
+            fs::write(dir.join("table.tmp"), bytes(level)).unwrap();
+            fs::rename(dir.join("table.tmp"), &path).unwrap();
+        };
+        save(10);
+
+        let watcher = DntWatcher::new(&path).unwrap();
+
+        save(10);
+        assert!(watcher
+            .receiver()
+            .recv_timeout(Duration::from_millis(500))
+            .is_err());
+
+        save(20);
+        let reload = watcher
+            .receiver()
+            .recv_timeout(Duration::from_secs(5))
+            .unwrap()
+            .unwrap();
+        assert_eq!(reload.table.body[0][1], DntValue::UInt32(20));
+        assert_eq!(reload.diff.unwrap().changed.len(), 1);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
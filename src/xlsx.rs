@@ -0,0 +1,303 @@
+use std::path::Path;
+
+use calamine::{open_workbook_auto, Data, Reader};
+use rust_xlsxwriter::Workbook;
+
+use crate::{
+    bytes::{parse_hex, to_hex},
+    DntColumn, DntDataType, DntError, DntRow, DntTable, DntValue,
+};
+
+impl DntTable {
+    pub fn to_xlsx(&self, path: impl AsRef<Path>, sheet: &str) -> Result<(), DntError> {
+        export_xlsx(path, [(sheet, self)])
+    }
+
+    pub fn from_xlsx(path: impl AsRef<Path>, sheet: &str) -> Result<DntTable, DntError> {
+        let (names, rows) = read_sheet(path, sheet)?;
+
+        let head = names
+            .iter()
+            .enumerate()
+            .map(|(index, name)| {
+                let data_type = if index == 0 {
+                    DntDataType::Int32
+                } else {
+                    infer_data_type(rows.iter().map(|row| &row[index]))
+                };
+                DntColumn::new(name, data_type)
+            })
+            .collect::<Vec<DntColumn>>();
+
+        let body = parse_rows(&head, &rows, |index| index)?;
+
+        Ok(DntTable::from_parts(head, body))
+    }
+
+    pub fn from_xlsx_with_head(
+        path: impl AsRef<Path>,
+        sheet: &str,
+        head: &[DntColumn],
+    ) -> Result<DntTable, DntError> {
+        let (names, rows) = read_sheet(path, sheet)?;
+
+        if let Some(name) = names
+            .iter()
+            .find(|name| !head.iter().any(|column| &column.text == *name))
+        {
+            return Err(DntError::InvalidHeader(format!(
+                "unexpected sheet column: {}",
+                name
+            )));
+        }
+
+        let indexes = head
+            .iter()
+            .map(|column| {
+                names
+                    .iter()
+                    .position(|name| name == &column.text)
+                    .ok_or_else(|| DntError::ColumnNotFound(column.text.to_owned()))
+            })
+            .collect::<Result<Vec<usize>, DntError>>()?;
+
+        let head = head.to_vec();
+        let body = parse_rows(&head, &rows, |index| indexes[index])?;
+
+        Ok(DntTable::from_parts(head, body))
+    }
+}
+
+pub fn export_xlsx<'a, I>(path: impl AsRef<Path>, tables: I) -> Result<(), DntError>
+where
+    I: IntoIterator<Item = (&'a str, &'a DntTable)>,
+{
+    let mut workbook = Workbook::new();
+
+    for (name, table) in tables {
+        let worksheet = workbook.add_worksheet();
+        worksheet.set_name(name)?;
+
+        for (column_index, column) in table.head.iter().enumerate() {
+            worksheet.write_string(0, column_index as u16, &column.text)?;
+        }
+
+        for (row_index, row) in table.body.iter().enumerate() {
+            let row_index = row_index as u32 + 1;
+
+            for (column_index, value) in row.values.iter().enumerate() {
+                let column_index = column_index as u16;
+
+                match value {
+                    DntValue::String(value) => {
+                        worksheet.write_string(row_index, column_index, value)?;
+                    }
+                    DntValue::UInt32(value) => {
+                        worksheet.write_number(row_index, column_index, *value)?;
+                    }
+                    DntValue::Int32(value) => {
+                        worksheet.write_number(row_index, column_index, *value)?;
+                    }
+                    DntValue::Float32(value) => {
+                        let value = value.to_string().parse::<f64>().unwrap_or(f64::NAN);
+                        worksheet.write_number(row_index, column_index, value)?;
+                    }
+                    DntValue::Float64(value) => {
+                        worksheet.write_number(row_index, column_index, *value)?;
+                    }
+                    DntValue::Bytes(value) => {
+                        worksheet.write_string(row_index, column_index, to_hex(value))?;
+                    }
+                    DntValue::Null => {}
+                }
+            }
+        }
+    }
+
+    workbook.save(path)?;
+    Ok(())
+}
+
+fn read_sheet(
+    path: impl AsRef<Path>,
+    sheet: &str,
+) -> Result<(Vec<String>, Vec<Vec<Data>>), DntError> {
+    let mut workbook = open_workbook_auto(path)?;
+    let range = workbook.worksheet_range(sheet)?;
+
+    let mut rows = range.rows();
+
+    let names = rows
+        .next()
+        .map(|row| {
+            row.iter()
+                .map(|cell| cell.to_string())
+                .collect::<Vec<String>>()
+        })
+        .unwrap_or_default();
+
+    if names.is_empty() {
+        return Err(DntError::InvalidHeader(String::from(
+            "sheet has no id column",
+        )));
+    }
+
+    let rows = rows
+        .map(|row| {
+            let mut row = row.to_vec();
+            row.resize(names.len(), Data::Empty);
+            row
+        })
+        .collect();
+
+    Ok((names, rows))
+}
+
+fn infer_data_type<'a>(mut cells: impl Iterator<Item = &'a Data> + Clone) -> DntDataType {
+    if cells
+        .clone()
+        .all(|cell| integer(cell).is_some_and(|value| i32::try_from(value).is_ok()))
+    {
+        DntDataType::Int32
+    } else if cells
+        .clone()
+        .all(|cell| integer(cell).is_some_and(|value| u32::try_from(value).is_ok()))
+    {
+        DntDataType::UInt32
+    } else if cells.all(|cell| matches!(cell, Data::Int(_) | Data::Float(_))) {
+        DntDataType::Float32
+    } else {
+        DntDataType::String
+    }
+}
+
+fn integer(cell: &Data) -> Option<i64> {
+    match cell {
+        Data::Int(value) => Some(*value),
+        Data::Float(value) if value.fract() == 0.0 && value.abs() <= i64::MAX as f64 => {
+            Some(*value as i64)
+        }
+        _ => None,
+    }
+}
+
+fn float(cell: &Data) -> Option<f64> {
+    match cell {
+        Data::Int(value) => Some(*value as f64),
+        Data::Float(value) => Some(*value),
+        Data::String(value) => value.parse().ok(),
+        _ => None,
+    }
+}
+
+fn parse_rows(
+    head: &[DntColumn],
+    rows: &[Vec<Data>],
+    index: impl Fn(usize) -> usize,
+) -> Result<Vec<DntRow>, DntError> {
+    rows.iter()
+        .enumerate()
+        .map(|(row_index, row)| {
+            let values = head
+                .iter()
+                .enumerate()
+                .map(|(column_index, column)| {
+                    let cell = &row[index(column_index)];
+                    parse_value(cell, column.data_type).ok_or_else(|| {
+                        DntError::InvalidValue(format!(
+                            "row {}, column {}: {:?} is not a valid {:?}",
+                            row_index, column.text, cell, column.data_type
+                        ))
+                    })
+                })
+                .collect::<Result<Vec<DntValue>, DntError>>()?;
+
+            Ok(DntRow { values })
+        })
+        .collect()
+}
+
+fn parse_value(cell: &Data, data_type: DntDataType) -> Option<DntValue> {
+    match data_type {
+        DntDataType::String => Some(DntValue::String(cell.to_string())),
+        DntDataType::UInt32 => integer(cell)
+            .or_else(|| cell.to_string().parse().ok())
+            .and_then(|value| u32::try_from(value).ok())
+            .map(DntValue::UInt32),
+        DntDataType::Int32 => integer(cell)
+            .or_else(|| cell.to_string().parse().ok())
+            .and_then(|value| i32::try_from(value).ok())
+            .map(DntValue::Int32),
+        DntDataType::Float32 => float(cell).map(|value| DntValue::Float32(value as f32)),
+        DntDataType::Float64 => float(cell).map(DntValue::Float64),
+        DntDataType::Unknown(_) => parse_hex(&cell.to_string()).map(DntValue::Bytes),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use crate::{export_xlsx, DntDataType, DntTable, DntTableBuilder};
+
+    fn directory(name: &str) -> PathBuf {
+        let directory =
+            std::env::temp_dir().join(format!("dnt-xlsx-{}-{}", name, std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        directory
+    }
+
+    fn table() -> DntTable {
+        DntTableBuilder::new()
+            .column("_Name", DntDataType::String)
+            .column("_Level", DntDataType::Int32)
+            .column("_Price", DntDataType::UInt32)
+            .column("_Rate", DntDataType::Float32)
+            .column("_Data", DntDataType::Unknown(9))
+            .row((1, "Sword", -1, 3_000_000_000u32, 0.5f32, vec![0xab, 0x01]))
+            .row((2, "12", 2, 20u32, 1.25f32, vec![]))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn reads_back_with_the_same_head() {
+        let directory = directory("head");
+        let path = directory.join("items.xlsx");
+        table().to_xlsx(&path, "items").unwrap();
+
+        let read = DntTable::from_xlsx_with_head(&path, "items", &table().head).unwrap();
+        assert!(read == table());
+
+        fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn infers_the_types_of_the_columns() {
+        let directory = directory("infer");
+        let path = directory.join("items.xlsx");
+        export_xlsx(&path, [("items", &table()), ("copy", &table())]).unwrap();
+
+        let read = DntTable::from_xlsx(&path, "copy").unwrap();
+        let types = read
+            .head
+            .iter()
+            .map(|column| column.data_type)
+            .collect::<Vec<DntDataType>>();
+        assert_eq!(
+            types,
+            [
+                DntDataType::Int32,
+                DntDataType::String,
+                DntDataType::Int32,
+                DntDataType::UInt32,
+                DntDataType::Float32,
+                DntDataType::String
+            ]
+        );
+        assert_eq!(read.body[1].values[1], "12".into());
+        assert!(DntTable::from_xlsx(&path, "missing").is_err());
+
+        fs::remove_dir_all(directory).unwrap();
+    }
+}
@@ -0,0 +1,45 @@
+use crate::{DntColumn, DntError, DntTable, ExportOptions};
+
+impl DntTable {
+    /// Writes the rows as a sequence of mappings keyed by column name, like
+    /// JSON, so that tables can be edited by hand and read back with
+    /// `from_yaml`.
+    pub fn to_yaml(&self) -> Result<String, DntError> {
+        self.to_yaml_with(&ExportOptions::default())
+    }
+
+    /// Writes YAML with floats formatted by `options`. As with JSON, only
+    /// `float_precision`, `trim_trailing_zeros` and `columns` apply.
+    pub fn to_yaml_with(&self, options: &ExportOptions) -> Result<String, DntError> {
+        Ok(serde_yaml_ng::to_string(&self.json_rows(options)?)?)
+    }
+
+    pub fn from_yaml(yaml: &str) -> Result<DntTable, DntError> {
+        DntTable::from_json_rows(serde_yaml_ng::from_str(yaml)?)
+    }
+
+    pub fn from_yaml_with_head(yaml: &str, head: &[DntColumn]) -> Result<DntTable, DntError> {
+        DntTable::from_json_rows_with_head(serde_yaml_ng::from_str(yaml)?, head)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DntDataType, DntTable, DntTableBuilder};
+
+    #[test]
+    fn round_trips_with_the_head() {
+        let table = DntTableBuilder::new()
+            .column("_Name", DntDataType::String)
+            .column("_Rate", DntDataType::Float32)
+            .row((1, "Sword: the first", 0.5f32))
+            .row((2, "- Shield", 1.5f32))
+            .build()
+            .unwrap();
+
+        let yaml = table.to_yaml().unwrap();
+        assert!(yaml.starts_with("- id: 1\n"));
+        let read = DntTable::from_yaml_with_head(&yaml, &table.head).unwrap();
+        assert!(read == table);
+    }
+}
@@ -0,0 +1,117 @@
+use std::{
+    fs,
+    io::Write,
+    path::PathBuf,
+    process::{Command, Output, Stdio},
+};
+
+use dnt_file_reader_writer::{DntDataType, DntTable, DntTableBuilder};
+
+const CSV: &str = "id,_Name,_Price\n1,Sword,10\n2,Shield,20\n";
+
+/// `_Price` is a UInt32 column, which reading the CSV alone would take for
+/// an Int32 column.
+fn table() -> DntTable {
+    DntTableBuilder::new()
+        .column("_Name", DntDataType::String)
+        .column("_Price", DntDataType::UInt32)
+        .row((1, "Sword", 10u32))
+        .row((2, "Shield", 20u32))
+        .build()
+        .unwrap()
+}
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("dnt-cli-{name}-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Runs the binary with `stdin` as its standard input and checks that it
+/// succeeded.
+fn dnt(args: &[&str], stdin: &[u8]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_dnt"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(stdin).unwrap();
+
+    let output = child.wait_with_output().unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    output
+}
+
+#[test]
+fn prints_the_header() {
+    let dir = temp_dir("info");
+    let input = dir.join("table.dnt");
+    fs::write(&input, table().to_bytes().unwrap()).unwrap();
+
+    let expected = "2 rows, 3 columns\nid\tInt32\t3\n_Name\tString\t1\n_Price\tUInt32\t2\n";
+    let output = dnt(&["info", input.to_str().unwrap()], b"");
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), expected);
+
+    let output = dnt(&["info", "-"], &table().to_bytes().unwrap());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), expected);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn converts_to_csv() {
+    let dir = temp_dir("to-csv");
+    let input = dir.join("table.dnt");
+    let output = dir.join("table.csv");
+    fs::write(&input, table().to_bytes().unwrap()).unwrap();
+
+    dnt(
+        &["to-csv", input.to_str().unwrap(), output.to_str().unwrap()],
+        b"",
+    );
+    assert_eq!(fs::read_to_string(&output).unwrap(), CSV);
+
+    let stdout = dnt(&["to-csv", "-", "-"], &table().to_bytes().unwrap()).stdout;
+    assert_eq!(String::from_utf8(stdout).unwrap(), CSV);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn converts_from_csv_like_an_existing_table() {
+    let dir = temp_dir("from-csv");
+    let like = dir.join("like.dnt");
+    let output = dir.join("table.dnt");
+    fs::write(&like, table().to_bytes().unwrap()).unwrap();
+
+    dnt(
+        &[
+            "from-csv",
+            "-",
+            output.to_str().unwrap(),
+            "--like",
+            like.to_str().unwrap(),
+        ],
+        CSV.as_bytes(),
+    );
+    assert!(DntTable::parse(&fs::read(&output).unwrap()).unwrap() == table());
+
+    let stdout = dnt(
+        &["from-csv", "-", "-", "--like", like.to_str().unwrap()],
+        CSV.as_bytes(),
+    )
+    .stdout;
+    assert!(DntTable::parse(&stdout).unwrap() == table());
+
+    // Without `--like` the types are inferred.
+    let stdout = dnt(&["from-csv", "-", "-"], CSV.as_bytes()).stdout;
+    assert!(DntTable::parse(&stdout).unwrap() != table());
+
+    fs::remove_dir_all(dir).unwrap();
+}